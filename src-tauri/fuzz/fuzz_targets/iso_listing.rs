@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Malformed or malicious ISOs feed attacker-controlled text into these
+// parsers via `7z l`'s listing output; they must never panic or hang no
+// matter what's in the file.
+fuzz_target!(|data: &[u8]| {
+    let Ok(contents) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = wowusb_ds9::iso_listing::os_type_from_listing(contents);
+    let _ = wowusb_ds9::iso_listing::architecture_from_listing(contents);
+    let _ = wowusb_ds9::iso_listing::has_large_file_in_listing(contents);
+    let _ = wowusb_ds9::iso_listing::boot_support_from_listing(contents);
+    let _ = wowusb_ds9::iso_listing::windows_version_from_idwbinfo(contents);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = wowusb_ds9::units::parse_size_string(s);
+    }
+
+    if data.len() >= 8 {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&data[..8]);
+        let _ = wowusb_ds9::units::format_size_bytes(u64::from_le_bytes(bytes));
+    }
+});
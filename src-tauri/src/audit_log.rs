@@ -0,0 +1,135 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+/// Name of the append-only audit log written to the root of every stick
+/// WowUSB creates, alongside [`crate::provenance::PROVENANCE_MANIFEST_FILENAME`].
+pub const AUDIT_LOG_FILENAME: &str = "wowusb_audit.jsonl";
+
+/// A modification made to a stick, for chain-of-custody reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    IsoWritten,
+    IsoRemoved,
+    Reverified,
+    Wiped,
+    BootloaderRepaired,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub action: AuditAction,
+    pub operator: Option<String>,
+    pub details: String,
+}
+
+impl AuditEvent {
+    pub fn new(action: AuditAction, operator: Option<String>, details: impl Into<String>) -> Self {
+        Self {
+            timestamp: chrono::Utc::now(),
+            action,
+            operator,
+            details: details.into(),
+        }
+    }
+}
+
+/// Where the local (host-side) audit history is kept, mirroring
+/// [`crate::policy::DevicePolicy`]'s well-known per-platform locations.
+#[cfg(target_os = "windows")]
+pub fn local_history_path() -> std::path::PathBuf {
+    std::path::PathBuf::from(r"C:\ProgramData\WowUSB\audit_history.jsonl")
+}
+
+#[cfg(target_os = "macos")]
+pub fn local_history_path() -> std::path::PathBuf {
+    std::path::PathBuf::from("/Library/Application Support/WowUSB/audit_history.jsonl")
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn local_history_path() -> std::path::PathBuf {
+    std::path::PathBuf::from("/etc/wowusb/audit_history.jsonl")
+}
+
+/// Appends [`AuditEvent`]s as newline-delimited JSON, to both the stick
+/// itself (so the media carries its own chain-of-custody record) and a
+/// local history file (so the record survives a lost or reformatted
+/// stick).
+pub struct AuditLog;
+
+impl AuditLog {
+    /// Append `event` to `wowusb_audit.jsonl` at the root of a mounted stick.
+    pub fn append_to_stick(stick_root: impl AsRef<Path>, event: &AuditEvent) -> Result<()> {
+        Self::append_to(stick_root.as_ref().join(AUDIT_LOG_FILENAME), event)
+    }
+
+    /// Append `event` to the local, host-side audit history.
+    pub fn append_to_local_history(history_path: impl AsRef<Path>, event: &AuditEvent) -> Result<()> {
+        Self::append_to(history_path, event)
+    }
+
+    fn append_to(path: impl AsRef<Path>, event: &AuditEvent) -> Result<()> {
+        let line = serde_json::to_string(event)
+            .map_err(|e| crate::error::WowUsbError::configuration(format!("Failed to serialize audit event: {}", e)))?;
+
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path.as_ref())?;
+        writeln!(file, "{}", line)?;
+
+        Ok(())
+    }
+
+    /// Read back every event previously appended, for display or export.
+    pub fn read_all(path: impl AsRef<Path>) -> Result<Vec<AuditEvent>> {
+        if !path.as_ref().exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        let mut events = Vec::new();
+        for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+            let event = serde_json::from_str(line)
+                .map_err(|e| crate::error::WowUsbError::configuration(format!("Invalid audit log entry: {}", e)))?;
+            events.push(event);
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appended_events_round_trip() {
+        let path = std::env::temp_dir().join(format!("wowusb_audit_test_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let event = AuditEvent::new(AuditAction::IsoWritten, Some("alice".to_string()), "ubuntu-24.04.iso");
+        AuditLog::append_to_local_history(&path, &event).unwrap();
+
+        let events = AuditLog::read_all(&path).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].action, AuditAction::IsoWritten);
+        assert_eq!(events[0].operator.as_deref(), Some("alice"));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn missing_log_reads_as_empty() {
+        let path = std::env::temp_dir().join("wowusb_audit_test_missing.jsonl");
+        std::fs::remove_file(&path).ok();
+        assert!(AuditLog::read_all(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::config::CreateConfig;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SlotStatus {
+    WaitingForDevice,
+    Imaging,
+    Verifying,
+    Done,
+    Failed(String),
+    /// This device's serial was already imaged earlier in this batch;
+    /// left untouched so the operator notices and swaps it for a fresh
+    /// stick instead of the run silently re-imaging it.
+    SkippedDuplicate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotState {
+    pub device: String,
+    #[serde(default)]
+    pub serial: Option<String>,
+    pub status: SlotStatus,
+}
+
+/// A "duplicator" job: the user arms a single ISO + config once, and every
+/// newly inserted USB device matching `min_size_bytes` is automatically
+/// imaged and verified, turning the host into a low-volume USB duplicator.
+pub struct DuplicatorJob {
+    pub source_path: String,
+    pub config: CreateConfig,
+    pub min_size_bytes: u64,
+    armed: Arc<RwLock<bool>>,
+    slots: Arc<RwLock<HashMap<String, SlotState>>>,
+    /// Serials (see [`crate::disk::PlatformDiskOps::device_serial`]) of
+    /// devices already imaged to completion in this batch, so a re-inserted
+    /// stick is flagged instead of imaged again.
+    imaged_serials: Arc<RwLock<HashSet<String>>>,
+}
+
+impl DuplicatorJob {
+    pub fn new(source_path: String, config: CreateConfig, min_size_bytes: u64) -> Self {
+        Self {
+            source_path,
+            config,
+            min_size_bytes,
+            armed: Arc::new(RwLock::new(false)),
+            slots: Arc::new(RwLock::new(HashMap::new())),
+            imaged_serials: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    pub async fn arm(&self) {
+        *self.armed.write().await = true;
+    }
+
+    pub async fn disarm(&self) {
+        *self.armed.write().await = false;
+    }
+
+    pub async fn is_armed(&self) -> bool {
+        *self.armed.read().await
+    }
+
+    /// Called by the device-poll loop whenever a new candidate device
+    /// appears; a slot is created if none exists for it yet. `serial` is
+    /// the device's hardware serial if the platform backend could
+    /// determine one; when it matches a serial already imaged in this
+    /// batch, the slot is created as [`SlotStatus::SkippedDuplicate`]
+    /// instead of [`SlotStatus::WaitingForDevice`].
+    pub async fn note_device_inserted(&self, device: &str, serial: Option<&str>) -> Result<()> {
+        if !self.is_armed().await {
+            return Ok(());
+        }
+
+        let mut slots = self.slots.write().await;
+        if slots.contains_key(device) {
+            return Ok(());
+        }
+
+        let is_duplicate = match serial {
+            Some(serial) => self.imaged_serials.read().await.contains(serial),
+            None => false,
+        };
+
+        slots.insert(
+            device.to_string(),
+            SlotState {
+                device: device.to_string(),
+                serial: serial.map(|s| s.to_string()),
+                status: if is_duplicate { SlotStatus::SkippedDuplicate } else { SlotStatus::WaitingForDevice },
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Updates a slot's status, and if it just finished imaging
+    /// successfully, records its serial as imaged so a later re-insertion
+    /// of the same stick is caught by [`Self::note_device_inserted`].
+    pub async fn set_slot_status(&self, device: &str, status: SlotStatus) {
+        let mut slots = self.slots.write().await;
+        let Some(slot) = slots.get_mut(device) else { return };
+        slot.status = status.clone();
+
+        if status == SlotStatus::Done {
+            if let Some(serial) = slot.serial.clone() {
+                drop(slots);
+                self.imaged_serials.write().await.insert(serial);
+            }
+        }
+    }
+
+    pub async fn slot_states(&self) -> Vec<SlotState> {
+        self.slots.read().await.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::target_os::TargetOs;
+
+    fn config() -> CreateConfig {
+        CreateConfig {
+            target_os: TargetOs::LinuxLive,
+            filesystem: "fat32".to_string(),
+            drive_label: "WOWUSB".to_string(),
+            wintogo_enabled: false,
+            hardware_profile: crate::wintogo_profiles::HardwareProfile::default(),
+            enable_multiboot: false,
+            enable_persistence: false,
+            persistence_mode: crate::persistence_overlay::PersistenceMode::default(),
+            persistence_overlay_size_mb: None,
+            menu_appearance: crate::bootloader::MenuAppearance::default(),
+            ei_config: None,
+            product_key: None,
+            oem_folder_path: None,
+            compact_os_enabled: false,
+            sync_policy: crate::write_cache::SyncPolicy::default(),
+            file_injections: Vec::new(),
+            suspend_realtime_scanning: false,
+            clean_os_litter: false,
+            write_mode: crate::config::WriteMode::Extract,
+            undo_grace_period_seconds: None,
+            io_throttle: None,
+            encryption: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn reinserted_serial_is_flagged_as_duplicate() {
+        let job = DuplicatorJob::new("ubuntu.iso".to_string(), config(), 4_000_000_000);
+        job.arm().await;
+
+        job.note_device_inserted("/dev/sdb", Some("SERIAL-1")).await.unwrap();
+        job.set_slot_status("/dev/sdb", SlotStatus::Done).await;
+
+        // Same stick comes back under a different device node.
+        job.note_device_inserted("/dev/sdc", Some("SERIAL-1")).await.unwrap();
+
+        let slots = job.slot_states().await;
+        let reinserted = slots.iter().find(|s| s.device == "/dev/sdc").unwrap();
+        assert_eq!(reinserted.status, SlotStatus::SkippedDuplicate);
+    }
+
+    #[tokio::test]
+    async fn distinct_serials_are_not_flagged() {
+        let job = DuplicatorJob::new("ubuntu.iso".to_string(), config(), 4_000_000_000);
+        job.arm().await;
+
+        job.note_device_inserted("/dev/sdb", Some("SERIAL-1")).await.unwrap();
+        job.set_slot_status("/dev/sdb", SlotStatus::Done).await;
+        job.note_device_inserted("/dev/sdc", Some("SERIAL-2")).await.unwrap();
+
+        let slots = job.slot_states().await;
+        let other = slots.iter().find(|s| s.device == "/dev/sdc").unwrap();
+        assert_eq!(other.status, SlotStatus::WaitingForDevice);
+    }
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+/// A kernel/GRUB boot parameter this crate recommends for a distro family's
+/// live media, keyed by substring match against
+/// [`crate::iso::IsoInfo::os_type`]. Unlike [`crate::iso_quirks::QuirkRule`],
+/// these aren't workarounds for something broken — they're defaults that
+/// measurably improve out-of-the-box boot success (faster boot off a slow
+/// stick, avoiding a black screen on unsupported GPUs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootParamRule {
+    pub id: String,
+    pub description: String,
+    /// Matches if `os_type` contains any of these substrings
+    /// (case-insensitive).
+    pub os_type_contains_any: Vec<String>,
+    pub params: Vec<String>,
+    /// Whether [`MultibootManager`](crate::manifest::MultibootManager)
+    /// should append this automatically, versus only surfacing it as a
+    /// suggestion — e.g. `nomodeset` helps on unsupported GPUs but can also
+    /// disable ones that already work, so it's opt-in.
+    pub auto_apply: bool,
+}
+
+impl BootParamRule {
+    fn matches(&self, os_type: &str) -> bool {
+        let lowered = os_type.to_lowercase();
+        self.os_type_contains_any
+            .iter()
+            .any(|needle| lowered.contains(&needle.to_lowercase()))
+    }
+}
+
+/// The set of boot parameter recommendations currently in effect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootParamsDatabase {
+    rules: Vec<BootParamRule>,
+}
+
+impl BootParamsDatabase {
+    /// Known-good boot parameters this crate ships out of the box.
+    pub fn builtin() -> Self {
+        Self {
+            rules: vec![
+                BootParamRule {
+                    id: "debian-family-toram".to_string(),
+                    description: "Copy the live squashfs into RAM (toram) so the session survives the stick being unplugged and isn't bottlenecked by USB read speed".to_string(),
+                    os_type_contains_any: vec!["ubuntu".to_string(), "debian".to_string()],
+                    params: vec!["toram".to_string()],
+                    auto_apply: true,
+                },
+                BootParamRule {
+                    id: "arch-live-copytoram".to_string(),
+                    description: "archiso's equivalent of toram: copy the live filesystem into RAM instead of reading it off the stick on every access".to_string(),
+                    os_type_contains_any: vec!["arch".to_string()],
+                    params: vec!["copytoram=y".to_string()],
+                    auto_apply: true,
+                },
+                BootParamRule {
+                    id: "fedora-live-ram".to_string(),
+                    description: "dracut's equivalent of toram for Fedora's live media".to_string(),
+                    os_type_contains_any: vec!["fedora".to_string()],
+                    params: vec!["rd.live.ram=1".to_string()],
+                    auto_apply: true,
+                },
+                BootParamRule {
+                    id: "nvidia-nomodeset-suggestion".to_string(),
+                    description: "If the live session boots to a black screen on Nvidia hardware, add nomodeset to fall back to a generic framebuffer driver".to_string(),
+                    os_type_contains_any: vec![
+                        "ubuntu".to_string(),
+                        "debian".to_string(),
+                        "fedora".to_string(),
+                        "arch".to_string(),
+                        "linux".to_string(),
+                    ],
+                    params: vec!["nomodeset".to_string()],
+                    auto_apply: false,
+                },
+            ],
+        }
+    }
+
+    /// Rules matching `os_type`, in declaration order.
+    pub fn matching<'a>(&'a self, os_type: &str) -> Vec<&'a BootParamRule> {
+        self.rules.iter().filter(|rule| rule.matches(os_type)).collect()
+    }
+
+    /// Kernel parameters that should be appended automatically for
+    /// `os_type` — the `auto_apply` subset of [`Self::matching`].
+    pub fn auto_apply_params(&self, os_type: &str) -> Vec<String> {
+        self.matching(os_type)
+            .into_iter()
+            .filter(|rule| rule.auto_apply)
+            .flat_map(|rule| rule.params.clone())
+            .collect()
+    }
+}
+
+impl Default for BootParamsDatabase {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ubuntu_gets_toram_and_nomodeset_suggestion() {
+        let db = BootParamsDatabase::builtin();
+        let matches = db.matching("Ubuntu");
+        assert!(matches.iter().any(|r| r.id == "debian-family-toram"));
+        assert!(matches.iter().any(|r| r.id == "nvidia-nomodeset-suggestion"));
+    }
+
+    #[test]
+    fn auto_apply_params_excludes_suggestions() {
+        let db = BootParamsDatabase::builtin();
+        assert_eq!(db.auto_apply_params("Ubuntu"), vec!["toram".to_string()]);
+    }
+
+    #[test]
+    fn windows_gets_no_recommendations() {
+        let db = BootParamsDatabase::builtin();
+        assert!(db.matching("Windows").is_empty());
+    }
+
+    #[test]
+    fn arch_and_fedora_use_their_own_ram_boot_param() {
+        let db = BootParamsDatabase::builtin();
+        assert_eq!(db.auto_apply_params("Arch Linux"), vec!["copytoram=y".to_string()]);
+        assert_eq!(db.auto_apply_params("Fedora"), vec!["rd.live.ram=1".to_string()]);
+    }
+}
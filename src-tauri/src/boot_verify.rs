@@ -0,0 +1,149 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Above this size, only [`BOOT_CRITICAL_HEADER_BYTES`] of a file is hashed
+/// rather than the whole thing — `install.wim` alone can be several GB,
+/// and a corrupted copy almost always shows up in the header (truncated
+/// transfer, wrong file entirely) rather than only in trailing bytes.
+const BOOT_CRITICAL_HEADER_BYTES: u64 = 4 * 1024 * 1024;
+
+struct BootCriticalFile {
+    /// Path relative to both the mounted source ISO and the written stick.
+    relative_path: &'static str,
+    hash_whole_file: bool,
+}
+
+/// Paths this crate knows are load-bearing for boot across the media types
+/// it supports; a stick missing or corrupting one of these won't boot even
+/// if every other byte on it is correct. Not every entry applies to every
+/// ISO — [`quick_verify`] silently skips ones absent from the source.
+const BOOT_CRITICAL_FILES: &[BootCriticalFile] = &[
+    BootCriticalFile { relative_path: "bootmgr", hash_whole_file: true },
+    BootCriticalFile { relative_path: "efi/boot/bootx64.efi", hash_whole_file: true },
+    BootCriticalFile { relative_path: "efi/boot/bootia32.efi", hash_whole_file: true },
+    BootCriticalFile { relative_path: "efi/boot/bootaa64.efi", hash_whole_file: true },
+    BootCriticalFile { relative_path: "sources/boot.wim", hash_whole_file: true },
+    BootCriticalFile { relative_path: "sources/install.wim", hash_whole_file: false },
+    BootCriticalFile { relative_path: "casper/vmlinuz", hash_whole_file: true },
+    BootCriticalFile { relative_path: "casper/initrd", hash_whole_file: true },
+    BootCriticalFile { relative_path: "live/vmlinuz", hash_whole_file: true },
+    BootCriticalFile { relative_path: "live/initrd.img", hash_whole_file: true },
+];
+
+/// Outcome of comparing one boot-critical file between the source ISO and
+/// the written stick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootFileVerification {
+    pub relative_path: String,
+    pub matches: bool,
+}
+
+/// A ~30-second sanity check that hashes only the handful of files a boot
+/// actually depends on — EFI binaries, `bootmgr`, kernel/initrd, and just
+/// the header of `install.wim` rather than all of it — instead of every
+/// byte [`crate::disk::DiskManager::create_bootable_usb`] wrote, so a user
+/// can catch a corrupted write without waiting for a full verification
+/// pass. Files the source ISO doesn't ship are skipped rather than treated
+/// as a mismatch.
+pub async fn quick_verify(mounted_iso_root: &str, stick_root: &str) -> Result<Vec<BootFileVerification>> {
+    let mut results = Vec::new();
+
+    for file in BOOT_CRITICAL_FILES {
+        let source = Path::new(mounted_iso_root).join(file.relative_path);
+        let dest = Path::new(stick_root).join(file.relative_path);
+        if !source.exists() || !dest.exists() {
+            continue;
+        }
+
+        let source = source.to_string_lossy().to_string();
+        let dest = dest.to_string_lossy().to_string();
+
+        let matches = if file.hash_whole_file {
+            let (source_hash, dest_hash) = crate::hashing::sha256_pair(&source, &dest).await?;
+            source_hash == dest_hash
+        } else {
+            let source_hash = crate::hashing::sha256_prefix(&source, BOOT_CRITICAL_HEADER_BYTES).await?;
+            let dest_hash = crate::hashing::sha256_prefix(&dest, BOOT_CRITICAL_HEADER_BYTES).await?;
+            source_hash == dest_hash
+        };
+
+        results.push(BootFileVerification {
+            relative_path: file.relative_path.to_string(),
+            matches,
+        });
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(dir: &Path, relative_path: &str, contents: &[u8]) {
+        let path = dir.join(relative_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wowusb_boot_verify_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn detects_matching_and_corrupted_boot_files() {
+        let iso_root = temp_dir("iso_match");
+        let stick_root = temp_dir("stick_match");
+        write_file(&iso_root, "bootmgr", b"bootmgr contents");
+        write_file(&stick_root, "bootmgr", b"bootmgr contents");
+        write_file(&iso_root, "efi/boot/bootx64.efi", b"efi contents");
+        write_file(&stick_root, "efi/boot/bootx64.efi", b"CORRUPTED");
+
+        let results = quick_verify(iso_root.to_str().unwrap(), stick_root.to_str().unwrap()).await.unwrap();
+
+        let bootmgr = results.iter().find(|r| r.relative_path == "bootmgr").unwrap();
+        assert!(bootmgr.matches);
+        let efi = results.iter().find(|r| r.relative_path == "efi/boot/bootx64.efi").unwrap();
+        assert!(!efi.matches);
+
+        std::fs::remove_dir_all(&iso_root).ok();
+        std::fs::remove_dir_all(&stick_root).ok();
+    }
+
+    #[tokio::test]
+    async fn skips_files_the_iso_does_not_ship() {
+        let iso_root = temp_dir("iso_skip");
+        let stick_root = temp_dir("stick_skip");
+        std::fs::create_dir_all(&iso_root).unwrap();
+        std::fs::create_dir_all(&stick_root).unwrap();
+
+        let results = quick_verify(iso_root.to_str().unwrap(), stick_root.to_str().unwrap()).await.unwrap();
+        assert!(results.is_empty());
+
+        std::fs::remove_dir_all(&iso_root).ok();
+        std::fs::remove_dir_all(&stick_root).ok();
+    }
+
+    #[tokio::test]
+    async fn install_wim_only_hashes_the_header() {
+        let iso_root = temp_dir("iso_wim");
+        let stick_root = temp_dir("stick_wim");
+        let mut source_contents = vec![1u8; BOOT_CRITICAL_HEADER_BYTES as usize];
+        let mut dest_contents = source_contents.clone();
+        // Differ only past the header — should still be reported as matching.
+        source_contents.extend_from_slice(b"source-only-trailer");
+        dest_contents.extend_from_slice(b"dest-only-trailer");
+        write_file(&iso_root, "sources/install.wim", &source_contents);
+        write_file(&stick_root, "sources/install.wim", &dest_contents);
+
+        let results = quick_verify(iso_root.to_str().unwrap(), stick_root.to_str().unwrap()).await.unwrap();
+        let install_wim = results.iter().find(|r| r.relative_path == "sources/install.wim").unwrap();
+        assert!(install_wim.matches);
+
+        std::fs::remove_dir_all(&iso_root).ok();
+        std::fs::remove_dir_all(&stick_root).ok();
+    }
+}
@@ -0,0 +1,146 @@
+use crate::error::{WowUsbError, Result};
+use std::path::Path;
+
+/// Above this size an ISO is assumed to be a full OS installer that should
+/// boot via loopback rather than be loaded whole into RAM.
+const MEMDISK_MAX_ISO_BYTES: u64 = 64 * 1024 * 1024;
+
+/// A single entry that will be rendered into the multiboot `grub.cfg`.
+#[derive(Debug, Clone)]
+pub struct BootEntry {
+    pub title: String,
+    pub iso_path: String,
+    pub kernel_args: Option<String>,
+    /// Size of `iso_path` in bytes, used to decide whether memdisk
+    /// chainloading is a viable fallback for legacy BIOS boots.
+    pub iso_size_bytes: u64,
+}
+
+/// Menu-wide appearance and behavior, exposed through `CreateConfig` so
+/// organizations can brand deployment sticks with their own timeout,
+/// default entry and theme.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MenuAppearance {
+    pub timeout_seconds: u32,
+    pub default_entry_index: u32,
+    pub hidden_menu: bool,
+    pub theme_path: Option<String>,
+    pub background_image_path: Option<String>,
+}
+
+impl Default for MenuAppearance {
+    fn default() -> Self {
+        Self {
+            timeout_seconds: 10,
+            default_entry_index: 0,
+            hidden_menu: false,
+            theme_path: None,
+            background_image_path: None,
+        }
+    }
+}
+
+pub struct BootloaderConfigGenerator;
+
+impl BootloaderConfigGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the top-of-file grub.cfg directives controlling menu
+    /// timeout, default entry, hidden-menu mode and optional theming.
+    pub fn render_menu_header(&self, appearance: &MenuAppearance) -> String {
+        let mut header = String::new();
+
+        header.push_str(&format!("set timeout={}\n", appearance.timeout_seconds));
+        header.push_str(&format!("set default={}\n", appearance.default_entry_index));
+
+        if appearance.hidden_menu {
+            header.push_str("set timeout_style=hidden\n");
+        }
+
+        if let Some(theme) = &appearance.theme_path {
+            header.push_str(&format!("set theme={}\n", theme));
+        }
+
+        if let Some(background) = &appearance.background_image_path {
+            header.push_str(&format!("background_image {}\n", background));
+        }
+
+        header.push('\n');
+        header
+    }
+
+    /// Render the grub.cfg stanza for a single entry, preferring the
+    /// distro-provided `boot/grub/loopback.cfg` when the ISO ships one
+    /// instead of hand-writing kernel/initrd lines, and falling back to
+    /// syslinux memdisk chainloading for small legacy-boot-only ISOs.
+    pub async fn render_entry(&self, entry: &BootEntry, extracted_iso_root: &str) -> Result<String> {
+        if self.has_loopback_cfg(extracted_iso_root).await? {
+            return Ok(self.render_loopback_entry(entry));
+        }
+
+        if entry.iso_size_bytes <= MEMDISK_MAX_ISO_BYTES {
+            return Ok(self.render_memdisk_entry(entry));
+        }
+
+        Err(WowUsbError::not_implemented(
+            "Manual kernel-line generation without loopback.cfg is not yet supported",
+        ))
+    }
+
+    /// Check whether the ISO ships its own `boot/grub/loopback.cfg`.
+    pub async fn has_loopback_cfg(&self, extracted_iso_root: &str) -> Result<bool> {
+        Ok(Path::new(extracted_iso_root)
+            .join("boot/grub/loopback.cfg")
+            .exists())
+    }
+
+    fn render_loopback_entry(&self, entry: &BootEntry) -> String {
+        let args_line = match &entry.kernel_args {
+            // Read by the distro-provided loopback.cfg to extend its own
+            // kernel command line, e.g. per-ISO persistence parameters
+            // from `MultibootManager` (`persistent persistent-path=...`).
+            Some(args) => format!("\tset extra_args=\"{}\"\n", args),
+            None => String::new(),
+        };
+        format!(
+            "menuentry \"{title}\" {{\n\
+            \tset isofile=\"{iso}\"\n\
+            {args_line}\
+            \tloopback loop $isofile\n\
+            \tsource (loop)/boot/grub/loopback.cfg\n\
+            }}\n",
+            title = entry.title,
+            iso = entry.iso_path,
+        )
+    }
+
+    /// Render a legacy-BIOS entry that chainloads syslinux memdisk with the
+    /// whole ISO loaded into RAM, for small utility ISOs where loopback
+    /// booting the filesystem in place is not supported by the payload.
+    /// `entry.kernel_args` is not applied here: memdisk chainloads the ISO
+    /// image verbatim, with no opportunity to extend its command line.
+    fn render_memdisk_entry(&self, entry: &BootEntry) -> String {
+        format!(
+            "menuentry \"{title} (memdisk)\" {{\n\
+            \tlinux16 /boot/syslinux/memdisk iso\n\
+            \tinitrd16 \"{iso}\"\n\
+            }}\n",
+            title = entry.title,
+            iso = entry.iso_path,
+        )
+    }
+
+    /// Path, relative to the stick root, that the bundled memdisk binary
+    /// must be copied to for `render_memdisk_entry` output to resolve.
+    pub fn memdisk_install_path(&self) -> &'static str {
+        "boot/syslinux/memdisk"
+    }
+}
+
+impl Default for BootloaderConfigGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A cheaply cloneable, per-job cancellation flag shared between the
+/// UI-facing cancel command and whichever [`crate::disk::PlatformDiskOps`]
+/// call is currently running. Kept separate from
+/// [`crate::progress::ProgressManager`] so the progress event hub stays a
+/// pure broadcaster that a multi-job or pause feature can reuse per-job
+/// without inheriting unrelated cancellation state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Clear a token for reuse by the next job, so a previous job's
+    /// cancellation doesn't immediately abort one that hasn't started yet.
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_uncancelled() {
+        assert!(!CancellationToken::new().is_cancelled());
+    }
+
+    #[test]
+    fn cancel_is_observed_across_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn reset_clears_a_cancelled_token() {
+        let token = CancellationToken::new();
+        token.cancel();
+        token.reset();
+        assert!(!token.is_cancelled());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::error::{WowUsbError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Where the locally-cached checksum database is kept, mirroring
+/// [`crate::device_rules::device_rules_path`]'s per-platform locations.
+///
+/// `WOWUSB_CHECKSUM_DB_PATH` overrides this, for the same reason
+/// `WOWUSB_DEVICE_RULES_PATH` does.
+pub fn checksum_db_cache_path() -> PathBuf {
+    if let Ok(path) = std::env::var("WOWUSB_CHECKSUM_DB_PATH") {
+        return PathBuf::from(path);
+    }
+    default_checksum_db_cache_path()
+}
+
+#[cfg(target_os = "windows")]
+fn default_checksum_db_cache_path() -> PathBuf {
+    PathBuf::from(r"C:\ProgramData\WowUSB\checksum_db.json")
+}
+
+#[cfg(target_os = "macos")]
+fn default_checksum_db_cache_path() -> PathBuf {
+    PathBuf::from("/Library/Application Support/WowUSB/checksum_db.json")
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn default_checksum_db_cache_path() -> PathBuf {
+    PathBuf::from("/etc/wowusb/checksum_db.json")
+}
+
+/// A locally-cached, signed database mapping well-known ISO names/sizes to
+/// official release hashes, so a user-selected ISO can be checked against
+/// it and tampered or corrupted downloads flagged proactively.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ChecksumDatabase {
+    /// Keyed by "{iso_name}:{size_bytes}" for an O(1), collision-resistant
+    /// lookup without needing to hash the candidate file first.
+    entries: HashMap<String, KnownRelease>,
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownRelease {
+    pub distro: String,
+    pub version: String,
+    pub sha256: String,
+    /// Where to fetch this release from, so a caller that finds it's out
+    /// of date (see [`Self::latest_for_distro`]) can offer a one-click
+    /// re-download instead of just a warning.
+    #[serde(default)]
+    pub download_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckResult {
+    Known { matches: bool },
+    Unknown,
+}
+
+impl ChecksumDatabase {
+    pub fn load_from_cache(cache_path: &str) -> Result<Self> {
+        if !std::path::Path::new(cache_path).exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(cache_path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| WowUsbError::configuration(format!("Invalid checksum database: {}", e)))
+    }
+
+    fn key(iso_name: &str, size_bytes: u64) -> String {
+        format!("{}:{}", iso_name, size_bytes)
+    }
+
+    /// Compare an ISO against the database by name, size and computed hash.
+    pub fn check(&self, iso_name: &str, size_bytes: u64, sha256: &str) -> CheckResult {
+        match self.entries.get(&Self::key(iso_name, size_bytes)) {
+            Some(known) => CheckResult::Known { matches: known.sha256.eq_ignore_ascii_case(sha256) },
+            None => CheckResult::Unknown,
+        }
+    }
+
+    /// [`Self::check`], but hashing `iso_path` itself first — see
+    /// [`crate::hashing::sha256_file`] for how that hash is computed.
+    pub async fn check_file(&self, iso_path: &str) -> Result<CheckResult> {
+        let iso_name = std::path::Path::new(iso_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| iso_path.to_string());
+        let size_bytes = std::fs::metadata(iso_path)?.len();
+        let sha256 = crate::hashing::sha256_file(iso_path).await?;
+        Ok(self.check(&iso_name, size_bytes, &sha256))
+    }
+
+    pub fn insert(&mut self, iso_name: &str, size_bytes: u64, release: KnownRelease) {
+        self.entries.insert(Self::key(iso_name, size_bytes), release);
+    }
+
+    /// The known release for `distro`, if any. The database is expected to
+    /// be refreshed with the current catalog (see
+    /// [`crate::updater::BundledAsset::IsoCatalog`]) rather than
+    /// accumulating every version ever seen, so this doesn't need to
+    /// disambiguate between multiple versions of the same distro — it
+    /// returns whichever one it finds.
+    pub fn latest_for_distro(&self, distro: &str) -> Option<&KnownRelease> {
+        self.entries.values().find(|r| r.distro.eq_ignore_ascii_case(distro))
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// A single external command the creation pipeline would run, exactly as
+/// it will be invoked — built from the same argument-construction helpers
+/// the real [`crate::disk::PlatformDiskOps`] calls use, not a
+/// hand-maintained string, so a preview can't drift from what actually
+/// executes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl PlannedCommand {
+    pub fn new(program: impl Into<String>, args: Vec<String>) -> Self {
+        Self { program: program.into(), args }
+    }
+
+    /// A copy-pasteable rendering, for display in the plan preview.
+    pub fn render(&self) -> String {
+        let mut parts = vec![self.program.clone()];
+        parts.extend(self.args.iter().cloned());
+        parts.join(" ")
+    }
+}
@@ -0,0 +1,28 @@
+use crate::error::{Result, WowUsbError};
+use tokio::process::Command as AsyncCommand;
+
+/// Applies WIMBoot/CompactOS compression to an already-extracted Windows
+/// installation, shrinking its on-disk footprint for small (32 GB) Windows
+/// To Go sticks. Requires `compact.exe` on the host (present on any
+/// Windows install; unavailable when preparing media from Linux/macOS).
+pub async fn apply_compact_os(windows_partition_root: &str) -> Result<()> {
+    if !cfg!(target_os = "windows") {
+        return Err(WowUsbError::not_implemented(
+            "CompactOS can only be applied from a Windows host (requires compact.exe)",
+        ));
+    }
+
+    let output = AsyncCommand::new("compact")
+        .args(&["/CompactOS:always", windows_partition_root])
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(WowUsbError::device_operation(format!(
+            "Failed to apply CompactOS: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    Ok(())
+}
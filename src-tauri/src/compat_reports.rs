@@ -0,0 +1,145 @@
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+/// Name of the local, host-side compatibility report log, alongside
+/// [`crate::audit_log::local_history_path`].
+pub const COMPAT_REPORTS_FILENAME: &str = "compat_reports.jsonl";
+
+/// One community/self-reported data point: this ISO, written with these
+/// options, did (or didn't) boot successfully. Accumulating these locally
+/// builds a knowledge base [`crate::iso_quirks::QuirkRuleSet`] can later
+/// be seeded or refreshed from, the same way it already loads bundled
+/// quirk rules via [`crate::updater::BundledAsset`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    pub iso_name: String,
+    pub iso_sha256: String,
+    pub target_os: String,
+    pub filesystem: String,
+    pub enable_multiboot: bool,
+    pub wintogo_enabled: bool,
+    /// Whether the resulting stick was confirmed to boot, either by an
+    /// automated boot test or the user manually confirming it worked.
+    pub boot_confirmed: bool,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// Where the local compatibility report log is kept, mirroring
+/// [`crate::audit_log::local_history_path`]'s per-platform locations.
+#[cfg(target_os = "windows")]
+pub fn local_reports_path() -> std::path::PathBuf {
+    std::path::PathBuf::from(r"C:\ProgramData\WowUSB\compat_reports.jsonl")
+}
+
+#[cfg(target_os = "macos")]
+pub fn local_reports_path() -> std::path::PathBuf {
+    std::path::PathBuf::from("/Library/Application Support/WowUSB/compat_reports.jsonl")
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn local_reports_path() -> std::path::PathBuf {
+    std::path::PathBuf::from("/etc/wowusb/compat_reports.jsonl")
+}
+
+/// Appends [`CompatibilityReport`]s as newline-delimited JSON, already an
+/// exportable format on its own (concatenate or ship the file as-is).
+pub struct CompatReportLog;
+
+impl CompatReportLog {
+    pub fn append(path: impl AsRef<Path>, report: &CompatibilityReport) -> Result<()> {
+        let line = serde_json::to_string(report)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize compatibility report: {}", e)))?;
+
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path.as_ref())?;
+        writeln!(file, "{}", line)?;
+
+        Ok(())
+    }
+
+    pub fn read_all(path: impl AsRef<Path>) -> Result<Vec<CompatibilityReport>> {
+        if !path.as_ref().exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        let mut reports = Vec::new();
+        for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+            let report = serde_json::from_str(line)
+                .map_err(|e| WowUsbError::configuration(format!("Invalid compatibility report entry: {}", e)))?;
+            reports.push(report);
+        }
+
+        Ok(reports)
+    }
+
+    /// Reports recorded for a specific ISO hash, most useful for surfacing
+    /// "others confirmed this ISO boots with these options" to the user.
+    pub fn for_iso(path: impl AsRef<Path>, iso_sha256: &str) -> Result<Vec<CompatibilityReport>> {
+        Ok(Self::read_all(path)?
+            .into_iter()
+            .filter(|r| r.iso_sha256.eq_ignore_ascii_case(iso_sha256))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> CompatibilityReport {
+        CompatibilityReport {
+            recorded_at: chrono::Utc::now(),
+            iso_name: "ubuntu-24.04.iso".to_string(),
+            iso_sha256: "deadbeef".to_string(),
+            target_os: "LinuxLive".to_string(),
+            filesystem: "fat32".to_string(),
+            enable_multiboot: false,
+            wintogo_enabled: false,
+            boot_confirmed: true,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn appended_reports_round_trip() {
+        let path = std::env::temp_dir().join(format!("wowusb_compat_test_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        CompatReportLog::append(&path, &report()).unwrap();
+
+        let reports = CompatReportLog::read_all(&path).unwrap();
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].boot_confirmed);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn for_iso_filters_by_hash() {
+        let path = std::env::temp_dir().join(format!("wowusb_compat_filter_test_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        CompatReportLog::append(&path, &report()).unwrap();
+        CompatReportLog::append(&path, &CompatibilityReport { iso_sha256: "other".to_string(), ..report() }).unwrap();
+
+        let matches = CompatReportLog::for_iso(&path, "deadbeef").unwrap();
+        assert_eq!(matches.len(), 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn missing_log_reads_as_empty() {
+        let path = std::env::temp_dir().join("wowusb_compat_test_missing.jsonl");
+        std::fs::remove_file(&path).ok();
+        assert!(CompatReportLog::read_all(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::error::{WowUsbError, Result};
+use crate::target_os::TargetOs;
+use serde::{Deserialize, Serialize};
+
+/// How [`crate::disk::DiskManager::create_bootable_usb`] gets the source
+/// image onto the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteMode {
+    /// Partition the device, format the payload partition, and extract the
+    /// ISO's filesystem onto it. The default, and the only mode that
+    /// supports multiboot, persistence, and Windows To Go.
+    Extract,
+    /// Stream the source image onto the device sector-by-sector, untouched.
+    /// Required for images that are already complete, bootable disk
+    /// images and carry their own partition table — partitioning or
+    /// formatting first would destroy it. See
+    /// [`crate::iso_quirks::Remediation::ForceWriteMode`] for ISOs known to
+    /// need this, and [`crate::rawwrite`] for the copy engine itself.
+    Raw,
+}
+
+impl Default for WriteMode {
+    fn default() -> Self {
+        WriteMode::Extract
+    }
+}
+
+/// Everything the creation pipeline needs to know about the stick being
+/// built.
+///
+/// This used to be two independently-declared structs — one in `main.rs`
+/// for the Tauri command boundary, one in `disk.rs` for the pipeline
+/// itself — that had drifted apart (different field names, fields present
+/// in one but not the other) and required a conversion between them that
+/// didn't actually exist, so several frontend-declared options were
+/// silently dropped before reaching [`crate::disk::DiskManager`]. There is
+/// now exactly one model, used end to end.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateConfig {
+    pub target_os: TargetOs,
+    pub filesystem: String,
+    pub drive_label: String,
+    #[serde(default)]
+    pub wintogo_enabled: bool,
+    /// Extra drivers/registry tweaks to inject for a specific hardware
+    /// target. Only meaningful when `wintogo_enabled`; see
+    /// [`crate::wintogo_profiles::HardwareProfile`].
+    #[serde(default)]
+    pub hardware_profile: crate::wintogo_profiles::HardwareProfile,
+    #[serde(default)]
+    pub enable_multiboot: bool,
+    /// Add a persistence overlay to a single-partition Linux live stick, so
+    /// changes survive a reboot. Ignored (and rejected by [`Self::validate`])
+    /// outside that combination — see [`crate::disk::DiskManager`]'s
+    /// partition layout for how the overlay is actually sized and created.
+    #[serde(default)]
+    pub enable_persistence: bool,
+    /// Whether the persistence overlay is its own partition or a file
+    /// inside the payload partition. Only meaningful when
+    /// `enable_persistence`. See [`crate::persistence_overlay`].
+    #[serde(default)]
+    pub persistence_mode: crate::persistence_overlay::PersistenceMode,
+    /// Size in MB of the overlay file when `persistence_mode` is
+    /// [`crate::persistence_overlay::PersistenceMode::File`]. Falls back to
+    /// a default reserved size when unset.
+    #[serde(default)]
+    pub persistence_overlay_size_mb: Option<u64>,
+    #[serde(default)]
+    pub menu_appearance: crate::bootloader::MenuAppearance,
+    /// Edition/channel selection written to `sources/ei.cfg` on Windows
+    /// media, so Setup skips the "select edition" prompt.
+    #[serde(default)]
+    pub ei_config: Option<crate::windows_unattend::EiConfig>,
+    /// Preset product key written to `PID.txt`, so Setup skips the product
+    /// key prompt.
+    #[serde(default)]
+    pub product_key: Option<String>,
+    /// Local directory whose contents get copied into `sources/$OEM$` on
+    /// the Windows partition for post-install customization.
+    #[serde(default)]
+    pub oem_folder_path: Option<String>,
+    /// Apply WIMBoot/CompactOS compression to shrink the footprint on
+    /// small Windows To Go sticks. Only meaningful when `wintogo_enabled`.
+    #[serde(default)]
+    pub compact_os_enabled: bool,
+    /// When to flush copied file data to the stick rather than leaving it
+    /// in the OS page cache. See [`crate::write_cache::SyncPolicy`].
+    #[serde(default)]
+    pub sync_policy: crate::write_cache::SyncPolicy,
+    /// Files to copy onto the medium after extraction, overriding whatever
+    /// the ISO shipped at that path. See [`crate::file_injection`].
+    #[serde(default)]
+    pub file_injections: Vec<crate::file_injection::FileInjection>,
+    /// Add a temporary Windows Defender exclusion for the target partition
+    /// while copying, since real-time scanning of every written file can
+    /// roughly halve throughput. Only meaningful on Windows; ignored
+    /// elsewhere. See [`crate::defender`].
+    #[serde(default)]
+    pub suspend_realtime_scanning: bool,
+    /// Remove OS-dropped litter (`System Volume Information`, `.Trashes`,
+    /// `.DS_Store`, thumbnail caches, ...) from the target before final
+    /// unmount, so a later diff against the source ISO doesn't report them
+    /// as spurious extras. See [`crate::litter_cleanup`].
+    #[serde(default)]
+    pub clean_os_litter: bool,
+    /// Extract-and-copy (the default) or dd-style raw sector copy. See
+    /// [`WriteMode`].
+    #[serde(default)]
+    pub write_mode: WriteMode,
+    /// If set, wait this many seconds after confirmation before the first
+    /// destructive command runs, so the user has a last chance to abort.
+    /// See [`crate::undo_window::UndoWindow`].
+    #[serde(default)]
+    pub undo_grace_period_seconds: Option<u64>,
+    /// Cap sustained write throughput and/or lower the copy's I/O priority,
+    /// so a background image job doesn't starve the rest of the desktop.
+    /// Only consulted by [`crate::config::WriteMode::Raw`] writes, which
+    /// copy byte-by-byte; the extract path shells out to a platform
+    /// extraction tool that doesn't expose per-chunk hooks to throttle.
+    /// See [`crate::scheduler::ThrottleSettings`].
+    #[serde(default)]
+    pub io_throttle: Option<crate::scheduler::ThrottleSettings>,
+    /// Encrypt the payload partition after it's written. Rejected by
+    /// [`Self::validate`] alongside [`WriteMode::Raw`] — see
+    /// [`crate::encryption::EncryptionOptions`].
+    #[serde(default)]
+    pub encryption: Option<crate::encryption::EncryptionOptions>,
+}
+
+impl CreateConfig {
+    /// Reject option combinations that don't make sense together before any
+    /// destructive work starts, rather than silently ignoring one of them
+    /// partway through the pipeline.
+    pub fn validate(&self) -> Result<()> {
+        if self.enable_persistence && self.enable_multiboot {
+            return Err(WowUsbError::validation(
+                "Persistence is not supported alongside a multiboot layout",
+            ));
+        }
+
+        if self.enable_persistence && !self.target_os.is_linux() {
+            return Err(WowUsbError::validation(
+                "Persistence is only supported for Linux live media",
+            ));
+        }
+
+        if self.write_mode == WriteMode::Raw
+            && (self.enable_multiboot || self.enable_persistence || self.wintogo_enabled || self.encryption.is_some())
+        {
+            return Err(WowUsbError::validation(
+                "Raw sector-by-sector write mode is not compatible with multiboot, persistence, Windows To Go, or encryption",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> CreateConfig {
+        CreateConfig {
+            target_os: TargetOs::LinuxLive,
+            filesystem: "fat32".to_string(),
+            drive_label: "WOWUSB".to_string(),
+            wintogo_enabled: false,
+            hardware_profile: crate::wintogo_profiles::HardwareProfile::default(),
+            enable_multiboot: false,
+            enable_persistence: false,
+            persistence_mode: crate::persistence_overlay::PersistenceMode::default(),
+            persistence_overlay_size_mb: None,
+            menu_appearance: crate::bootloader::MenuAppearance::default(),
+            ei_config: None,
+            product_key: None,
+            oem_folder_path: None,
+            compact_os_enabled: false,
+            sync_policy: crate::write_cache::SyncPolicy::default(),
+            file_injections: Vec::new(),
+            suspend_realtime_scanning: false,
+            clean_os_litter: false,
+            write_mode: WriteMode::Extract,
+            undo_grace_period_seconds: None,
+            io_throttle: None,
+            encryption: None,
+        }
+    }
+
+    #[test]
+    fn persistence_alone_on_linux_is_valid() {
+        let config = CreateConfig { enable_persistence: true, ..base_config() };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn persistence_with_multiboot_is_rejected() {
+        let config = CreateConfig { enable_persistence: true, enable_multiboot: true, ..base_config() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn persistence_on_windows_is_rejected() {
+        let config = CreateConfig { enable_persistence: true, target_os: TargetOs::Windows, ..base_config() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn raw_write_mode_alone_is_valid() {
+        let config = CreateConfig { write_mode: WriteMode::Raw, ..base_config() };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn raw_write_mode_with_multiboot_is_rejected() {
+        let config = CreateConfig { write_mode: WriteMode::Raw, enable_multiboot: true, ..base_config() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn raw_write_mode_with_encryption_is_rejected() {
+        let config = CreateConfig {
+            write_mode: WriteMode::Raw,
+            encryption: Some(crate::encryption::EncryptionOptions::BitLocker {
+                recovery_key_path: "/tmp/recovery.txt".to_string(),
+            }),
+            ..base_config()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn raw_write_mode_with_veracrypt_encryption_is_rejected() {
+        let config = CreateConfig {
+            write_mode: WriteMode::Raw,
+            encryption: Some(crate::encryption::EncryptionOptions::VeraCrypt {
+                password: "hunter2".to_string(),
+                container_size_mb: 512,
+            }),
+            ..base_config()
+        };
+        assert!(config.validate().is_err());
+    }
+}
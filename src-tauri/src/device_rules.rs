@@ -0,0 +1,156 @@
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the local settings file recording per-device serial rules.
+pub const DEVICE_RULES_FILENAME: &str = "device_rules.json";
+
+/// Where the local device rule set is kept, mirroring
+/// [`crate::audit_log::local_history_path`]'s per-platform locations.
+///
+/// `WOWUSB_DEVICE_RULES_PATH` overrides this, the same way
+/// [`crate::tool_paths::ToolPaths`]'s per-tool env vars override the
+/// bundled/settings lookup — mainly so tests can point at a rule set
+/// without touching the real system-wide file.
+pub fn device_rules_path() -> PathBuf {
+    if let Ok(path) = std::env::var("WOWUSB_DEVICE_RULES_PATH") {
+        return PathBuf::from(path);
+    }
+    default_device_rules_path()
+}
+
+#[cfg(target_os = "windows")]
+fn default_device_rules_path() -> PathBuf {
+    PathBuf::from(r"C:\ProgramData\WowUSB\device_rules.json")
+}
+
+#[cfg(target_os = "macos")]
+fn default_device_rules_path() -> PathBuf {
+    PathBuf::from("/Library/Application Support/WowUSB/device_rules.json")
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn default_device_rules_path() -> PathBuf {
+    PathBuf::from("/etc/wowusb/device_rules.json")
+}
+
+/// User-managed allow/deny rules keyed by device serial (see
+/// [`crate::disk::PlatformDiskOps::device_serial`]), so a mistaken click
+/// can't touch a backup drive and a designated scratch stick doesn't need
+/// to be found in the device list by hand every time.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DeviceRules {
+    /// Serials that must never be listed or operated on, e.g. a backup
+    /// drive that happens to also be removable and USB-attached.
+    #[serde(default)]
+    never_touch: Vec<String>,
+    /// Serials that should be preselected in the UI whenever present, e.g.
+    /// a stick permanently dedicated to WowUSB.
+    #[serde(default)]
+    always_preselect: Vec<String>,
+}
+
+impl DeviceRules {
+    /// Load rules from `path`, or an empty rule set if it doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| WowUsbError::configuration(format!("Invalid device rules file {}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize device rules: {}", e)))?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn is_denied(&self, serial: &str) -> bool {
+        self.never_touch.iter().any(|s| s == serial)
+    }
+
+    pub fn is_preselected(&self, serial: &str) -> bool {
+        self.always_preselect.iter().any(|s| s == serial)
+    }
+
+    pub fn deny(&mut self, serial: impl Into<String>) {
+        let serial = serial.into();
+        self.always_preselect.retain(|s| s != &serial);
+        if !self.never_touch.contains(&serial) {
+            self.never_touch.push(serial);
+        }
+    }
+
+    pub fn preselect(&mut self, serial: impl Into<String>) {
+        let serial = serial.into();
+        self.never_touch.retain(|s| s != &serial);
+        if !self.always_preselect.contains(&serial) {
+            self.always_preselect.push(serial);
+        }
+    }
+
+    /// Remove any rule (deny or preselect) recorded for `serial`.
+    pub fn clear(&mut self, serial: &str) {
+        self.never_touch.retain(|s| s != serial);
+        self.always_preselect.retain(|s| s != serial);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("wowusb_device_rules_test_{}.json", std::process::id()))
+    }
+
+    #[test]
+    fn denying_a_serial_removes_any_preselect_rule() {
+        let mut rules = DeviceRules::default();
+        rules.preselect("SERIAL-1");
+        rules.deny("SERIAL-1");
+
+        assert!(rules.is_denied("SERIAL-1"));
+        assert!(!rules.is_preselected("SERIAL-1"));
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = temp_path();
+        let mut rules = DeviceRules::default();
+        rules.deny("SERIAL-1");
+        rules.preselect("SERIAL-2");
+        rules.save(&path).unwrap();
+
+        let loaded = DeviceRules::load(&path).unwrap();
+        assert!(loaded.is_denied("SERIAL-1"));
+        assert!(loaded.is_preselected("SERIAL-2"));
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn loading_a_missing_file_is_an_empty_rule_set() {
+        let rules = DeviceRules::load(temp_path()).unwrap();
+        assert!(!rules.is_denied("anything"));
+        assert!(!rules.is_preselected("anything"));
+    }
+
+    #[test]
+    fn clear_removes_either_kind_of_rule() {
+        let mut rules = DeviceRules::default();
+        rules.deny("SERIAL-1");
+        rules.clear("SERIAL-1");
+        assert!(!rules.is_denied("SERIAL-1"));
+    }
+}
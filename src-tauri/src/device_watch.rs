@@ -0,0 +1,76 @@
+use crate::disk::{Device, DiskManager};
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
+
+/// Emitted whenever the device list actually changes, never more often
+/// than [`DeviceWatcher`]'s configured interval — the debounced
+/// replacement for a frontend that polls `list_devices` on its own timer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceChangeEvent {
+    pub devices: Vec<Device>,
+    pub detected_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Centralizes device-list polling behind rate limiting and
+/// change-detection, so a chatty frontend timer doesn't spawn a new
+/// PowerShell process every second on the Windows backend (or hammer
+/// `lsblk`/`diskutil` elsewhere). Also the natural home for hotplug events
+/// if one is added later: whatever notices the change would just feed this
+/// same debounced broadcast instead of the frontend needing a second path.
+pub struct DeviceWatcher {
+    min_poll_interval: Duration,
+    last_poll: RwLock<Option<Instant>>,
+    last_devices: RwLock<Option<Vec<Device>>>,
+    sender: broadcast::Sender<DeviceChangeEvent>,
+}
+
+impl DeviceWatcher {
+    pub fn new(min_poll_interval: Duration) -> Self {
+        let (sender, _) = broadcast::channel(16);
+        Self {
+            min_poll_interval,
+            last_poll: RwLock::new(None),
+            last_devices: RwLock::new(None),
+            sender,
+        }
+    }
+
+    /// Subscribe to change-only device list updates.
+    pub fn subscribe(&self) -> broadcast::Receiver<DeviceChangeEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Return the current device list. Only re-queries `disk_manager` if
+    /// the minimum poll interval has elapsed since the last real query;
+    /// otherwise returns the last known list without spawning any new
+    /// work. Broadcasts a [`DeviceChangeEvent`] only when the freshly
+    /// polled list actually differs from what was last seen.
+    pub async fn poll(&self, disk_manager: &DiskManager) -> Result<Vec<Device>> {
+        let due_for_poll = match *self.last_poll.read().await {
+            Some(last) => last.elapsed() >= self.min_poll_interval,
+            None => true,
+        };
+
+        if !due_for_poll {
+            if let Some(cached) = self.last_devices.read().await.clone() {
+                return Ok(cached);
+            }
+        }
+
+        let devices = disk_manager.list_devices().await?;
+        *self.last_poll.write().await = Some(Instant::now());
+
+        let changed = *self.last_devices.read().await != Some(devices.clone());
+        if changed {
+            *self.last_devices.write().await = Some(devices.clone());
+            let _ = self.sender.send(DeviceChangeEvent {
+                devices: devices.clone(),
+                detected_at: chrono::Utc::now(),
+            });
+        }
+
+        Ok(devices)
+    }
+}
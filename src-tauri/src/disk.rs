@@ -1,8 +1,9 @@
+use crate::config::CreateConfig;
 use crate::error::{WowUsbError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Device {
     pub name: String,
     pub size: String,
@@ -11,43 +12,189 @@ pub struct Device {
     pub mountpoint: Option<String>,
     pub is_removable: bool,
     pub is_usb: bool,
+    /// Bus the device is attached through (`"usb"`, `"ata"`, `"nvme"`, ...),
+    /// as reported by udev's `ID_BUS`/`ID_USB_DRIVER` rather than guessed
+    /// from the device name.
+    #[serde(default)]
+    pub bus_type: Option<String>,
+    /// Filesystem label of the mounted partition, if any, so the UI can
+    /// show e.g. "DATA (23 GB used)" instead of a bare device path.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Used space on the mounted partition, in bytes.
+    #[serde(default)]
+    pub used_space_bytes: Option<u64>,
+    /// Set when this device's serial is on the user's "always preselect"
+    /// list (see [`crate::device_rules::DeviceRules`]), so the frontend can
+    /// default the target picker to it instead of leaving it unselected.
+    #[serde(default)]
+    pub preselected: bool,
 }
 
+/// [`PartitionConfig::filesystem`] value for the GPT BIOS Boot Partition
+/// used to embed GRUB's `core.img` on hybrid multiboot media. It isn't a
+/// real filesystem — backends must create it as a bare, unformatted
+/// partition tagged with the `bios_grub` GPT type GUID instead of running
+/// any `mkfs.*`.
+pub(crate) const BIOS_GRUB_PLACEHOLDER: &str = "bios_grub";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PartitionConfig {
     pub size_mb: u64,
     pub filesystem: String,
     pub label: String,
-    pub bootable: bool,
+    /// This is the disk's EFI System Partition. On a GPT disk this is
+    /// `parted`'s `boot` flag — despite the name, setting it on GPT marks
+    /// the partition as an ESP rather than picking a "first partition to
+    /// boot" the way it does on MBR.
+    pub esp: bool,
+    /// BIOS-bootable on a GPT disk (`parted`'s `legacy_boot` flag), so
+    /// legacy/CSM firmware can chainload GRUB from a partition sitting
+    /// alongside a UEFI ESP. Together with `esp` this is what makes a
+    /// layout boot on both firmware types.
+    pub legacy_boot: bool,
+    /// The active partition on an MBR disk — the flag legacy BIOS firmware
+    /// reads to decide what to boot. Meaningless on GPT.
+    pub active: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CreateConfig {
-    pub source_path: String,
-    pub target_device: String,
-    pub filesystem: String,
-    pub drive_label: String,
-    pub wintogo_enabled: bool,
-    pub multiboot_enabled: bool,
-    pub target_os: String,
+/// Result of a non-destructive pre-flight check of whether the current
+/// process can actually write to a target device, so the frontend can
+/// surface a fix before the user configures a whole job that fails at the
+/// last step with an opaque permission error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionCheck {
+    pub can_write: bool,
+    /// `None` when `can_write` is true; otherwise a human-readable
+    /// explanation of what's missing and how to grant it.
+    pub remediation: Option<String>,
+}
+
+impl PermissionCheck {
+    pub fn ok() -> Self {
+        Self { can_write: true, remediation: None }
+    }
+
+    pub fn denied(remediation: impl Into<String>) -> Self {
+        Self { can_write: false, remediation: Some(remediation.into()) }
+    }
+}
+
+/// Result of running a filesystem's native check/repair tool
+/// (`fsck.fat`/`ntfsfix`/`fsck.exfat`/`chkdsk`) against a partition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsckReport {
+    /// No errors were found.
+    pub clean: bool,
+    /// Errors were found and the tool fixed them; `clean` is false but the
+    /// partition should be usable again.
+    pub repaired: bool,
+    /// Raw stdout/stderr from the tool, for display when a user wants to
+    /// see exactly what was wrong.
+    pub details: String,
 }
 
+// `async fn` in traits isn't object-safe on its own, and this trait is used
+// exclusively behind `Box<dyn PlatformDiskOps>` so a platform backend can be
+// chosen at runtime. `async_trait` desugars each method to a boxed future,
+// which restores object safety and lets tests substitute a mock backend.
+#[async_trait::async_trait]
 pub trait PlatformDiskOps: Send + Sync {
     async fn list_devices(&self) -> Result<Vec<Device>>;
     async fn verify_device(&self, device: &str) -> Result<bool>;
     async fn create_partitions(&self, device: &str, config: &[PartitionConfig]) -> Result<()>;
     async fn format_partition(&self, partition: &str, filesystem: &str, label: &str) -> Result<()>;
     async fn mount_partition(&self, partition: &str, mountpoint: &str) -> Result<String>;
+    /// Mount `partition` read-only at `mountpoint`, for inspection-only
+    /// operations like [`crate::stick_contents::list_contents`] that must
+    /// never risk writing to a stick found in an unknown state. Default
+    /// falls back to the regular (writable) mount for backends where the
+    /// OS already handles removable media safely for browsing (Windows'
+    /// automatic drive-letter assignment doesn't distinguish the two).
+    async fn mount_partition_readonly(&self, partition: &str, mountpoint: &str) -> Result<String> {
+        self.mount_partition(partition, mountpoint).await
+    }
     async fn unmount_partition(&self, mountpoint: &str) -> Result<()>;
+    /// Unmount `mountpoint` even if a process still has files open on it.
+    /// Default falls back to the regular (non-forcing) unmount for
+    /// backends that don't have a meaningfully different forced path.
+    async fn force_unmount_partition(&self, mountpoint: &str) -> Result<()> {
+        self.unmount_partition(mountpoint).await
+    }
+    /// Explicitly flush `device`'s own write cache and block until the
+    /// hardware confirms it, rather than trusting that the filesystem-level
+    /// unmount was enough — a stick yanked the instant "Success" appears is
+    /// the most common corruption report from users on USB controllers that
+    /// keep buffering writes past what `unmount_partition` waits for.
+    /// Default is a no-op for backends where nothing meaningful sits
+    /// underneath the filesystem unmount.
+    async fn flush_device_write_cache(&self, _device: &str) -> Result<()> {
+        Ok(())
+    }
     async fn wipe_device(&self, device: &str) -> Result<()>;
+    /// Non-destructively test whether the current process can open `device`
+    /// for writing — never partitions, formats, or writes a single byte —
+    /// and if not, explain what's missing (group membership, elevation,
+    /// sandbox permission) so the user can fix it before configuring a
+    /// whole job around a device it can't actually use.
+    async fn check_permissions(&self, device: &str) -> Result<PermissionCheck>;
     async fn validate_iso(&self, iso_path: &str) -> Result<bool>;
-    async fn extract_iso(&self, iso_path: &str, target_path: &str) -> Result<()>;
-    async fn install_bootloader(&self, device: &str, bootloader_type: &str) -> Result<()>;
+    /// Extract the ISO's contents to `target_path`. Checks `cancellation`
+    /// before starting the underlying extraction tool, so a job cancelled
+    /// while queued behind an earlier stage doesn't still kick off a
+    /// multi-gigabyte copy it's just going to throw away.
+    async fn extract_iso(&self, iso_path: &str, target_path: &str, cancellation: &crate::cancellation::CancellationToken) -> Result<()>;
+    /// Extract a single file at `internal_path` (e.g. `sources/ei.cfg`) out
+    /// of the ISO to `dest`, without extracting the whole image.
+    async fn extract_iso_file(&self, iso_path: &str, internal_path: &str, dest: &str) -> Result<()>;
+    /// Install `bootloader_type` onto `device`, writing its files into the
+    /// already-mounted `boot_mountpoint` and `efi_mountpoint` (the same
+    /// path when the payload partition doubles as the ESP) rather than the
+    /// host's own `/boot`.
+    async fn install_bootloader(&self, device: &str, bootloader_type: &str, boot_mountpoint: &str, efi_mountpoint: &str) -> Result<()>;
+    /// Mount `iso_path` read-only at `mountpoint` so its contents can be
+    /// browsed without staging a copy or committing to a write, returning
+    /// the path it actually ended up mounted at.
+    async fn mount_iso_readonly(&self, iso_path: &str, mountpoint: &str) -> Result<String>;
+    async fn unmount_iso(&self, mountpoint: &str) -> Result<()>;
+    /// Run `filesystem`'s native check/repair tool against `partition`, so a
+    /// stick yanked mid-write can be healed instead of recreated.
+    async fn check_filesystem(&self, partition: &str, filesystem: &str) -> Result<FsckReport>;
+    /// Write a few MB directly to `device` and time it, giving a rough
+    /// bytes/sec estimate of its real write speed for
+    /// [`crate::progress::StageWeights::estimate`]/`estimated_total_seconds`.
+    /// Safe to run before partitioning since the whole device is about to
+    /// be overwritten anyway.
+    async fn probe_write_speed(&self, device: &str) -> Result<u64>;
+    /// A hardware-level identifier for `device` that survives it being
+    /// unplugged and re-inserted (possibly under a different device node),
+    /// used by [`crate::batch::DuplicatorJob`] to tell a re-inserted stick
+    /// apart from a genuinely new one. `None` if the platform can't cheaply
+    /// determine one, in which case callers fall back to the device path.
+    async fn device_serial(&self, device: &str) -> Result<Option<String>>;
+    /// Preview, without executing anything, the literal external command
+    /// lines this backend would run to partition, format, and install the
+    /// bootloader for `config` on `device` — built from the same argument
+    /// helpers the real calls use, for expert users who want to see the
+    /// plan before committing to it. Default is empty: only backends that
+    /// have factored their argument construction into reusable helpers
+    /// override this.
+    fn preview_pipeline_commands(
+        &self,
+        _device: &str,
+        _partitions: &[PartitionConfig],
+        _config: &crate::config::CreateConfig,
+    ) -> Vec<crate::cmdrunner::PlannedCommand> {
+        Vec::new()
+    }
 }
 
 #[cfg(target_os = "windows")]
 mod windows;
 
+#[cfg(target_os = "windows")]
+pub mod windows_volumes;
+
 #[cfg(target_os = "windows")]
 use self::windows::WindowsDiskOps;
 
@@ -63,8 +210,18 @@ mod macos;
 #[cfg(target_os = "macos")]
 use self::macos::MacOSDiskOps;
 
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+mod bsd;
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+use self::bsd::BsdDiskOps;
+
 pub struct DiskManager {
     ops: Box<dyn PlatformDiskOps>,
+    /// Shared across every `create_bootable_usb` call this process makes
+    /// (rather than one per call), since the whole point is to limit
+    /// concurrent writes/verification across jobs, not just within one.
+    io_scheduler: crate::scheduler::IoScheduler,
 }
 
 impl DiskManager {
@@ -78,114 +235,1015 @@ impl DiskManager {
         #[cfg(target_os = "macos")]
         let ops = Box::new(MacOSDiskOps::new());
 
-        Self { ops }
+        #[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+        let ops = Box::new(BsdDiskOps::new());
+
+        Self::with_ops(ops)
     }
 
+    /// Inject an arbitrary backend, bypassing the platform-detection in
+    /// [`DiskManager::new`]. Used to plug in [`crate::sim_disk::SimulatedDiskOps`]
+    /// for frontend development, and [`MockDiskOps`] in unit tests.
+    pub fn with_ops(ops: Box<dyn PlatformDiskOps>) -> Self {
+        Self { ops, io_scheduler: crate::scheduler::IoScheduler::default() }
+    }
+
+    /// Backed by [`crate::sim_disk::SimulatedDiskOps`] instead of a real
+    /// platform backend, so the frontend can be developed and demoed
+    /// without hardware, and its error/retry flows exercised deterministically.
+    pub fn new_simulated(plan: std::sync::Arc<tokio::sync::RwLock<crate::sim_disk::FailureInjectionPlan>>) -> Self {
+        Self::with_ops(Box::new(crate::sim_disk::SimulatedDiskOps::new(plan)))
+    }
+
+    /// Access the underlying platform backend directly, for callers (like
+    /// [`crate::session_recovery`]) that need to drive it outside the
+    /// `create_bootable_usb` pipeline.
+    pub fn ops(&self) -> &dyn PlatformDiskOps {
+        self.ops.as_ref()
+    }
+
+    /// Lists devices from the platform backend, then applies the user's
+    /// [`crate::device_rules::DeviceRules`]: denied serials are dropped
+    /// entirely rather than merely flagged, so a "never touch" drive can't
+    /// be selected by mistake even if the frontend ignores the flag, and
+    /// preselected serials are marked via [`Device::preselected`].
     pub async fn list_devices(&self) -> Result<Vec<Device>> {
-        self.ops.list_devices().await
+        let devices = self.ops.list_devices().await?;
+        let rules = crate::device_rules::DeviceRules::load(crate::device_rules::device_rules_path())?;
+
+        let mut kept = Vec::with_capacity(devices.len());
+        for mut device in devices {
+            let serial = self.ops.device_serial(&device.name).await.unwrap_or(None);
+            if let Some(serial) = &serial {
+                if rules.is_denied(serial) {
+                    continue;
+                }
+                device.preselected = rules.is_preselected(serial);
+            }
+            kept.push(device);
+        }
+
+        Ok(kept)
     }
 
+    /// Verifies `device` is a real, writable disk, then rejects it anyway
+    /// if its serial is on the "never touch" list — this is the gate
+    /// [`Self::create_bootable_usb`] itself checks before writing, so the
+    /// rule holds even for a caller that bypassed [`Self::list_devices`].
     pub async fn verify_device(&self, device: &str) -> Result<bool> {
-        self.ops.verify_device(device).await
+        if !self.ops.verify_device(device).await? {
+            return Ok(false);
+        }
+
+        if let Some(serial) = self.ops.device_serial(device).await.unwrap_or(None) {
+            let rules = crate::device_rules::DeviceRules::load(crate::device_rules::device_rules_path())?;
+            if rules.is_denied(&serial) {
+                return Err(WowUsbError::validation(
+                    "This device is on the never-touch list and cannot be used",
+                ));
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// See [`PlatformDiskOps::check_permissions`].
+    pub async fn check_permissions(&self, device: &str) -> Result<PermissionCheck> {
+        self.ops.check_permissions(device).await
+    }
+
+    /// Mount `partition` read-only into a scratch staging directory, walk
+    /// its contents into a [`crate::stick_contents::ContentEntry`] tree
+    /// (with sizes and hashes), and unmount it again. Used by the
+    /// update/diff tooling and by users auditing what's actually on a
+    /// stick they found.
+    pub async fn list_usb_contents(&self, partition: &str) -> Result<crate::stick_contents::ContentEntry> {
+        let staging = crate::staging::StagingDirectory::resolve(None);
+        let mountpoint = staging
+            .job_dir(&format!("list_contents_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        let mounted_at = self.ops.mount_partition_readonly(partition, &mountpoint).await?;
+        let result = crate::stick_contents::list_contents(&mounted_at).await;
+        let _ = self.ops.unmount_partition(&mounted_at).await;
+        result
     }
 
-    pub async fn create_bootable_usb(&self, source_path: &str, target_device: &str, config: &CreateConfig) -> Result<String> {
-        // Step 1: Validate inputs
+    pub async fn check_filesystem(&self, partition: &str, filesystem: &str) -> Result<FsckReport> {
+        self.ops.check_filesystem(partition, filesystem).await
+    }
+
+    pub async fn device_serial(&self, device: &str) -> Result<Option<String>> {
+        self.ops.device_serial(device).await
+    }
+
+    /// Rough total duration estimate, in seconds, for creating `source_path`
+    /// on `target_device`, for display before the run starts. Falls back to
+    /// an assumed device speed if the probe itself fails, rather than
+    /// failing the whole estimate.
+    pub async fn estimate_duration_seconds(&self, source_path: &str, target_device: &str) -> Result<f64> {
+        let iso_size_bytes = std::fs::metadata(source_path).map(|m| m.len()).unwrap_or(0);
+        let write_bytes_per_sec = self.ops.probe_write_speed(target_device).await.unwrap_or(0);
+        Ok(crate::progress::StageWeights::estimated_total_seconds(iso_size_bytes, write_bytes_per_sec))
+    }
+
+    /// Literal external command lines `create_bootable_usb` would run for
+    /// `source_path` onto `target_device` with `config`, without executing
+    /// anything, so an expert user can review the plan first. See
+    /// [`PlatformDiskOps::preview_pipeline_commands`].
+    pub fn preview_commands(
+        &self,
+        source_path: &str,
+        target_device: &str,
+        config: &CreateConfig,
+    ) -> Result<Vec<crate::cmdrunner::PlannedCommand>> {
+        let iso_size_bytes = std::fs::metadata(source_path).map(|m| m.len()).unwrap_or(0);
+        let partitions = self.create_partition_config(config, iso_size_bytes)?;
+        Ok(self.ops.preview_pipeline_commands(target_device, &partitions, config))
+    }
+
+    pub async fn create_bootable_usb(
+        &self,
+        source_path: &str,
+        target_device: &str,
+        config: &CreateConfig,
+        progress: Option<&std::sync::Arc<tokio::sync::RwLock<crate::progress::ProgressManager>>>,
+        cancellation: Option<&crate::cancellation::CancellationToken>,
+        undo_window: Option<&crate::undo_window::UndoWindow>,
+    ) -> Result<(String, crate::report::CreationReport)> {
+        use crate::progress::Stage;
+        use std::time::Instant;
+
+        let cancellation = cancellation.cloned().unwrap_or_default();
+        let mut report = crate::report::ReportBuilder::new(target_device, source_path);
+
+        // Step 1-3b: Validate inputs, ISO, device, and enterprise policy.
+        let validate_started = Instant::now();
         if source_path.is_empty() {
             return Err(WowUsbError::validation("Source path cannot be empty"));
         }
         if target_device.is_empty() {
             return Err(WowUsbError::validation("Target device cannot be empty"));
         }
+        config.validate()?;
+
+        // Step 3a: Give the user a last chance to abort before the first
+        // destructive command below actually runs. No-op unless a grace
+        // period was requested. See `crate::undo_window::UndoWindow`.
+        if let Some(undo_window) = undo_window {
+            undo_window.wait().await?;
+        }
+
+        if config.write_mode == crate::config::WriteMode::Raw {
+            let is_valid_device = self.verify_device(target_device).await?;
+            if !is_valid_device {
+                return Err(WowUsbError::validation("Invalid target device"));
+            }
+            report.record_stage(Stage::Validate, validate_started.elapsed(), 0);
+            return self.write_raw_image(source_path, target_device, progress, &cancellation, config.io_throttle.as_ref(), report).await;
+        }
 
-        // Step 2: Validate ISO
         let is_valid_iso = self.ops.validate_iso(source_path).await?;
         if !is_valid_iso {
             return Err(WowUsbError::validation("Invalid or corrupted ISO file"));
         }
 
-        // Step 3: Verify target device
-        let is_valid_device = self.ops.verify_device(target_device).await?;
+        let is_valid_device = self.verify_device(target_device).await?;
         if !is_valid_device {
             return Err(WowUsbError::validation("Invalid target device"));
         }
 
+        if let Some(policy) = crate::policy::DevicePolicy::load()? {
+            let devices = self.ops.list_devices().await?;
+            let device_size_bytes = devices
+                .iter()
+                .find(|d| d.name == target_device)
+                .and_then(|d| d.size.parse::<u64>().ok())
+                .unwrap_or(0);
+            policy.enforce(config, device_size_bytes)?;
+        }
+        report.record_stage(Stage::Validate, validate_started.elapsed(), 0);
+
+        // Step 3c: Snapshot whatever's already on the device before
+        // partitioning wipes it away. Best-effort: an unreadable or
+        // genuinely blank existing partition just yields an empty snapshot
+        // rather than failing the job over it.
+        let existing_main_partition = self.get_main_partition(target_device);
+        let pre_wipe_mountpoint = crate::staging::StagingDirectory::resolve(None)
+            .job_dir(&format!("prewipe_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let _ = std::fs::create_dir_all(&pre_wipe_mountpoint);
+        let mounted_before_wipe = self
+            .ops
+            .mount_partition_readonly(&existing_main_partition, &pre_wipe_mountpoint)
+            .await
+            .ok();
+        let existing_label = self
+            .ops
+            .list_devices()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .find(|d| d.name == target_device)
+            .and_then(|d| d.filesystem)
+            .into_iter()
+            .collect();
+        if let Ok(snapshot) = crate::prewipe::PreWipeSnapshot::capture(
+            target_device,
+            existing_label,
+            mounted_before_wipe.as_deref(),
+        ) {
+            report.set_pre_wipe_snapshot(snapshot);
+        }
+        if let Some(mounted) = &mounted_before_wipe {
+            let _ = self.ops.unmount_partition(mounted).await;
+        }
+        let _ = std::fs::remove_dir_all(&pre_wipe_mountpoint);
+
         // Step 4: Create partitions based on configuration
-        let partitions = self.create_partition_config(config)?;
+        let partition_started = Instant::now();
+        let iso_size_bytes = std::fs::metadata(source_path).map(|m| m.len()).unwrap_or(0);
+        let partitions = self.create_partition_config(config, iso_size_bytes)?;
+
+        let devices = self.ops.list_devices().await?;
+        let device_size_bytes = devices
+            .iter()
+            .find(|d| d.name == target_device)
+            .and_then(|d| d.size.parse::<u64>().ok())
+            .unwrap_or(0);
+        crate::geometry::validate_layout(&partitions, device_size_bytes, iso_size_bytes)?;
+
         self.ops.create_partitions(target_device, &partitions).await?;
+        report.record_stage(Stage::Partition, partition_started.elapsed(), 0);
 
         // Step 5: Format the main partition
+        let format_started = Instant::now();
         let main_partition = self.get_main_partition(target_device);
         self.ops.format_partition(&main_partition, &config.filesystem, &config.drive_label).await?;
+        report.record_stage(Stage::Format, format_started.elapsed(), 0);
 
         // Step 6: Mount and copy files
-        let mountpoint = format!("/tmp/wowusb_mount_{}", std::process::id());
+        let copy_started = Instant::now();
+        let staging = crate::staging::StagingDirectory::resolve(None);
+        let mountpoint = staging
+            .job_dir(&format!("mount_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
         std::fs::create_dir_all(&mountpoint)?;
 
         let actual_mountpoint = self.ops.mount_partition(&main_partition, &mountpoint).await?;
-        self.ops.extract_iso(source_path, &actual_mountpoint).await?;
 
-        // Step 7: Install bootloader
-        self.ops.install_bootloader(target_device, "grub2").await?;
+        // Real-time scanning of every file as it lands on the stick can
+        // roughly halve copy throughput on some Windows hosts; opting in
+        // trades that scanning for the duration of the copy. A no-op
+        // everywhere except Windows — see `defender::add_temporary_exclusion`.
+        if config.suspend_realtime_scanning {
+            crate::defender::add_temporary_exclusion(&actual_mountpoint).await?;
+        }
+
+        // See `crate::scheduler::IoScheduler`: caps how many extract
+        // copies run concurrently against the same target.
+        let write_slot = self.io_scheduler.acquire_write_slot(target_device).await;
+        let extract_result = self.extract_iso_with_removal_watch(target_device, source_path, &actual_mountpoint, &cancellation).await;
+        drop(write_slot);
+
+        if let Err(e) = extract_result {
+            if config.suspend_realtime_scanning {
+                crate::defender::remove_exclusion(&actual_mountpoint).await;
+            }
+            self.rollback_partial_write(&actual_mountpoint, &mountpoint).await;
+            return Err(e);
+        }
+
+        // Step 6b: Apply any requested file injections/overrides now, so a
+        // customized boot config or the like takes effect before the
+        // bootloader install step below reads it. Templated injections are
+        // resolved against this specific target device, so a batch
+        // provisioning run gives each stick a distinct hostname.
+        let template_context = crate::file_injection::TemplateContext::for_device(target_device, chrono::Utc::now());
+        crate::file_injection::apply(&actual_mountpoint, &config.file_injections, &template_context)?;
+
+        // Step 6c: For file-based persistence, the overlay lives inside the
+        // payload partition itself rather than getting its own partition —
+        // see `create_partition_config` for why a trailing partition isn't
+        // created in this mode.
+        if config.enable_persistence && config.persistence_mode == crate::persistence_overlay::PersistenceMode::File {
+            let size_mb = config.persistence_overlay_size_mb.unwrap_or(Self::DEFAULT_PERSISTENCE_OVERLAY_MB);
+            crate::persistence_overlay::create_overlay_file(
+                &actual_mountpoint,
+                crate::persistence_overlay::OVERLAY_FILENAME,
+                size_mb,
+            )
+            .await?;
+        }
+
+        if config.suspend_realtime_scanning {
+            crate::defender::remove_exclusion(&actual_mountpoint).await;
+        }
+
+        // Keep macOS's own indexing/journaling daemons off the freshly
+        // written stick — a no-op on every other host.
+        crate::mac_hygiene::apply(&actual_mountpoint).await?;
+
+        // Restore the distro's default SELinux context on the copied files
+        // — a no-op everywhere else. See `crate::lsm`.
+        crate::lsm::restore_default_context(&actual_mountpoint).await?;
+
+        if config.clean_os_litter {
+            crate::litter_cleanup::clean(&actual_mountpoint).await?;
+        }
+        report.record_stage(Stage::Copy, copy_started.elapsed(), iso_size_bytes);
+
+        // Step 7: Install bootloader. GRUB needs `--boot-directory`/
+        // `--efi-directory` pointing at the stick's own mounted
+        // filesystems, or it falls back to modifying the host's `/boot`
+        // and `/boot/efi`. The payload partition doubles as the ESP unless
+        // the filesystem needed a separate support ESP (see
+        // `create_partition_config`), in which case that one has to be
+        // mounted too, just for this step.
+        let bootloader_started = Instant::now();
+        let needs_esp = crate::filesystem::uefi_bootability_for(&config.filesystem)
+            == crate::filesystem::UefiBootability::RequiresEsp;
+
+        let esp_staging_dir = if needs_esp {
+            let esp_partition = crate::platform_paths::partition_name(target_device, 2);
+            let esp_dir = staging
+                .job_dir(&format!("esp_{}", std::process::id()))
+                .to_string_lossy()
+                .to_string();
+            std::fs::create_dir_all(&esp_dir)?;
+            Some(self.ops.mount_partition(&esp_partition, &esp_dir).await?)
+        } else {
+            None
+        };
+        let efi_mountpoint = esp_staging_dir.as_deref().unwrap_or(&actual_mountpoint);
+
+        self.ops.install_bootloader(target_device, "grub2", &actual_mountpoint, efi_mountpoint).await?;
+
+        if let Some(esp_mountpoint) = &esp_staging_dir {
+            self.ops.unmount_partition(esp_mountpoint).await?;
+            std::fs::remove_dir(esp_mountpoint)?;
+        }
+
+        // Step 7b: Write provenance manifest so a later WowUSB instance can
+        // recognize and manage this stick.
+        let iso_name = std::path::Path::new(source_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| source_path.to_string());
+        let manifest = crate::provenance::ProvenanceManifest {
+            tool_version: crate::version::VERSION.to_string(),
+            iso_name,
+            iso_sha256: String::new(),
+            created_at: chrono::Utc::now(),
+            layout: if config.enable_multiboot { "multiboot".to_string() } else { "single".to_string() },
+            filesystem: config.filesystem.clone(),
+            target_os: config.target_os.to_string(),
+            injected_files: config.file_injections.iter().map(|i| i.medium_path.clone()).collect(),
+        };
+        manifest.write_to(&actual_mountpoint)?;
+
+        // Step 7c: Record the write in the stick's own audit log and the
+        // local audit history, for chain-of-custody reporting.
+        let audit_event = crate::audit_log::AuditEvent::new(
+            crate::audit_log::AuditAction::IsoWritten,
+            None,
+            format!("Wrote {} to {}", manifest.iso_name, target_device),
+        );
+        crate::audit_log::AuditLog::append_to_stick(&actual_mountpoint, &audit_event)?;
+        crate::audit_log::AuditLog::append_to_local_history(crate::audit_log::local_history_path(), &audit_event)?;
+
+        // Step 7d: Write Windows edition/product-key selection files, if
+        // requested, so Setup skips those prompts on enterprise media.
+        if config.target_os == crate::target_os::TargetOs::Windows {
+            if let Some(ei_config) = &config.ei_config {
+                ei_config.write_to(&actual_mountpoint)?;
+            }
+            if let Some(product_key) = &config.product_key {
+                crate::windows_unattend::write_pid_txt(&actual_mountpoint, product_key)?;
+            }
+            if let Some(oem_folder_path) = &config.oem_folder_path {
+                crate::windows_unattend::inject_oem_folder(&actual_mountpoint, oem_folder_path, config.sync_policy)?;
+            }
+            if config.wintogo_enabled && config.compact_os_enabled {
+                crate::compact_os::apply_compact_os(&actual_mountpoint).await?;
+            }
+            if config.wintogo_enabled {
+                crate::wintogo_profiles::apply_profile(config.hardware_profile, &actual_mountpoint).await?;
+            }
+        }
+        report.record_stage(Stage::Bootloader, bootloader_started.elapsed(), 0);
+
+        // Step 7e: Encrypt the payload partition, if requested. Runs after
+        // everything else has been written to it, since both BitLocker and
+        // a VeraCrypt container need the filesystem to already hold its
+        // final contents. See [`crate::encryption::EncryptionOptions`].
+        if let Some(encryption) = &config.encryption {
+            encryption.apply(&actual_mountpoint).await?;
+        }
+
+        // Step 8: Wait for cached writes to actually reach the device before
+        // unmounting, reporting real progress instead of appearing stuck at
+        // 100% while the kernel silently writes back gigabytes of cached
+        // data during (or after) the unmount call.
+        let flush_started = Instant::now();
+        self.watch_flush_progress(progress).await;
+        self.ops.unmount_partition(&actual_mountpoint).await?;
+        let verify_slot = self.io_scheduler.acquire_verify_slot().await;
+        self.ops.flush_device_write_cache(target_device).await?;
+        drop(verify_slot);
+        report.record_stage(Stage::Flush, flush_started.elapsed(), 0);
+
+        // Step 9: Cleanup
+        let cleanup_started = Instant::now();
+        std::fs::remove_dir(&mountpoint)?;
+        report.record_stage(Stage::Cleanup, cleanup_started.elapsed(), 0);
+
+        let message = format!("Successfully created bootable USB on {}", target_device);
+        Ok((message, report.finish(true)))
+    }
+
+    /// Sector-by-sector counterpart to the rest of [`Self::create_bootable_usb`],
+    /// for source images that are already complete, bootable disk images
+    /// (hybrid ISOs, FreeBSD memstick images, ...) and must not be
+    /// partitioned or have their filesystem extracted, since that would
+    /// destroy the partition table the image already carries. Skips
+    /// partitioning, formatting, mounting, and bootloader installation
+    /// entirely — see [`crate::rawwrite::write_image`] for the actual copy.
+    async fn write_raw_image(
+        &self,
+        source_path: &str,
+        target_device: &str,
+        progress: Option<&std::sync::Arc<tokio::sync::RwLock<crate::progress::ProgressManager>>>,
+        cancellation: &crate::cancellation::CancellationToken,
+        throttle: Option<&crate::scheduler::ThrottleSettings>,
+        mut report: crate::report::ReportBuilder,
+    ) -> Result<(String, crate::report::CreationReport)> {
+        use crate::progress::Stage;
+        use std::time::Instant;
+
+        let source_metadata = std::fs::metadata(source_path).map_err(|_| {
+            WowUsbError::validation(format!("Source image not found: {}", source_path))
+        })?;
+        if source_metadata.len() == 0 {
+            return Err(WowUsbError::validation("Source image is empty"));
+        }
+
+        // Serialize concurrent writes against the same target so multiple
+        // duplicator-job slots writing through the same controller don't
+        // fight it for bandwidth. See `crate::scheduler::IoScheduler`.
+        let copy_started = Instant::now();
+        let _write_slot = self.io_scheduler.acquire_write_slot(target_device).await;
+        crate::rawwrite::write_image(source_path, target_device, progress, cancellation, throttle).await?;
+        report.record_stage(Stage::Copy, copy_started.elapsed(), source_metadata.len());
+        drop(_write_slot);
+
+        let flush_started = Instant::now();
+        let _verify_slot = self.io_scheduler.acquire_verify_slot().await;
+        self.ops.flush_device_write_cache(target_device).await?;
+        report.record_stage(Stage::Flush, flush_started.elapsed(), 0);
+
+        // Record a sidecar so a later `verify_usb` can check the stick
+        // against the image it was written from without needing the image
+        // file present. See `crate::rawwrite::WriteTrailer`.
+        let trailer = crate::rawwrite::WriteTrailer::compute(source_path).await?;
+        trailer.write_sidecar(target_device)?;
+
+        let message = format!("Successfully wrote raw image to {}", target_device);
+        Ok((message, report.finish(true)))
+    }
+
+    /// Re-run just the bootloader-install step of [`Self::create_bootable_usb`]
+    /// against an existing WowUSB stick, without touching its payload — for
+    /// when a firmware update or a stray write clobbers the boot bits but
+    /// the copied files are still fine. Reads the filesystem recorded in the
+    /// stick's [`crate::provenance::ProvenanceManifest`] rather than trusting
+    /// the caller, since a support ESP is only mounted when that filesystem
+    /// needs one.
+    pub async fn repair_bootloader(&self, device: &str) -> Result<()> {
+        let main_partition = self.get_main_partition(device);
+        let staging = crate::staging::StagingDirectory::resolve(None);
+        let mountpoint = staging
+            .job_dir(&format!("repair_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        std::fs::create_dir_all(&mountpoint)?;
+        let actual_mountpoint = self.ops.mount_partition(&main_partition, &mountpoint).await?;
+
+        let repair_result = self.repair_bootloader_on_mounted(device, &actual_mountpoint, &staging).await;
 
-        // Step 8: Cleanup
         self.ops.unmount_partition(&actual_mountpoint).await?;
         std::fs::remove_dir(&mountpoint)?;
 
-        Ok(format!("Successfully created bootable USB on {}", target_device))
+        repair_result
+    }
+
+    async fn repair_bootloader_on_mounted(&self, device: &str, actual_mountpoint: &str, staging: &crate::staging::StagingDirectory) -> Result<()> {
+        let manifest = crate::provenance::ProvenanceManifest::read_from(actual_mountpoint)?.ok_or_else(|| {
+            WowUsbError::validation(format!("{} has no WowUSB provenance manifest; refusing to repair an unrecognized stick", device))
+        })?;
+
+        let needs_esp = crate::filesystem::uefi_bootability_for(&manifest.filesystem)
+            == crate::filesystem::UefiBootability::RequiresEsp;
+
+        let esp_staging_dir = if needs_esp {
+            let esp_partition = crate::platform_paths::partition_name(device, 2);
+            let esp_dir = staging
+                .job_dir(&format!("repair_esp_{}", std::process::id()))
+                .to_string_lossy()
+                .to_string();
+            std::fs::create_dir_all(&esp_dir)?;
+            Some(self.ops.mount_partition(&esp_partition, &esp_dir).await?)
+        } else {
+            None
+        };
+        let efi_mountpoint = esp_staging_dir.as_deref().unwrap_or(actual_mountpoint);
+
+        let install_result = self.ops.install_bootloader(device, "grub2", actual_mountpoint, efi_mountpoint).await;
+
+        if let Some(esp_mountpoint) = &esp_staging_dir {
+            self.ops.unmount_partition(esp_mountpoint).await?;
+            std::fs::remove_dir(esp_mountpoint)?;
+        }
+        install_result?;
+
+        let audit_event = crate::audit_log::AuditEvent::new(
+            crate::audit_log::AuditAction::BootloaderRepaired,
+            None,
+            format!("Repaired bootloader on {}", device),
+        );
+        crate::audit_log::AuditLog::append_to_stick(actual_mountpoint, &audit_event)?;
+        crate::audit_log::AuditLog::append_to_local_history(crate::audit_log::local_history_path(), &audit_event)?;
+
+        Ok(())
     }
 
-    fn create_partition_config(&self, config: &CreateConfig) -> Result<Vec<PartitionConfig>> {
+    /// How often to poll for the target device disappearing during the
+    /// long-running copy step.
+    const REMOVAL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+    /// How often to re-check dirty page cache size during the flush stage.
+    const FLUSH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+    /// Give up waiting for the page cache to clear after this many polls,
+    /// and proceed to unmount anyway rather than hanging forever if
+    /// something else on the system keeps generating dirty pages.
+    const FLUSH_MAX_POLLS: u32 = 120;
+
+    /// Poll dirty page cache size and publish [`crate::progress::Stage::Flush`]
+    /// updates until it drains (or we give up), so the UI has something
+    /// truthful to show instead of a bar frozen at 100%.
+    async fn watch_flush_progress(&self, progress: Option<&std::sync::Arc<tokio::sync::RwLock<crate::progress::ProgressManager>>>) {
+        let Some(progress) = progress else { return };
+
+        let Some(initial_dirty) = crate::flush_progress::dirty_page_cache_bytes().filter(|b| *b > 0) else {
+            // Either already clean, or this platform can't observe the page
+            // cache; either way there's nothing to poll toward.
+            let manager = progress.read().await;
+            let _ = manager
+                .update_weighted(crate::progress::Stage::Flush, 1.0, crate::flush_progress::flush_message(crate::flush_progress::dirty_page_cache_bytes()))
+                .await;
+            return;
+        };
+
+        for _ in 0..Self::FLUSH_MAX_POLLS {
+            let dirty = crate::flush_progress::dirty_page_cache_bytes().unwrap_or(0);
+            let fraction = 1.0 - (dirty as f64 / initial_dirty as f64).clamp(0.0, 1.0);
+
+            let manager = progress.read().await;
+            let _ = manager.update_weighted(crate::progress::Stage::Flush, fraction, crate::flush_progress::flush_message(Some(dirty))).await;
+            drop(manager);
+
+            if dirty == 0 {
+                break;
+            }
+            tokio::time::sleep(Self::FLUSH_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Run `extract_iso` racing against a background poll of `target_device`,
+    /// so a cable bump mid-write fails fast with [`WowUsbError::DeviceRemoved`]
+    /// instead of grinding through confusing downstream I/O errors. Also
+    /// polls `cancellation` for a user- or shutdown-triggered cancellation,
+    /// so closing the app mid-copy stops the write instead of running it to
+    /// completion in the background.
+    async fn extract_iso_with_removal_watch(
+        &self,
+        target_device: &str,
+        iso_path: &str,
+        mountpoint: &str,
+        cancellation: &crate::cancellation::CancellationToken,
+    ) -> Result<()> {
+        let extract = self.ops.extract_iso(iso_path, mountpoint, cancellation);
+        tokio::pin!(extract);
+
+        loop {
+            tokio::select! {
+                result = &mut extract => return result,
+                _ = tokio::time::sleep(Self::REMOVAL_POLL_INTERVAL) => {
+                    if !self.verify_device(target_device).await.unwrap_or(false) {
+                        return Err(WowUsbError::device_removed(target_device.to_string()));
+                    }
+                    if cancellation.is_cancelled() {
+                        return Err(WowUsbError::Cancelled);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Best-effort teardown of an in-progress write's mount after it's
+    /// interrupted (cancelled or the device disappeared), so an aborted job
+    /// leaves neither a stale mount nor a half-populated staging directory
+    /// behind for [`crate::session_recovery`] to find on the next launch.
+    /// Force-unmounts since the copy may still have files open on it.
+    async fn rollback_partial_write(&self, actual_mountpoint: &str, mountpoint: &str) {
+        if let Err(e) = self.ops.force_unmount_partition(actual_mountpoint).await {
+            log::warn!("Rollback: failed to unmount {} after an interrupted write: {}", actual_mountpoint, e);
+        }
+        if let Err(e) = std::fs::remove_dir_all(mountpoint) {
+            log::warn!("Rollback: failed to remove staging directory {}: {}", mountpoint, e);
+        }
+    }
+
+    /// Extra space reserved on top of the ISO size when the payload
+    /// partition can't simply claim "the rest of the disk" because another
+    /// partition follows it (a support ESP and/or a persistence overlay).
+    const PAYLOAD_SLACK_MB: u64 = 512;
+
+    /// Default size for a file-based persistence overlay
+    /// ([`crate::persistence_overlay::PersistenceMode::File`]) when the
+    /// caller doesn't specify one.
+    pub(crate) const DEFAULT_PERSISTENCE_OVERLAY_MB: u64 = 4096;
+
+    fn create_partition_config(&self, config: &CreateConfig, iso_size_bytes: u64) -> Result<Vec<PartitionConfig>> {
         let mut partitions = Vec::new();
 
-        if config.multiboot_enabled {
-            // Multiboot layout: ESP, BIOS_GRUB, Windows, Payload
+        if config.enable_multiboot {
+            // Multiboot layout: ESP, BIOS_GRUB, Windows, Payload. Legacy
+            // BIOS booting is handled by the dedicated BIOS_GRUB partition
+            // (its type, not a flag, is what makes it bootable — see
+            // `create_partitions`), so the ESP only needs the `esp` flag.
             partitions.push(PartitionConfig {
-                size_mb: 512,
+                size_mb: crate::esp_sizing::detect_esp_size_mb(),
                 filesystem: "fat32".to_string(),
                 label: "EFI".to_string(),
-                bootable: true,
+                esp: true,
+                legacy_boot: false,
+                active: false,
             });
 
             partitions.push(PartitionConfig {
                 size_mb: 1,
-                filesystem: "bios_grub".to_string(),
+                filesystem: BIOS_GRUB_PLACEHOLDER.to_string(),
                 label: "BIOS_GRUB".to_string(),
-                bootable: false,
+                esp: false,
+                legacy_boot: false,
+                active: false,
             });
 
             partitions.push(PartitionConfig {
                 size_mb: 64000, // 64GB for Windows
                 filesystem: "ntfs".to_string(),
                 label: "Windows".to_string(),
-                bootable: false,
+                esp: false,
+                legacy_boot: false,
+                active: false,
             });
         } else {
-            // Standard single partition
+            // Standard single-payload layout, optionally paired with a
+            // support ESP (when the payload filesystem needs one — see
+            // `filesystem::uefi_bootability_for`) and/or a persistence
+            // overlay (live Linux media only; `CreateConfig::validate`
+            // already rejects that combined with multiboot or a non-Linux
+            // target). A `PersistenceMode::Partition` overlay is its own
+            // trailing partition claiming the disk's remaining space; a
+            // `PersistenceMode::File` overlay instead lives as a file
+            // inside the payload partition (see `create_bootable_usb`'s
+            // creation of it after extraction), so it only needs to be
+            // reserved out of the payload's own size when something else
+            // still trails the payload. Whichever partition ends the
+            // layout claims the disk's remaining space, so everything
+            // ahead of it needs a concrete size.
+            let needs_esp = crate::filesystem::uefi_bootability_for(&config.filesystem)
+                == crate::filesystem::UefiBootability::RequiresEsp;
+            let persistence_partition =
+                config.enable_persistence && config.persistence_mode == crate::persistence_overlay::PersistenceMode::Partition;
+            let has_trailing_partition = needs_esp || persistence_partition;
+
+            let overlay_reserve_mb = if config.enable_persistence
+                && config.persistence_mode == crate::persistence_overlay::PersistenceMode::File
+            {
+                config.persistence_overlay_size_mb.unwrap_or(Self::DEFAULT_PERSISTENCE_OVERLAY_MB)
+            } else {
+                0
+            };
+
+            let payload_mb = if has_trailing_partition {
+                (iso_size_bytes / (1024 * 1024)) + Self::PAYLOAD_SLACK_MB + overlay_reserve_mb
+            } else {
+                0 // Use remaining space; nothing else follows it.
+            };
             partitions.push(PartitionConfig {
-                size_mb: 0, // Use remaining space
+                size_mb: payload_mb,
                 filesystem: config.filesystem.clone(),
                 label: config.drive_label.clone(),
-                bootable: true,
+                // When the payload's own filesystem is UEFI-native (fat32)
+                // it's also the partition legacy BIOS boots from, so it
+                // gets both flags — a hybrid layout bootable on either
+                // firmware from a single partition. When it needs a
+                // separate support ESP, the payload itself carries neither.
+                esp: !needs_esp,
+                legacy_boot: !needs_esp,
+                active: false,
             });
+
+            if needs_esp {
+                partitions.push(PartitionConfig {
+                    size_mb: crate::esp_sizing::detect_esp_size_mb(),
+                    filesystem: "fat32".to_string(),
+                    label: "EFI".to_string(),
+                    esp: true,
+                    legacy_boot: false,
+                    active: false,
+                });
+            }
+
+            if persistence_partition {
+                partitions.push(PartitionConfig {
+                    size_mb: 0, // Use remaining space
+                    filesystem: "ext4".to_string(),
+                    label: "casper-rw".to_string(),
+                    esp: false,
+                    legacy_boot: false,
+                    active: false,
+                });
+            }
         }
 
         Ok(partitions)
     }
 
     fn get_main_partition(&self, device: &str) -> String {
-        // This is a simplified version - in practice, this would be more sophisticated
-        if device.ends_with("0") || !device.chars().last().unwrap().is_numeric() {
-            format!("{}1", device)
-        } else {
-            device.to_string()
-        }
+        crate::platform_paths::main_partition_name(device)
     }
 
     pub async fn validate_iso(&self, iso_path: &str) -> Result<bool> {
         self.ops.validate_iso(iso_path).await
     }
+
+    /// Mount `iso_path` read-only so advanced users can browse it in their
+    /// file manager before committing to a write.
+    pub async fn mount_iso_readonly(&self, iso_path: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+        self.ops.mount_iso_readonly(iso_path, mountpoint).await
+    }
+
+    pub async fn unmount_iso(&self, mountpoint: &str) -> Result<()> {
+        self.ops.unmount_iso(mountpoint).await
+    }
+
+    /// List processes holding files open under `mountpoint`, for reporting
+    /// why an unmount failed. See [`crate::busy_mount::list_busy_processes`].
+    pub async fn list_busy_processes(&self, mountpoint: &str) -> Result<Vec<crate::busy_mount::BusyProcess>> {
+        crate::busy_mount::list_busy_processes(mountpoint).await
+    }
+
+    /// Unmount `mountpoint`, forcing it closed even if a process still has
+    /// files open there. Callers should offer this only after a regular
+    /// unmount has already failed and [`Self::list_busy_processes`] has
+    /// been shown to the operator.
+    pub async fn force_unmount_partition(&self, mountpoint: &str) -> Result<()> {
+        self.ops.force_unmount_partition(mountpoint).await
+    }
+
+    /// Pull a single file (EULA, checksums, `grub.cfg`, `ei.cfg`, ...) out
+    /// of the ISO for display, without extracting the whole image.
+    pub async fn extract_iso_file(&self, iso_path: &str, internal_path: &str, dest: &str) -> Result<()> {
+        self.ops.extract_iso_file(iso_path, internal_path, dest).await
+    }
 }
 
 // Platform implementations will go here
-// For now, I'll create stub implementations
\ No newline at end of file
+// For now, I'll create stub implementations
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    /// Minimal, fully in-memory [`PlatformDiskOps`] for exercising
+    /// `DiskManager`'s pipeline logic without shelling out to real tools.
+    struct MockDiskOps {
+        fail_on_verify: bool,
+        serial: Option<String>,
+    }
+
+    impl MockDiskOps {
+        fn new(fail_on_verify: bool) -> Self {
+            Self { fail_on_verify, serial: None }
+        }
+
+        fn with_serial(serial: &str) -> Self {
+            Self { fail_on_verify: false, serial: Some(serial.to_string()) }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl PlatformDiskOps for MockDiskOps {
+        async fn list_devices(&self) -> Result<Vec<Device>> {
+            Ok(vec![Device {
+                name: "/dev/mockdisk".to_string(),
+                size: "8000000000".to_string(),
+                model: "Mock Drive".to_string(),
+                filesystem: Some("fat32".to_string()),
+                mountpoint: None,
+                is_removable: true,
+                is_usb: true,
+                bus_type: Some("usb".to_string()),
+                label: None,
+                used_space_bytes: None,
+                preselected: false,
+            }])
+        }
+
+        async fn verify_device(&self, _device: &str) -> Result<bool> {
+            Ok(!self.fail_on_verify)
+        }
+
+        async fn check_permissions(&self, _device: &str) -> Result<PermissionCheck> {
+            Ok(PermissionCheck::ok())
+        }
+
+        async fn create_partitions(&self, _device: &str, _config: &[PartitionConfig]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn format_partition(&self, _partition: &str, _filesystem: &str, _label: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn mount_partition(&self, _partition: &str, mountpoint: &str) -> Result<String> {
+            Ok(mountpoint.to_string())
+        }
+
+        async fn unmount_partition(&self, _mountpoint: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn wipe_device(&self, _device: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn validate_iso(&self, _iso_path: &str) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn extract_iso(&self, _iso_path: &str, _target_path: &str, _cancellation: &crate::cancellation::CancellationToken) -> Result<()> {
+            Ok(())
+        }
+
+        async fn install_bootloader(&self, _device: &str, _bootloader_type: &str, _boot_mountpoint: &str, _efi_mountpoint: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn extract_iso_file(&self, _iso_path: &str, _internal_path: &str, _dest: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn mount_iso_readonly(&self, _iso_path: &str, mountpoint: &str) -> Result<String> {
+            Ok(mountpoint.to_string())
+        }
+
+        async fn unmount_iso(&self, _mountpoint: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn check_filesystem(&self, _partition: &str, _filesystem: &str) -> Result<FsckReport> {
+            Ok(FsckReport { clean: true, repaired: false, details: String::new() })
+        }
+
+        async fn probe_write_speed(&self, _device: &str) -> Result<u64> {
+            Ok(20 * 1024 * 1024)
+        }
+
+        async fn device_serial(&self, _device: &str) -> Result<Option<String>> {
+            Ok(self.serial.clone())
+        }
+    }
+
+    fn test_config() -> CreateConfig {
+        CreateConfig {
+            filesystem: "fat32".to_string(),
+            drive_label: "WOWUSB".to_string(),
+            wintogo_enabled: false,
+            hardware_profile: crate::wintogo_profiles::HardwareProfile::default(),
+            enable_multiboot: false,
+            enable_persistence: false,
+            persistence_mode: crate::persistence_overlay::PersistenceMode::default(),
+            persistence_overlay_size_mb: None,
+            target_os: crate::target_os::TargetOs::LinuxLive,
+            menu_appearance: crate::bootloader::MenuAppearance::default(),
+            ei_config: None,
+            product_key: None,
+            oem_folder_path: None,
+            compact_os_enabled: false,
+            sync_policy: crate::write_cache::SyncPolicy::default(),
+            file_injections: Vec::new(),
+            suspend_realtime_scanning: false,
+            clean_os_litter: false,
+            write_mode: crate::config::WriteMode::Extract,
+            undo_grace_period_seconds: None,
+            io_throttle: None,
+            encryption: None,
+        }
+    }
+
+    #[test]
+    fn partition_config_claims_remaining_space_when_nothing_follows() {
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::new(false)));
+        let config = test_config(); // fat32, no persistence: boots natively, nothing trails it
+        let partitions = manager.create_partition_config(&config, 4_000_000_000).unwrap();
+
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].size_mb, 0);
+        assert!(partitions[0].esp, "fat32 payload doubles as the ESP");
+        assert!(partitions[0].legacy_boot, "and stays legacy-BIOS-bootable");
+    }
+
+    #[test]
+    fn partition_config_adds_esp_for_non_native_uefi_filesystem() {
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::new(false)));
+        let mut config = test_config();
+        config.filesystem = "ntfs".to_string();
+        let partitions = manager.create_partition_config(&config, 4_000_000_000).unwrap();
+
+        assert_eq!(partitions.len(), 2);
+        assert_eq!(partitions[0].filesystem, "ntfs");
+        assert!(!partitions[0].esp, "firmware can't boot ntfs directly");
+        assert_eq!(partitions[0].size_mb, 4000 + DiskManager::PAYLOAD_SLACK_MB);
+        assert_eq!(partitions[1].label, "EFI");
+        assert_eq!(partitions[1].size_mb, crate::esp_sizing::ESP_SIZE_FALLBACK_MB, "no bundled resources dir in a test build");
+        assert!(partitions[1].esp);
+        assert!(!partitions[1].legacy_boot, "UEFI-only support partition");
+    }
+
+    #[test]
+    fn partition_config_multiboot_uses_esp_and_dedicated_bios_grub() {
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::new(false)));
+        let mut config = test_config();
+        config.enable_multiboot = true;
+        let partitions = manager.create_partition_config(&config, 4_000_000_000).unwrap();
+
+        let efi = partitions.iter().find(|p| p.label == "EFI").unwrap();
+        assert!(efi.esp);
+        assert!(!efi.legacy_boot, "legacy boot goes through BIOS_GRUB instead");
+
+        let bios_grub = partitions.iter().find(|p| p.label == "BIOS_GRUB").unwrap();
+        assert!(!bios_grub.esp);
+        assert!(!bios_grub.legacy_boot, "its partition type, not a flag, makes it bootable");
+    }
+
+    #[tokio::test]
+    async fn verify_device_reflects_backend() {
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::new(false)));
+        assert!(manager.verify_device("/dev/mockdisk").await.unwrap());
+
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::new(true)));
+        assert!(!manager.verify_device("/dev/mockdisk").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn create_bootable_usb_rejects_invalid_device() {
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::new(true)));
+        let result = manager.create_bootable_usb("test.iso", "/dev/mockdisk", &test_config(), None, None, None).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn create_bootable_usb_rejects_a_device_on_the_never_touch_list() {
+        // Regression test: create_bootable_usb used to call self.ops.verify_device
+        // directly, which never consults DeviceRules, so a denied serial passed
+        // straight through instead of being rejected.
+        let rules_path = std::env::temp_dir().join(format!("wowusb_disk_test_never_touch_{}.json", std::process::id()));
+        let mut rules = crate::device_rules::DeviceRules::default();
+        rules.deny("MOCK-SERIAL-1");
+        rules.save(&rules_path).unwrap();
+
+        std::env::set_var("WOWUSB_DEVICE_RULES_PATH", &rules_path);
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::with_serial("MOCK-SERIAL-1")));
+        let result = manager.create_bootable_usb("test.iso", "/dev/mockdisk", &test_config(), None, None, None).await;
+        std::env::remove_var("WOWUSB_DEVICE_RULES_PATH");
+        std::fs::remove_file(&rules_path).ok();
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_devices_delegates_to_backend() {
+        let manager = DiskManager::with_ops(Box::new(MockDiskOps::new(false)));
+        let devices = manager.list_devices().await.unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "/dev/mockdisk");
+    }
+}
\ No newline at end of file
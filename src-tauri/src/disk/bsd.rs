@@ -0,0 +1,409 @@
+use crate::disk::{Device, PartitionConfig, PlatformDiskOps};
+use crate::error::{WowUsbError, Result};
+use std::path::Path;
+use tokio::process::Command as AsyncCommand;
+
+/// FreeBSD/OpenBSD backend built on `geom`/`gpart` for partitioning and
+/// `newfs_msdos` for FAT formatting, so desktop BSD users aren't limited
+/// to the Linux and macOS backends.
+pub struct BsdDiskOps;
+
+impl BsdDiskOps {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl PlatformDiskOps for BsdDiskOps {
+    async fn list_devices(&self) -> Result<Vec<Device>> {
+        let output = AsyncCommand::new("geom")
+            .args(&["disk", "list"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "geom disk list failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let mut devices = Vec::new();
+        let contents = String::from_utf8(output.stdout)?;
+
+        for block in contents.split("Geom name: ").skip(1) {
+            let name = block.lines().next().unwrap_or("").trim();
+            if name.is_empty() {
+                continue;
+            }
+
+            let mediasize_line = block.lines().find(|l| l.trim_start().starts_with("Mediasize:"));
+            let size = mediasize_line
+                .and_then(|l| l.split_whitespace().nth(1))
+                .unwrap_or("unknown")
+                .to_string();
+
+            devices.push(Device {
+                name: format!("/dev/{}", name),
+                size,
+                model: "Unknown".to_string(),
+                filesystem: None,
+                mountpoint: None,
+                is_removable: name.starts_with("da"),
+                is_usb: name.starts_with("da"),
+                bus_type: if name.starts_with("da") { Some("usb".to_string()) } else { None },
+                label: None,
+                used_space_bytes: None,
+                preselected: false,
+            });
+        }
+
+        Ok(devices)
+    }
+
+    async fn verify_device(&self, device: &str) -> Result<bool> {
+        Ok(Path::new(device).exists())
+    }
+
+    async fn check_permissions(&self, device: &str) -> Result<crate::disk::PermissionCheck> {
+        match std::fs::OpenOptions::new().write(true).open(device) {
+            Ok(_) => Ok(crate::disk::PermissionCheck::ok()),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                let remediation = if crate::hostenv::current_user_in_group("operator") {
+                    "Already a member of the operator group, but access to this device was \
+                    still denied; check the device node's permissions directly."
+                } else {
+                    "Add your user to the operator group (`pw groupmod operator -m $USER`), \
+                    then log out and back in, or run WowUSB with sudo/doas."
+                };
+                Ok(crate::disk::PermissionCheck::denied(remediation))
+            }
+            Err(e) => Err(WowUsbError::device_operation(format!(
+                "Could not open {} to check permissions: {}", device, e
+            ))),
+        }
+    }
+
+    async fn create_partitions(&self, device: &str, config: &[PartitionConfig]) -> Result<()> {
+        let device_name = device.trim_start_matches("/dev/");
+
+        let output = AsyncCommand::new("gpart")
+            .args(&["create", "-s", "gpt", device_name])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "gpart create failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        for partition in config {
+            let size_arg = if partition.size_mb == 0 {
+                Vec::new()
+            } else {
+                vec!["-s".to_string(), format!("{}M", partition.size_mb)]
+            };
+
+            let mut args = vec!["add", "-t", "fat32"];
+            let size_arg_refs: Vec<&str> = size_arg.iter().map(|s| s.as_str()).collect();
+            args.extend(size_arg_refs.iter());
+            args.push(device_name);
+
+            let output = AsyncCommand::new("gpart").args(&args).output().await?;
+
+            if !output.status.success() {
+                return Err(WowUsbError::device_operation(format!(
+                    "gpart add failed: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn format_partition(&self, partition: &str, filesystem: &str, label: &str) -> Result<()> {
+        match filesystem.to_lowercase().as_str() {
+            "fat32" => {
+                let output = AsyncCommand::new("newfs_msdos")
+                    .args(&["-F", "32", "-L", label, partition])
+                    .output()
+                    .await?;
+
+                if !output.status.success() {
+                    return Err(WowUsbError::filesystem(format!(
+                        "newfs_msdos failed: {}",
+                        String::from_utf8_lossy(&output.stderr)
+                    )));
+                }
+
+                Ok(())
+            }
+            other => Err(WowUsbError::filesystem(format!(
+                "Unsupported filesystem on BSD: {}",
+                other
+            ))),
+        }
+    }
+
+    async fn mount_partition(&self, partition: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+
+        let output = AsyncCommand::new("mount_msdosfs")
+            .args(&[partition, mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "mount_msdosfs failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(mountpoint.to_string())
+    }
+
+    async fn mount_partition_readonly(&self, partition: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+
+        let output = AsyncCommand::new("mount_msdosfs")
+            .args(&["-o", "ro", partition, mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "mount_msdosfs (read-only) failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(mountpoint.to_string())
+    }
+
+    async fn unmount_partition(&self, mountpoint: &str) -> Result<()> {
+        let output = AsyncCommand::new("umount").arg(mountpoint).output().await?;
+
+        if !output.status.success() && !String::from_utf8_lossy(&output.stderr).contains("not currently mounted") {
+            return Err(WowUsbError::device_operation(format!(
+                "umount failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Force unmount (`umount -f`): the BSDs don't have Linux's lazy
+    /// (`-l`) detach-now-clean-up-later semantics, so this forcibly
+    /// invalidates any file descriptors still open on the mountpoint
+    /// instead.
+    async fn force_unmount_partition(&self, mountpoint: &str) -> Result<()> {
+        let output = AsyncCommand::new("umount").args(&["-f", mountpoint]).output().await?;
+
+        if !output.status.success() && !String::from_utf8_lossy(&output.stderr).contains("not currently mounted") {
+            return Err(WowUsbError::device_operation(format!(
+                "Force umount failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Neither FreeBSD nor OpenBSD ship a portable per-device cache-flush
+    /// tool, so a system-wide `sync` is the common denominator; it still
+    /// blocks until buffered writes for `device`'s filesystem reach the
+    /// hardware.
+    async fn flush_device_write_cache(&self, _device: &str) -> Result<()> {
+        let output = AsyncCommand::new("sync").output().await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "sync failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn wipe_device(&self, device: &str) -> Result<()> {
+        let device_name = device.trim_start_matches("/dev/");
+        let output = AsyncCommand::new("gpart")
+            .args(&["destroy", "-F", device_name])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "gpart destroy failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn validate_iso(&self, iso_path: &str) -> Result<bool> {
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+        let output = AsyncCommand::new(tool_paths.resolve("7z")).args(&["t", iso_path]).output().await?;
+        Ok(output.status.success())
+    }
+
+    async fn extract_iso(&self, iso_path: &str, target_path: &str, cancellation: &crate::cancellation::CancellationToken) -> Result<()> {
+        if cancellation.is_cancelled() {
+            return Err(WowUsbError::Cancelled);
+        }
+
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+        let output = AsyncCommand::new(tool_paths.resolve("7z"))
+            .args(&["x", iso_path, &crate::platform_paths::sevenzip_output_flag(target_path), "-y"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(format!(
+                "Failed to extract ISO: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn extract_iso_file(&self, iso_path: &str, internal_path: &str, dest: &str) -> Result<()> {
+        let extract_dir = format!("/tmp/wowusb_single_{}", std::process::id());
+        std::fs::create_dir_all(&extract_dir)?;
+
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+        let output = AsyncCommand::new(tool_paths.resolve("7z"))
+            .args(&["x", iso_path, internal_path, &crate::platform_paths::sevenzip_output_flag(&extract_dir), "-y"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            std::fs::remove_dir_all(&extract_dir).ok();
+            return Err(WowUsbError::iso_processing(format!(
+                "Failed to extract {} from ISO: {}",
+                internal_path,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let extracted = Path::new(&extract_dir).join(internal_path);
+        std::fs::rename(&extracted, dest)?;
+        std::fs::remove_dir_all(&extract_dir).ok();
+
+        Ok(())
+    }
+
+    async fn mount_iso_readonly(&self, iso_path: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+
+        let attach = AsyncCommand::new("mdconfig")
+            .args(&["-a", "-t", "vnode", "-f", iso_path, "-o", "readonly"])
+            .output()
+            .await?;
+
+        if !attach.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "mdconfig attach failed: {}",
+                String::from_utf8_lossy(&attach.stderr)
+            )));
+        }
+
+        let md_device = String::from_utf8(attach.stdout)?.trim().to_string();
+
+        let output = AsyncCommand::new("mount_cd9660")
+            .args(&["-r", &format!("/dev/{}", md_device), mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "mount_cd9660 failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(mountpoint.to_string())
+    }
+
+    async fn unmount_iso(&self, mountpoint: &str) -> Result<()> {
+        self.unmount_partition(mountpoint).await
+    }
+
+    async fn install_bootloader(&self, _device: &str, bootloader_type: &str, _boot_mountpoint: &str, _efi_mountpoint: &str) -> Result<()> {
+        Err(WowUsbError::not_implemented(format!(
+            "Bootloader type not supported on BSD: {}",
+            bootloader_type
+        )))
+    }
+
+    async fn check_filesystem(&self, partition: &str, filesystem: &str) -> Result<crate::disk::FsckReport> {
+        match filesystem.to_lowercase().as_str() {
+            "fat32" => {
+                let output = AsyncCommand::new("fsck_msdosfs")
+                    .args(&["-y", partition])
+                    .output()
+                    .await?;
+
+                let details = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+
+                if !output.status.success() {
+                    return Err(WowUsbError::filesystem(format!(
+                        "fsck_msdosfs could not repair {}: {}", partition, details
+                    )));
+                }
+
+                let repaired = details.to_lowercase().contains("modified") || details.to_lowercase().contains("fixed");
+                Ok(crate::disk::FsckReport { clean: !repaired, repaired, details })
+            }
+            other => Err(WowUsbError::filesystem(format!(
+                "No filesystem check tool known for {} on BSD", other
+            ))),
+        }
+    }
+
+    async fn probe_write_speed(&self, device: &str) -> Result<u64> {
+        const PROBE_MB: u64 = 4;
+        let started = std::time::Instant::now();
+
+        let output = AsyncCommand::new("dd")
+            .args(&[
+                "if=/dev/zero",
+                &format!("of={}", device),
+                "bs=1m",
+                &format!("count={}", PROBE_MB),
+            ])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Write-speed probe failed on {}: {}", device, String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let elapsed = started.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return Ok(0);
+        }
+        Ok(((PROBE_MB * 1024 * 1024) as f64 / elapsed) as u64)
+    }
+
+    async fn device_serial(&self, _device: &str) -> Result<Option<String>> {
+        // No universal, dependency-free way to pull a media serial across
+        // the BSDs; callers fall back to matching on the device path.
+        Ok(None)
+    }
+}
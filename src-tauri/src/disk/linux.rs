@@ -15,14 +15,56 @@ impl LinuxDiskOps {
     }
 }
 
+/// Build `parted`'s `mkpart` args for `partition`, shared between
+/// `create_partitions`'s real invocation and `preview_pipeline_commands` so
+/// the preview can't drift from what actually runs. See `create_partitions`
+/// for why the BIOS_GRUB placeholder is omitted as an fs-type.
+fn mkpart_args(device: &str, partition: &PartitionConfig, start_mb: &str, end_mb: &str) -> Vec<String> {
+    let mut args = vec!["--script".to_string(), device.to_string(), "mkpart".to_string(), "primary".to_string()];
+    if partition.filesystem != crate::disk::BIOS_GRUB_PLACEHOLDER {
+        args.push(partition.filesystem.clone());
+    }
+    args.push(start_mb.to_string());
+    args.push(end_mb.to_string());
+    args
+}
+
+fn set_flag_args(device: &str, partition_num: usize, flag: &str) -> Vec<String> {
+    vec!["--script".to_string(), device.to_string(), "set".to_string(), partition_num.to_string(), flag.to_string(), "on".to_string()]
+}
+
+/// Build the `mkfs.*` program name and args for `filesystem`, or `None` for
+/// an unrecognized one, shared between `format_partition`'s real
+/// invocation and `preview_pipeline_commands`.
+fn mkfs_command(filesystem: &str, label: &str, partition: &str) -> Option<(&'static str, Vec<String>)> {
+    match filesystem {
+        "fat32" => Some(("mkfs.fat", vec!["-F".to_string(), "32".to_string(), "-n".to_string(), label.to_string(), partition.to_string()])),
+        "ntfs" => Some(("mkfs.ntfs", vec!["-f".to_string(), "-L".to_string(), label.to_string(), partition.to_string()])),
+        "exfat" => Some(("mkfs.exfat", vec!["-n".to_string(), label.to_string(), partition.to_string()])),
+        "ext4" => Some(("mkfs.ext4", vec!["-F".to_string(), "-L".to_string(), label.to_string(), partition.to_string()])),
+        "f2fs" => Some(("mkfs.f2fs", vec!["-f".to_string(), "-l".to_string(), label.to_string(), partition.to_string()])),
+        _ => None,
+    }
+}
+
+#[async_trait::async_trait]
 impl PlatformDiskOps for LinuxDiskOps {
     async fn list_devices(&self) -> Result<Vec<Device>> {
         let output = AsyncCommand::new("lsblk")
-            .args(&["-J", "-o", "NAME,SIZE,MODEL,FSTYPE,MOUNTPOINT,TYPE,MOUNTPOINT"])
+            .args(&["-J", "-o", "NAME,SIZE,MODEL,FSTYPE,MOUNTPOINT,TYPE,LABEL"])
             .output()
             .await?;
 
         if !output.status.success() {
+            if crate::hostenv::is_wsl() {
+                return Err(crate::hostenv::wsl_device_passthrough_hint("target device"));
+            }
+
+            let sandbox = crate::hostenv::detect_sandbox();
+            if sandbox != crate::hostenv::SandboxKind::None {
+                return Err(crate::hostenv::sandbox_permission_hint(sandbox, "target device"));
+            }
+
             return Err(WowUsbError::device_operation(
                 format!("lsblk failed: {}", String::from_utf8_lossy(&output.stderr))
             ));
@@ -41,7 +83,7 @@ impl PlatformDiskOps for LinuxDiskOps {
                 ) {
                     // Skip system disks and internal devices that aren't removable
                     let device_path = format!("/dev/{}", name);
-                    if self.is_removable_device(&device_path).await? || name.starts_with("sd") {
+                    if self.is_removable_device(&device_path).await? || Self::is_candidate_device_name(name) {
                         let model = device.get("model")
                             .and_then(|v| v.as_str())
                             .unwrap_or("Unknown")
@@ -57,7 +99,11 @@ impl PlatformDiskOps for LinuxDiskOps {
                             .map(|s| s.to_string());
 
                         let is_removable = self.is_removable_device(&device_path).await?;
-                        let is_usb = name.starts_with("sd") && is_removable;
+                        let bus_type = self.udev_bus_type(&device_path).await;
+                        let is_usb = bus_type.as_deref() == Some("usb");
+
+                        let label = device.get("label").and_then(|v| v.as_str()).map(|s| s.to_string());
+                        let used_space_bytes = mountpoint.as_deref().and_then(Self::used_space_bytes);
 
                         devices.push(Device {
                             name: device_path,
@@ -67,6 +113,10 @@ impl PlatformDiskOps for LinuxDiskOps {
                             mountpoint,
                             is_removable,
                             is_usb,
+                            bus_type,
+                            label,
+                            used_space_bytes,
+                            preselected: false,
                         });
                     }
                 }
@@ -96,9 +146,43 @@ impl PlatformDiskOps for LinuxDiskOps {
         Ok(file_type.trim() == "block special file")
     }
 
+    async fn check_permissions(&self, device: &str) -> Result<crate::disk::PermissionCheck> {
+        if crate::hostenv::is_wsl() {
+            return Ok(crate::disk::PermissionCheck::denied(
+                crate::hostenv::wsl_device_passthrough_hint(device).to_string(),
+            ));
+        }
+
+        let sandbox = crate::hostenv::detect_sandbox();
+        if sandbox != crate::hostenv::SandboxKind::None {
+            return Ok(crate::disk::PermissionCheck::denied(
+                crate::hostenv::sandbox_permission_hint(sandbox, device).to_string(),
+            ));
+        }
+
+        match std::fs::OpenOptions::new().write(true).open(device) {
+            Ok(_) => Ok(crate::disk::PermissionCheck::ok()),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                let remediation = if crate::hostenv::current_user_in_group("disk") {
+                    "Already a member of the disk group, but access to this device was still \
+                    denied; a udev rule may be excluding it specifically."
+                } else {
+                    "Add your user to the disk group (`sudo usermod -aG disk $USER`), then log \
+                    out and back in, or run WowUSB with sudo."
+                };
+                Ok(crate::disk::PermissionCheck::denied(remediation))
+            }
+            Err(e) => Err(WowUsbError::device_operation(format!(
+                "Could not open {} to check permissions: {}", device, e
+            ))),
+        }
+    }
+
     async fn create_partitions(&self, device: &str, config: &[PartitionConfig]) -> Result<()> {
+        let tool_paths = crate::tool_paths::ToolPaths::load()?;
+
         // Create partition table
-        let output = AsyncCommand::new("parted")
+        let output = AsyncCommand::new(tool_paths.resolve("parted"))
             .args(&["--script", device, "mklabel", "gpt"])
             .output()
             .await?;
@@ -110,26 +194,23 @@ impl PlatformDiskOps for LinuxDiskOps {
         }
 
         // Create partitions
-        let mut current_start = 1; // Start at 1MB
+        let mut current_start: u64 = 1; // Start at 1MB
         for (index, partition) in config.iter().enumerate() {
             let partition_num = index + 1;
-            let end_mb = if partition.size_mb == 0 {
-                "100%" // Use remaining space
-            } else {
-                &format!("{}MB", current_start + partition.size_mb)
+            let end_mb = match crate::units::partition_end_mb(current_start, partition.size_mb) {
+                Some(end) => format!("{}MB", end),
+                None => "100%".to_string(), // Use remaining space
             };
 
             let start_mb = format!("{}MB", current_start);
 
-            let output = AsyncCommand::new("parted")
-                .args(&[
-                    "--script", device,
-                    "mkpart",
-                    "primary",
-                    partition.filesystem.as_str(),
-                    start_mb,
-                    end_mb
-                ])
+            if let Some(next_start) = crate::units::next_start_mb(current_start, partition.size_mb) {
+                current_start = next_start;
+            }
+
+            let args = mkpart_args(device, partition, &start_mb, &end_mb);
+            let output = AsyncCommand::new(tool_paths.resolve("parted"))
+                .args(&args)
                 .output()
                 .await?;
 
@@ -139,18 +220,20 @@ impl PlatformDiskOps for LinuxDiskOps {
                 ));
             }
 
-            // Set bootable flag if needed
-            if partition.bootable {
-                let output = AsyncCommand::new("parted")
-                    .args(&["--script", device, "set", &format!("{}", partition_num), "boot", "on"])
-                    .output()
-                    .await?;
+            if partition.filesystem == crate::disk::BIOS_GRUB_PLACEHOLDER {
+                self.set_partition_flag(device, partition_num, "bios_grub").await?;
+            }
 
-                if !output.status.success() {
-                    return Err(WowUsbError::device_operation(
-                        format!("Failed to set boot flag on partition {}: {}", partition_num, String::from_utf8_lossy(&output.stderr))
-                    ));
-                }
+            // `parted`'s `boot` flag is scheme-overloaded: on the GPT table
+            // we always create it marks the ESP, but the same flag name is
+            // what MBR calls the active partition, so both `esp` and
+            // `active` map onto it here.
+            if partition.esp || partition.active {
+                self.set_partition_flag(device, partition_num, "boot").await?;
+            }
+
+            if partition.legacy_boot {
+                self.set_partition_flag(device, partition_num, "legacy_boot").await?;
             }
 
             current_start += partition.size_mb;
@@ -160,44 +243,22 @@ impl PlatformDiskOps for LinuxDiskOps {
     }
 
     async fn format_partition(&self, partition: &str, filesystem: &str, label: &str) -> Result<()> {
-        let output = match filesystem {
-            "fat32" => {
-                AsyncCommand::new("mkfs.fat")
-                    .args(&["-F", "32", "-n", label, partition])
-                    .output()
-                    .await?
-            }
-            "ntfs" => {
-                AsyncCommand::new("mkfs.ntfs")
-                    .args(&["-f", "-L", label, partition])
-                    .output()
-                    .await?
-            }
-            "exfat" => {
-                AsyncCommand::new("mkfs.exfat")
-                    .args(&["-n", label, partition])
-                    .output()
-                    .await?
-            }
-            "ext4" => {
-                AsyncCommand::new("mkfs.ext4")
-                    .args(&["-F", "-L", label, partition])
-                    .output()
-                    .await?
-            }
-            "f2fs" => {
-                AsyncCommand::new("mkfs.f2fs")
-                    .args(&["-f", "-l", label, partition])
-                    .output()
-                    .await?
-            }
-            _ => {
-                return Err(WowUsbError::filesystem(
-                    format!("Unsupported filesystem: {}", filesystem)
-                ));
-            }
+        if filesystem == crate::disk::BIOS_GRUB_PLACEHOLDER {
+            // Already created as a bare `bios_grub`-tagged partition;
+            // running an mkfs against it would overwrite the space
+            // grub-install embeds core.img into.
+            return Ok(());
+        }
+
+        let Some((program, args)) = mkfs_command(filesystem, label, partition) else {
+            return Err(WowUsbError::filesystem(
+                format!("Unsupported filesystem: {}", filesystem)
+            ));
         };
 
+        let tool_paths = crate::tool_paths::ToolPaths::load()?;
+        let output = AsyncCommand::new(tool_paths.resolve(program)).args(&args).output().await?;
+
         if !output.status.success() {
             return Err(WowUsbError::filesystem(
                 format!("Failed to format partition: {}", String::from_utf8_lossy(&output.stderr))
@@ -211,8 +272,21 @@ impl PlatformDiskOps for LinuxDiskOps {
         // Create mount point if it doesn't exist
         std::fs::create_dir_all(mountpoint)?;
 
+        // On a hardened distro, files written under the default context can
+        // come out unreadable to the tools that need them later (or fail to
+        // mount at all); see `crate::lsm`.
+        let lsm_options = crate::lsm::mount_options_for(crate::lsm::detect_lsm());
+        let joined_options = lsm_options.join(",");
+        let mut args: Vec<&str> = Vec::new();
+        if !lsm_options.is_empty() {
+            args.push("-o");
+            args.push(&joined_options);
+        }
+        args.push(partition);
+        args.push(mountpoint);
+
         let output = AsyncCommand::new("mount")
-            .args(&[partition, mountpoint])
+            .args(&args)
             .output()
             .await?;
 
@@ -225,6 +299,23 @@ impl PlatformDiskOps for LinuxDiskOps {
         Ok(mountpoint.to_string())
     }
 
+    async fn mount_partition_readonly(&self, partition: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+
+        let output = AsyncCommand::new("mount")
+            .args(&["-o", "ro", partition, mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to mount partition read-only: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(mountpoint.to_string())
+    }
+
     async fn unmount_partition(&self, mountpoint: &str) -> Result<()> {
         let output = AsyncCommand::new("umount")
             .arg(mountpoint)
@@ -241,6 +332,51 @@ impl PlatformDiskOps for LinuxDiskOps {
         Ok(())
     }
 
+    /// Lazy unmount (`umount -l`): detaches the mountpoint from the
+    /// filesystem hierarchy immediately, and the underlying filesystem
+    /// itself is cleaned up once the processes still holding it open close
+    /// their file descriptors (or are killed).
+    async fn force_unmount_partition(&self, mountpoint: &str) -> Result<()> {
+        let output = AsyncCommand::new("umount")
+            .args(&["-l", mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() && !String::from_utf8_lossy(&output.stderr).contains("not mounted") {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to force-unmount partition: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `sync` flushes buffered writes system-wide; `blockdev --flushbufs`
+    /// then issues the BLKFLSBUF ioctl against `device` specifically, which
+    /// also drops the kernel's buffer cache for it so a stale cached read
+    /// can't paper over a device that failed to actually commit the write.
+    async fn flush_device_write_cache(&self, device: &str) -> Result<()> {
+        let sync_output = AsyncCommand::new("sync").output().await?;
+        if !sync_output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to sync filesystem buffers: {}", String::from_utf8_lossy(&sync_output.stderr))
+            ));
+        }
+
+        let flush_output = AsyncCommand::new("blockdev")
+            .args(&["--flushbufs", device])
+            .output()
+            .await?;
+
+        if !flush_output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to flush device write cache: {}", String::from_utf8_lossy(&flush_output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
     async fn wipe_device(&self, device: &str) -> Result<()> {
         let output = AsyncCommand::new("wipefs")
             .args(&["--all", device])
@@ -265,9 +401,13 @@ impl PlatformDiskOps for LinuxDiskOps {
         Ok(output.status.success())
     }
 
-    async fn extract_iso(&self, iso_path: &str, target_path: &str) -> Result<()> {
+    async fn extract_iso(&self, iso_path: &str, target_path: &str, cancellation: &crate::cancellation::CancellationToken) -> Result<()> {
+        if cancellation.is_cancelled() {
+            return Err(WowUsbError::Cancelled);
+        }
+
         let output = AsyncCommand::new("7z")
-            .args(&["x", iso_path, f"-o{target_path}", "-y"])
+            .args(&["x", iso_path, &crate::platform_paths::sevenzip_output_flag(target_path), "-y"])
             .output()
             .await?;
 
@@ -280,14 +420,62 @@ impl PlatformDiskOps for LinuxDiskOps {
         Ok(())
     }
 
-    async fn install_bootloader(&self, device: &str, bootloader_type: &str) -> Result<()> {
+    async fn extract_iso_file(&self, iso_path: &str, internal_path: &str, dest: &str) -> Result<()> {
+        let extract_dir = format!("{}_single_{}", self.temp_dir, std::process::id());
+        std::fs::create_dir_all(&extract_dir)?;
+
+        let output = AsyncCommand::new("7z")
+            .args(&["x", iso_path, internal_path, &crate::platform_paths::sevenzip_output_flag(&extract_dir), "-y"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            std::fs::remove_dir_all(&extract_dir).ok();
+            return Err(WowUsbError::iso_processing(
+                format!("Failed to extract {} from ISO: {}", internal_path, String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        let extracted = Path::new(&extract_dir).join(internal_path);
+        std::fs::rename(&extracted, dest)?;
+        std::fs::remove_dir_all(&extract_dir).ok();
+
+        Ok(())
+    }
+
+    async fn mount_iso_readonly(&self, iso_path: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+
+        let output = AsyncCommand::new("mount")
+            .args(&["-o", "loop,ro", iso_path, mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to loop-mount ISO: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(mountpoint.to_string())
+    }
+
+    async fn unmount_iso(&self, mountpoint: &str) -> Result<()> {
+        self.unmount_partition(mountpoint).await
+    }
+
+    async fn install_bootloader(&self, device: &str, bootloader_type: &str, boot_mountpoint: &str, efi_mountpoint: &str) -> Result<()> {
         match bootloader_type {
             "grub2" => {
-                // Install GRUB for UEFI
-                let output = AsyncCommand::new("grub-install")
-                    .args(&["--target=x86_64-efi", "--efi-directory=/boot/efi", "--removable", device])
-                    .output()
-                    .await?;
+                // Fedora/openSUSE/RHEL ship `grub2-install`/`grub2-mkimage`
+                // instead of the unprefixed names Debian/Ubuntu/Arch use.
+                let toolset = crate::grub_tooling::detect_toolset()?;
+                let tool_paths = crate::tool_paths::ToolPaths::load()?;
+
+                // Install GRUB for UEFI, writing into the stick's own
+                // mounted ESP instead of the host's `/boot/efi`.
+                let efi_args = crate::grub_tooling::install_efi_args(device, efi_mountpoint, boot_mountpoint);
+                let output = AsyncCommand::new(tool_paths.resolve(toolset.install_binary())).args(&efi_args).output().await?;
 
                 if !output.status.success() {
                     return Err(WowUsbError::device_operation(
@@ -295,16 +483,30 @@ impl PlatformDiskOps for LinuxDiskOps {
                     ));
                 }
 
-                // Install GRUB for BIOS
-                let output = AsyncCommand::new("grub-install")
-                    .args(&["--target=i386-pc", "--removable", device])
-                    .output()
-                    .await?;
+                // Install GRUB for BIOS. On a GPT disk, `grub-install`
+                // targets the device as a whole and embeds `core.img` into
+                // whichever partition carries the `bios_grub` type GUID —
+                // the one `create_partitions` tags for multiboot layouts —
+                // rather than the cramped post-MBR gap it'd otherwise use.
+                // Its own module/config files still go under the stick's
+                // mounted boot directory rather than the host's.
+                let bios_args = crate::grub_tooling::install_bios_args(device, boot_mountpoint);
+                let output = AsyncCommand::new(tool_paths.resolve(toolset.install_binary())).args(&bios_args).output().await?;
 
                 if !output.status.success() {
-                    return Err(WowUsbError::device_operation(
-                        format!("Failed to install GRUB BIOS: {}", String::from_utf8_lossy(&output.stderr))
-                    ));
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    if crate::grub_tooling::is_missing_i386_pc_modules(&stderr) {
+                        // The host's GRUB package is UEFI-only (common on
+                        // Fedora/openSUSE), so build the BIOS core.img
+                        // ourselves from bundled modules and drop it
+                        // straight onto the bios_grub partition instead of
+                        // giving up.
+                        self.install_bios_core_img_via_mkimage(toolset, device, boot_mountpoint, &tool_paths).await?;
+                    } else {
+                        return Err(WowUsbError::device_operation(
+                            format!("Failed to install GRUB BIOS: {}", stderr)
+                        ));
+                    }
                 }
             }
             _ => {
@@ -316,9 +518,281 @@ impl PlatformDiskOps for LinuxDiskOps {
 
         Ok(())
     }
+
+    async fn check_filesystem(&self, partition: &str, filesystem: &str) -> Result<crate::disk::FsckReport> {
+        // Each of these tools uses fsck's own exit-code convention: 0 for
+        // "clean", 1 for "errors found and corrected", and anything higher
+        // for a failure a rerun can't fix on its own.
+        let (tool, args): (&str, Vec<&str>) = match filesystem.to_lowercase().as_str() {
+            "fat32" | "fat16" | "vfat" => ("fsck.fat", vec!["-a", partition]),
+            "ntfs" => ("ntfsfix", vec![partition]),
+            "exfat" => ("fsck.exfat", vec!["-y", partition]),
+            "ext4" => ("fsck.ext4", vec!["-p", partition]),
+            other => {
+                return Err(WowUsbError::filesystem(format!(
+                    "No filesystem check tool known for {}", other
+                )));
+            }
+        };
+
+        let output = AsyncCommand::new(tool).args(&args).output().await?;
+        let details = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        match output.status.code() {
+            Some(0) => Ok(crate::disk::FsckReport { clean: true, repaired: false, details }),
+            Some(1) => Ok(crate::disk::FsckReport { clean: false, repaired: true, details }),
+            _ => Err(WowUsbError::filesystem(format!(
+                "{} could not repair {}: {}", tool, partition, details
+            ))),
+        }
+    }
+
+    async fn probe_write_speed(&self, device: &str) -> Result<u64> {
+        const PROBE_MB: u64 = 4;
+        let started = std::time::Instant::now();
+
+        // `oflag=direct` bypasses the page cache so the timing reflects the
+        // device's own write speed instead of how fast RAM can absorb it.
+        let output = AsyncCommand::new("dd")
+            .args(&[
+                "if=/dev/zero",
+                &format!("of={}", device),
+                "bs=1M",
+                &format!("count={}", PROBE_MB),
+                "oflag=direct",
+            ])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Write-speed probe failed on {}: {}", device, String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let elapsed = started.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return Ok(0);
+        }
+        Ok(((PROBE_MB * 1024 * 1024) as f64 / elapsed) as u64)
+    }
+
+    async fn device_serial(&self, device: &str) -> Result<Option<String>> {
+        let output = AsyncCommand::new("udevadm")
+            .args(&["info", "--query=property", "--name", device])
+            .output()
+            .await
+            .ok();
+
+        let Some(output) = output else { return Ok(None) };
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let props = String::from_utf8_lossy(&output.stdout);
+        for prefix in ["ID_SERIAL_SHORT=", "ID_SERIAL="] {
+            if let Some(value) = props.lines().find_map(|l| l.strip_prefix(prefix)) {
+                if !value.is_empty() {
+                    return Ok(Some(value.to_string()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn preview_pipeline_commands(
+        &self,
+        device: &str,
+        partitions: &[PartitionConfig],
+        config: &crate::config::CreateConfig,
+    ) -> Vec<crate::cmdrunner::PlannedCommand> {
+        use crate::cmdrunner::PlannedCommand;
+
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+
+        let mut commands = vec![PlannedCommand::new(
+            tool_paths.resolve("parted"),
+            vec!["--script".to_string(), device.to_string(), "mklabel".to_string(), "gpt".to_string()],
+        )];
+
+        let mut current_start: u64 = 1;
+        for (index, partition) in partitions.iter().enumerate() {
+            let partition_num = index + 1;
+            let end_mb = match crate::units::partition_end_mb(current_start, partition.size_mb) {
+                Some(end) => format!("{}MB", end),
+                None => "100%".to_string(),
+            };
+            let start_mb = format!("{}MB", current_start);
+            if let Some(next_start) = crate::units::next_start_mb(current_start, partition.size_mb) {
+                current_start = next_start;
+            }
+
+            commands.push(PlannedCommand::new(tool_paths.resolve("parted"), mkpart_args(device, partition, &start_mb, &end_mb)));
+
+            if partition.filesystem == crate::disk::BIOS_GRUB_PLACEHOLDER {
+                commands.push(PlannedCommand::new(tool_paths.resolve("parted"), set_flag_args(device, partition_num, "bios_grub")));
+            }
+            if partition.esp || partition.active {
+                commands.push(PlannedCommand::new(tool_paths.resolve("parted"), set_flag_args(device, partition_num, "boot")));
+            }
+            if partition.legacy_boot {
+                commands.push(PlannedCommand::new(tool_paths.resolve("parted"), set_flag_args(device, partition_num, "legacy_boot")));
+            }
+
+            let partition_device = crate::platform_paths::partition_name(device, partition_num as u32);
+            if let Some((program, args)) = mkfs_command(&partition.filesystem, &partition.label, &partition_device) {
+                commands.push(PlannedCommand::new(tool_paths.resolve(program), args));
+            }
+        }
+
+        // Predicted mount points, computed the same way `create_bootable_usb`
+        // will compute them for this same process, so the preview lines up
+        // with what actually gets mounted.
+        let staging = crate::staging::StagingDirectory::resolve(None);
+        let boot_mountpoint = staging.job_dir(&format!("mount_{}", std::process::id())).to_string_lossy().to_string();
+        let needs_esp = crate::filesystem::uefi_bootability_for(&config.filesystem) == crate::filesystem::UefiBootability::RequiresEsp;
+        let efi_mountpoint = if needs_esp {
+            staging.job_dir(&format!("esp_{}", std::process::id())).to_string_lossy().to_string()
+        } else {
+            boot_mountpoint.clone()
+        };
+
+        if let Ok(toolset) = crate::grub_tooling::detect_toolset() {
+            commands.push(PlannedCommand::new(
+                tool_paths.resolve(toolset.install_binary()),
+                crate::grub_tooling::install_efi_args(device, &efi_mountpoint, &boot_mountpoint),
+            ));
+            commands.push(PlannedCommand::new(
+                tool_paths.resolve(toolset.install_binary()),
+                crate::grub_tooling::install_bios_args(device, &boot_mountpoint),
+            ));
+        }
+
+        commands
+    }
 }
 
 impl LinuxDiskOps {
+    /// Whether `name` looks like a device type worth surfacing as a target
+    /// even when the removable sysfs flag can't be read: SATA/SCSI USB
+    /// bridges (`sdX`), USB NVMe enclosures (`nvmeXnY`), and SD-card
+    /// readers (`mmcblkX`) are all common non-`sd*` removable media.
+    fn is_candidate_device_name(name: &str) -> bool {
+        name.starts_with("sd") || name.starts_with("nvme") || name.starts_with("mmcblk")
+    }
+
+    /// Used space on a mounted partition, via `statvfs`, so the UI can show
+    /// e.g. "DATA (23 GB used)" next to the device the user is about to erase.
+    fn used_space_bytes(mountpoint: &str) -> Option<u64> {
+        let stat = nix::sys::statvfs::statvfs(mountpoint).ok()?;
+        Some((stat.blocks() - stat.blocks_free()) * stat.fragment_size())
+    }
+
+    /// Classify the bus a device is attached through via udev properties
+    /// instead of guessing from the device name, which misclassifies SATA
+    /// disks named `sdX` as USB and misses NVMe-over-USB enclosures.
+    async fn udev_bus_type(&self, device: &str) -> Option<String> {
+        let output = AsyncCommand::new("udevadm")
+            .args(&["info", "--query=property", "--name", device])
+            .output()
+            .await
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let props = String::from_utf8(output.stdout).ok()?;
+
+        for line in props.lines() {
+            if let Some(value) = line.strip_prefix("ID_BUS=") {
+                return Some(value.to_lowercase());
+            }
+        }
+
+        // Some USB mass-storage bridges only expose ID_USB_DRIVER, not
+        // ID_BUS=usb, so fall back to that before giving up.
+        if props.lines().any(|l| l.starts_with("ID_USB_DRIVER=")) {
+            return Some("usb".to_string());
+        }
+
+        None
+    }
+
+    /// Run `parted set <partition_num> <flag> on`, used for both the
+    /// `boot` flag (ESP on GPT, active on MBR) and `legacy_boot`.
+    async fn set_partition_flag(&self, device: &str, partition_num: usize, flag: &str) -> Result<()> {
+        let tool_paths = crate::tool_paths::ToolPaths::load()?;
+        let output = AsyncCommand::new(tool_paths.resolve("parted"))
+            .args(&["--script", device, "set", &partition_num.to_string(), flag, "on"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to set {} flag on partition {}: {}", flag, partition_num, String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Build a BIOS `core.img` from bundled i386-pc modules and write it
+    /// directly onto `device`'s `bios_grub`-flagged partition (always
+    /// partition 2 in the multiboot layout `create_partition_config`
+    /// builds for hybrid boot) with `dd`, bypassing `grub-install` entirely
+    /// for hosts whose GRUB package never installed those modules.
+    async fn install_bios_core_img_via_mkimage(
+        &self,
+        toolset: crate::grub_tooling::GrubToolset,
+        device: &str,
+        boot_mountpoint: &str,
+        tool_paths: &crate::tool_paths::ToolPaths,
+    ) -> Result<()> {
+        let core_img_path = format!("{}_core.img", self.temp_dir);
+        let boot_directory = format!("{}/boot", boot_mountpoint);
+        let args = crate::grub_tooling::mkimage_core_img_args(
+            crate::grub_tooling::BUNDLED_I386_PC_MODULES_DIR,
+            &boot_directory,
+            &core_img_path,
+        );
+
+        let output = AsyncCommand::new(tool_paths.resolve(toolset.mkimage_binary())).args(&args).output().await?;
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Failed to build fallback BIOS core.img: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let bios_grub_partition = crate::platform_paths::partition_name(device, 2);
+        let dd_result = AsyncCommand::new("dd")
+            .args(&[
+                format!("if={}", core_img_path),
+                format!("of={}", bios_grub_partition),
+                "bs=512".to_string(),
+            ])
+            .output()
+            .await?;
+
+        std::fs::remove_file(&core_img_path).ok();
+
+        if !dd_result.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Failed to write fallback BIOS core.img to {}: {}",
+                bios_grub_partition,
+                String::from_utf8_lossy(&dd_result.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
     async fn is_removable_device(&self, device: &str) -> Result<bool> {
         let device_name = Path::new(device)
             .file_name()
@@ -343,7 +817,11 @@ impl LinuxDiskOps {
             }
         }
 
-        // Fallback: assume USB devices are removable
-        Ok(device_name.starts_with("sd") && !device_name.starts_with("sda"))
+        // Fallback: assume non-primary sd/nvme/mmcblk devices are removable
+        // (the first disk of each family is conventionally the boot disk).
+        Ok(Self::is_candidate_device_name(device_name)
+            && !device_name.starts_with("sda")
+            && device_name != "nvme0n1"
+            && device_name != "mmcblk0")
     }
 }
\ No newline at end of file
@@ -12,6 +12,7 @@ impl MacOSDiskOps {
     }
 }
 
+#[async_trait::async_trait]
 impl PlatformDiskOps for MacOSDiskOps {
     async fn list_devices(&self) -> Result<Vec<Device>> {
         let output = AsyncCommand::new("diskutil")
@@ -51,6 +52,21 @@ impl PlatformDiskOps for MacOSDiskOps {
         Ok(output.status.success())
     }
 
+    async fn check_permissions(&self, device: &str) -> Result<crate::disk::PermissionCheck> {
+        match std::fs::OpenOptions::new().write(true).open(device) {
+            Ok(_) => Ok(crate::disk::PermissionCheck::ok()),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                Ok(crate::disk::PermissionCheck::denied(
+                    "macOS requires elevated access to raw disks: grant WowUSB Full Disk \
+                    Access in System Settings > Privacy & Security, or run it with sudo.",
+                ))
+            }
+            Err(e) => Err(WowUsbError::device_operation(format!(
+                "Could not open {} to check permissions: {}", device, e
+            ))),
+        }
+    }
+
     async fn create_partitions(&self, device: &str, config: &[PartitionConfig]) -> Result<()> {
         // First, unmount the disk
         let output = AsyncCommand::new("diskutil")
@@ -64,7 +80,10 @@ impl PlatformDiskOps for MacOSDiskOps {
             ));
         }
 
-        // Partition the disk
+        // Partition the disk. `diskutil partitionDisk` has no per-partition
+        // boot-flag argument — GPT scheme and ESP placement are implicit in
+        // the format keywords it's given — so `PartitionConfig`'s
+        // esp/legacy_boot/active fields aren't consulted here.
         let mut partition_args = vec!["partitionDisk".to_string(), device.to_string()];
 
         for (index, partition) in config.iter().enumerate() {
@@ -135,6 +154,23 @@ impl PlatformDiskOps for MacOSDiskOps {
         Ok(mountpoint.to_string())
     }
 
+    async fn mount_partition_readonly(&self, partition: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+
+        let output = AsyncCommand::new("diskutil")
+            .args(&["mount", "-readOnly", "-mountPoint", mountpoint, partition])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to mount partition read-only: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(mountpoint.to_string())
+    }
+
     async fn unmount_partition(&self, mountpoint: &str) -> Result<()> {
         let output = AsyncCommand::new("diskutil")
             .args(&["unmount", mountpoint])
@@ -150,6 +186,39 @@ impl PlatformDiskOps for MacOSDiskOps {
         Ok(())
     }
 
+    async fn force_unmount_partition(&self, mountpoint: &str) -> Result<()> {
+        let output = AsyncCommand::new("diskutil")
+            .args(&["unmount", "force", mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to force-unmount partition: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// `diskutil synchronizeDisk` issues a SCSI/ATA SYNCHRONIZE CACHE to
+    /// `device`, blocking until the drive itself reports the write cache
+    /// flushed, rather than trusting that unmounting the volume was enough.
+    async fn flush_device_write_cache(&self, device: &str) -> Result<()> {
+        let output = AsyncCommand::new("diskutil")
+            .args(&["synchronizeDisk", device])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to synchronize disk: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
     async fn wipe_device(&self, device: &str) -> Result<()> {
         let output = AsyncCommand::new("diskutil")
             .args(&["zeroDisk", device])
@@ -189,7 +258,11 @@ impl PlatformDiskOps for MacOSDiskOps {
         }
     }
 
-    async fn extract_iso(&self, iso_path: &str, target_path: &str) -> Result<()> {
+    async fn extract_iso(&self, iso_path: &str, target_path: &str, cancellation: &crate::cancellation::CancellationToken) -> Result<()> {
+        if cancellation.is_cancelled() {
+            return Err(WowUsbError::Cancelled);
+        }
+
         // Mount the ISO
         let output = AsyncCommand::new("hdiutil")
             .args(&["attach", iso_path])
@@ -238,7 +311,81 @@ impl PlatformDiskOps for MacOSDiskOps {
         Ok(())
     }
 
-    async fn install_bootloader(&self, device: &str, bootloader_type: &str) -> Result<()> {
+    async fn extract_iso_file(&self, iso_path: &str, internal_path: &str, dest: &str) -> Result<()> {
+        let output = AsyncCommand::new("hdiutil")
+            .args(&["attach", "-readonly", "-nobrowse", iso_path])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(
+                format!("Failed to mount ISO: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        let output_str = String::from_utf8(output.stdout)?;
+        let mount_point = output_str
+            .lines()
+            .find(|line| line.trim().starts_with("/Volumes/"))
+            .and_then(|line| line.split_whitespace().last())
+            .ok_or_else(|| WowUsbError::iso_processing("hdiutil did not report a mount point"))?
+            .to_string();
+
+        let source = Path::new(&mount_point).join(internal_path);
+        let copy_result = AsyncCommand::new("cp")
+            .args(&["-p", &source.to_string_lossy(), dest])
+            .output()
+            .await;
+
+        let detach = AsyncCommand::new("hdiutil")
+            .args(&["detach", &mount_point])
+            .output()
+            .await;
+
+        let output = copy_result?;
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(
+                format!("Failed to extract {} from ISO: {}", internal_path, String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+        detach?;
+
+        Ok(())
+    }
+
+    async fn mount_iso_readonly(&self, iso_path: &str, mountpoint: &str) -> Result<String> {
+        std::fs::create_dir_all(mountpoint)?;
+
+        let output = AsyncCommand::new("hdiutil")
+            .args(&["attach", "-readonly", "-nobrowse", "-mountpoint", mountpoint, iso_path])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to mount ISO read-only: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(mountpoint.to_string())
+    }
+
+    async fn unmount_iso(&self, mountpoint: &str) -> Result<()> {
+        let output = AsyncCommand::new("hdiutil")
+            .args(&["detach", mountpoint])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to unmount ISO: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn install_bootloader(&self, device: &str, bootloader_type: &str, _boot_mountpoint: &str, _efi_mountpoint: &str) -> Result<()> {
         match bootloader_type {
             "grub2" => {
                 return Err(WowUsbError::not_implemented(
@@ -252,6 +399,81 @@ impl PlatformDiskOps for MacOSDiskOps {
             }
         }
     }
+
+    async fn check_filesystem(&self, partition: &str, _filesystem: &str) -> Result<crate::disk::FsckReport> {
+        // `diskutil repairVolume` covers every filesystem macOS mounts
+        // (FAT32/exFAT/NTFS/APFS) without needing a per-filesystem tool.
+        let output = AsyncCommand::new("diskutil")
+            .args(&["repairVolume", partition])
+            .output()
+            .await?;
+
+        let details = String::from_utf8_lossy(&output.stdout).to_string();
+
+        if !output.status.success() {
+            return Err(WowUsbError::filesystem(format!(
+                "diskutil repairVolume failed on {}: {}", partition, String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let repaired = details.to_lowercase().contains("repaired") || details.to_lowercase().contains("fixed");
+        Ok(crate::disk::FsckReport { clean: !repaired, repaired, details })
+    }
+
+    async fn probe_write_speed(&self, device: &str) -> Result<u64> {
+        // macOS's `dd` has no `oflag=direct` equivalent, so this includes
+        // some page-cache buffering and reads a little optimistic — good
+        // enough for a rough pre-flight estimate, not a benchmark.
+        const PROBE_MB: u64 = 4;
+        let started = std::time::Instant::now();
+
+        let output = AsyncCommand::new("dd")
+            .args(&[
+                "if=/dev/zero",
+                &format!("of={}", device),
+                "bs=1m",
+                &format!("count={}", PROBE_MB),
+            ])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Write-speed probe failed on {}: {}", device, String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let elapsed = started.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return Ok(0);
+        }
+        Ok(((PROBE_MB * 1024 * 1024) as f64 / elapsed) as u64)
+    }
+
+    async fn device_serial(&self, device: &str) -> Result<Option<String>> {
+        let output = AsyncCommand::new("diskutil").args(&["info", "-plist", device]).output().await.ok();
+        let Some(output) = output else { return Ok(None) };
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        // Cheap plist scrape rather than pulling in a plist parser for one
+        // field: `MediaUUID` is stable across unplug/replug for the same
+        // physical media, unlike the disk-number-based device path.
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut lines = text.lines();
+        while let Some(line) = lines.next() {
+            if line.contains("<key>MediaUUID</key>") {
+                if let Some(value_line) = lines.next() {
+                    if let Some(value) = value_line.trim().strip_prefix("<string>").and_then(|s| s.strip_suffix("</string>")) {
+                        return Ok(Some(value.to_string()));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 impl MacOSDiskOps {
@@ -295,6 +517,10 @@ impl MacOSDiskOps {
             mountpoint: None,
             is_removable,
             is_usb,
+            bus_type: if is_usb { Some("usb".to_string()) } else { None },
+            label: None,
+            used_space_bytes: None,
+            preselected: false,
         })
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::disk::{Device, PartitionConfig, PlatformDiskOps};
+use crate::disk::windows_volumes::DriveLetterAllocator;
 use crate::error::{WowUsbError, Result};
 use std::process::Command;
 use std::path::Path;
@@ -12,6 +13,7 @@ impl WindowsDiskOps {
     }
 }
 
+#[async_trait::async_trait]
 impl PlatformDiskOps for WindowsDiskOps {
     async fn list_devices(&self) -> Result<Vec<Device>> {
         // Use PowerShell to get disk information
@@ -69,10 +71,8 @@ impl PlatformDiskOps for WindowsDiskOps {
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
 
-                let is_usb = disk.get("BusType")
-                    .and_then(|v| v.as_str())
-                    .map(|s| s.to_lowercase().contains("usb"))
-                    .unwrap_or(false);
+                let bus_type = disk.get("BusType").and_then(|v| v.as_str()).map(|s| s.to_lowercase());
+                let is_usb = bus_type.as_deref().map(|s| s.contains("usb")).unwrap_or(false);
 
                 devices.push(Device {
                     name: device_path,
@@ -82,6 +82,10 @@ impl PlatformDiskOps for WindowsDiskOps {
                     mountpoint,
                     is_removable,
                     is_usb,
+                    bus_type,
+                    label: None,
+                    used_space_bytes: None,
+                    preselected: false,
                 });
             }
         }
@@ -122,6 +126,41 @@ impl PlatformDiskOps for WindowsDiskOps {
         Ok(result == "True")
     }
 
+    async fn check_permissions(&self, device: &str) -> Result<crate::disk::PermissionCheck> {
+        let disk_number = self.extract_disk_number(device)?;
+
+        // Raw disk access on Windows is gated on process elevation, not a
+        // per-object ACL check, so ask for the disk's read-only property
+        // (which Windows only reports accurately for an elevated process)
+        // rather than attempting an actual write.
+        let powershell_script = format!(r#"
+        $isAdmin = ([Security.Principal.WindowsPrincipal] [Security.Principal.WindowsIdentity]::GetCurrent()).IsInRole([Security.Principal.WindowsBuiltInRole]::Administrator)
+        try {{
+            Get-Disk -Number {} | Out-Null
+            if ($isAdmin) {{ "ok" }} else {{ "not_admin" }}
+        }} catch {{
+            "not_found"
+        }}
+        "#, disk_number);
+
+        let output = AsyncCommand::new("powershell")
+            .args(&["-Command", &powershell_script])
+            .output()
+            .await?;
+
+        let result = String::from_utf8(output.stdout)?.trim().to_string();
+        match result.as_str() {
+            "ok" => Ok(crate::disk::PermissionCheck::ok()),
+            "not_admin" => Ok(crate::disk::PermissionCheck::denied(
+                "Writing to a physical disk on Windows requires an elevated process. \
+                Restart WowUSB with \"Run as administrator\".",
+            )),
+            _ => Err(WowUsbError::device_operation(format!(
+                "Could not query disk {} to check permissions", disk_number
+            ))),
+        }
+    }
+
     async fn create_partitions(&self, device: &str, config: &[PartitionConfig]) -> Result<()> {
         let disk_number = self.extract_disk_number(device)?;
 
@@ -157,7 +196,11 @@ impl PlatformDiskOps for WindowsDiskOps {
             ));
         }
 
-        // Create partitions
+        // Create partitions, allocating currently-free drive letters up
+        // front instead of guessing 'C' + index (which collides with
+        // existing volumes on the host).
+        let drive_letters = DriveLetterAllocator::new().allocate(config.len()).await?;
+
         let mut current_size = 0;
         for (index, partition) in config.iter().enumerate() {
             let size_mb = if partition.size_mb == 0 {
@@ -166,9 +209,19 @@ impl PlatformDiskOps for WindowsDiskOps {
                 &format!("{}MB", partition.size_mb)
             };
 
+            // Windows' GPT-only disk initialization has no MBR active flag
+            // or `legacy_boot`-style concept to model, so the ESP GUID
+            // (`-GptType`) is the only one of `PartitionConfig`'s boot
+            // fields this backend can act on.
+            let gpt_type = if partition.esp {
+                " -GptType '{c12a7328-f81f-11d2-ba4b-00a0c93ec93b}'"
+            } else {
+                ""
+            };
+
             let create_script = format!(r#"
-            New-Partition -DiskNumber {} -Size {} -DriveLetter {} -AssignDriveLetter
-            "#, disk_number, size_mb, char(b'C' + index as u8));
+            New-Partition -DiskNumber {} -Size {} -DriveLetter {}{} -AssignDriveLetter
+            "#, disk_number, size_mb, drive_letters[index], gpt_type);
 
             let output = AsyncCommand::new("powershell")
                 .args(&["-Command", &create_script])
@@ -229,6 +282,33 @@ impl PlatformDiskOps for WindowsDiskOps {
         Ok(())
     }
 
+    /// Opens `device` for write and calls `FileStream.Flush(true)`, which
+    /// maps directly onto Win32's `FlushFileBuffers` — it blocks until the
+    /// OS confirms the drive's own cache has committed everything written
+    /// through the handle, rather than trusting that removing the drive
+    /// letter was enough.
+    async fn flush_device_write_cache(&self, device: &str) -> Result<()> {
+        let disk_number = self.extract_disk_number(device)?;
+
+        let flush_script = format!(r#"
+        $stream = [System.IO.File]::Open("\\.\PhysicalDrive{}", 'Open', 'Write')
+        try {{ $stream.Flush($true) }} finally {{ $stream.Close() }}
+        "#, disk_number);
+
+        let output = AsyncCommand::new("powershell")
+            .args(&["-Command", &flush_script])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to flush device write cache: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
     async fn wipe_device(&self, device: &str) -> Result<()> {
         let disk_number = self.extract_disk_number(device)?;
 
@@ -252,7 +332,8 @@ impl PlatformDiskOps for WindowsDiskOps {
 
     async fn validate_iso(&self, iso_path: &str) -> Result<bool> {
         // Use 7-Zip to validate ISO
-        let output = AsyncCommand::new("7z")
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+        let output = AsyncCommand::new(tool_paths.resolve("7z"))
             .args(&["t", iso_path])
             .output()
             .await?;
@@ -260,9 +341,14 @@ impl PlatformDiskOps for WindowsDiskOps {
         Ok(output.status.success())
     }
 
-    async fn extract_iso(&self, iso_path: &str, target_path: &str) -> Result<()> {
-        let output = AsyncCommand::new("7z")
-            .args(&["x", iso_path, f"-o{target_path}", "-y"])
+    async fn extract_iso(&self, iso_path: &str, target_path: &str, cancellation: &crate::cancellation::CancellationToken) -> Result<()> {
+        if cancellation.is_cancelled() {
+            return Err(WowUsbError::Cancelled);
+        }
+
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+        let output = AsyncCommand::new(tool_paths.resolve("7z"))
+            .args(&["x", iso_path, &format!("-o{}", target_path), "-y"])
             .output()
             .await?;
 
@@ -275,7 +361,82 @@ impl PlatformDiskOps for WindowsDiskOps {
         Ok(())
     }
 
-    async fn install_bootloader(&self, device: &str, bootloader_type: &str) -> Result<()> {
+    async fn extract_iso_file(&self, iso_path: &str, internal_path: &str, dest: &str) -> Result<()> {
+        let extract_dir = format!("{}\\wowusb_single_{}", std::env::temp_dir().to_string_lossy(), std::process::id());
+        std::fs::create_dir_all(&extract_dir)?;
+
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+        let output = AsyncCommand::new(tool_paths.resolve("7z"))
+            .args(&["x", iso_path, internal_path, &format!("-o{}", extract_dir), "-y"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            std::fs::remove_dir_all(&extract_dir).ok();
+            return Err(WowUsbError::iso_processing(
+                format!("Failed to extract {} from ISO: {}", internal_path, String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        let extracted = Path::new(&extract_dir).join(internal_path);
+        std::fs::rename(&extracted, dest)?;
+        std::fs::remove_dir_all(&extract_dir).ok();
+
+        Ok(())
+    }
+
+    async fn mount_iso_readonly(&self, iso_path: &str, _mountpoint: &str) -> Result<String> {
+        // Windows mounts disk images as a drive letter rather than an
+        // arbitrary path, so we mount via Mount-DiskImage and report back
+        // the assigned letter.
+        let mount_script = format!(
+            r#"$image = Mount-DiskImage -ImagePath "{}" -Access ReadOnly -PassThru
+            ($image | Get-Volume).DriveLetter"#,
+            iso_path
+        );
+
+        let output = AsyncCommand::new("powershell")
+            .args(&["-Command", &mount_script])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to mount ISO read-only: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        let drive_letter = String::from_utf8(output.stdout)?.trim().to_string();
+        if drive_letter.is_empty() {
+            return Err(WowUsbError::device_operation("Mount-DiskImage did not report a drive letter"));
+        }
+
+        Ok(format!("{}:", drive_letter))
+    }
+
+    async fn unmount_iso(&self, mountpoint: &str) -> Result<()> {
+        let unmount_script = format!(
+            r#"$vol = Get-Volume -DriveLetter "{}"
+            Dismount-DiskImage -ImagePath (Get-DiskImage -DevicePath (Get-Partition -DriveLetter "{}").DiskId).ImagePath"#,
+            mountpoint.trim_end_matches(':'),
+            mountpoint.trim_end_matches(':')
+        );
+
+        let output = AsyncCommand::new("powershell")
+            .args(&["-Command", &unmount_script])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(
+                format!("Failed to unmount ISO: {}", String::from_utf8_lossy(&output.stderr))
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn install_bootloader(&self, device: &str, bootloader_type: &str, _boot_mountpoint: &str, _efi_mountpoint: &str) -> Result<()> {
         match bootloader_type {
             "grub2" => {
                 // Windows bootloader installation would use tools like Rufus APIs
@@ -291,6 +452,72 @@ impl PlatformDiskOps for WindowsDiskOps {
             }
         }
     }
+
+    async fn check_filesystem(&self, partition: &str, _filesystem: &str) -> Result<crate::disk::FsckReport> {
+        let drive_letter = self.extract_drive_letter(partition)?;
+
+        let output = AsyncCommand::new("chkdsk")
+            .args(&[&format!("{}:", drive_letter), "/f", "/r"])
+            .output()
+            .await?;
+
+        let details = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        // chkdsk: 0 = no errors, 1 = errors found and fixed, 2+ = couldn't
+        // complete the check (open handles, unrecoverable corruption, ...).
+        match output.status.code() {
+            Some(0) => Ok(crate::disk::FsckReport { clean: true, repaired: false, details }),
+            Some(1) => Ok(crate::disk::FsckReport { clean: false, repaired: true, details }),
+            _ => Err(WowUsbError::filesystem(format!(
+                "chkdsk could not repair {}: {}", partition, details
+            ))),
+        }
+    }
+
+    async fn probe_write_speed(&self, _device: &str) -> Result<u64> {
+        // No convenient command-line raw-write benchmark on Windows;
+        // callers fall back to the assumed-speed default instead.
+        Err(WowUsbError::not_implemented(
+            "Write-speed probing not yet implemented on Windows"
+        ))
+    }
+
+    async fn device_serial(&self, device: &str) -> Result<Option<String>> {
+        let disk_number = if device.starts_with("\\\\.\\PhysicalDrive") {
+            device.strip_prefix("\\\\.\\PhysicalDrive").and_then(|n| n.parse::<u32>().ok())
+        } else {
+            None
+        };
+
+        let Some(disk_number) = disk_number else { return Ok(None) };
+
+        let powershell_script = format!(
+            "(Get-Disk -Number {}).SerialNumber",
+            disk_number
+        );
+
+        let output = AsyncCommand::new("powershell")
+            .args(&["-Command", &powershell_script])
+            .output()
+            .await
+            .ok();
+
+        let Some(output) = output else { return Ok(None) };
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let serial = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if serial.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(serial))
+        }
+    }
 }
 
 impl WindowsDiskOps {
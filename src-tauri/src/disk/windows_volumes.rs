@@ -0,0 +1,68 @@
+use crate::error::{WowUsbError, Result};
+use tokio::process::Command as AsyncCommand;
+
+/// Finds a drive letter not currently in use instead of guessing
+/// `'C' + index`, which collides with existing volumes on the host.
+pub struct DriveLetterAllocator;
+
+impl DriveLetterAllocator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Query Windows for drive letters already assigned to any volume.
+    async fn used_letters(&self) -> Result<Vec<char>> {
+        let output = AsyncCommand::new("powershell")
+            .args(&["-Command", "(Get-Volume | Where-Object DriveLetter).DriveLetter -join ','"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Failed to enumerate assigned drive letters: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let letters = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .split(',')
+            .filter_map(|s| s.trim().chars().next())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        Ok(letters)
+    }
+
+    /// Allocate `count` free drive letters, starting after `D:` (`A`-`C`
+    /// are reserved for floppy/system use by convention).
+    pub async fn allocate(&self, count: usize) -> Result<Vec<char>> {
+        let used = self.used_letters().await?;
+        let mut allocated = Vec::with_capacity(count);
+
+        for letter in b'D'..=b'Z' {
+            if allocated.len() == count {
+                break;
+            }
+
+            let letter = letter as char;
+            if !used.contains(&letter) {
+                allocated.push(letter);
+            }
+        }
+
+        if allocated.len() < count {
+            return Err(WowUsbError::device_operation(
+                "Ran out of free drive letters to assign to new partitions",
+            ));
+        }
+
+        Ok(allocated)
+    }
+}
+
+impl Default for DriveLetterAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
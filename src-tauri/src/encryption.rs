@@ -0,0 +1,184 @@
+use crate::error::{WowUsbError, Result};
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command as AsyncCommand;
+
+/// Encrypt the payload partition after it's written, so the stick's
+/// contents are protected at rest. Only meaningful for
+/// [`crate::config::WriteMode::Extract`] — a raw sector copy has no
+/// formatted partition to encrypt into. See
+/// [`crate::disk::DiskManager::create_bootable_usb`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum EncryptionOptions {
+    /// BitLocker To Go. `recovery_key_path` is where the numerical recovery
+    /// key gets saved; only available when creating from Windows.
+    BitLocker { recovery_key_path: String },
+    /// A VeraCrypt container file dropped onto the payload partition,
+    /// rather than encrypting the partition itself, so the stick still
+    /// mounts normally and the encrypted contents stay opt-in.
+    VeraCrypt {
+        password: String,
+        container_size_mb: u64,
+    },
+}
+
+impl EncryptionOptions {
+    /// Apply this encryption method to the just-written payload partition
+    /// mounted at `mountpoint` (a drive letter like `"E:"` on Windows, a
+    /// filesystem path everywhere else).
+    pub async fn apply(&self, mountpoint: &str) -> Result<()> {
+        match self {
+            EncryptionOptions::BitLocker { recovery_key_path } => {
+                BitLockerEncryptor::new()
+                    .encrypt_partition(mountpoint, recovery_key_path)
+                    .await
+            }
+            EncryptionOptions::VeraCrypt { password, container_size_mb } => {
+                let container_path = format!("{}/veracrypt_container", mountpoint);
+                VeraCryptEncryptor::new()
+                    .create_container(&container_path, *container_size_mb, password, "exFAT")
+                    .await
+            }
+        }
+    }
+}
+
+/// Encrypts the extra data partition of a stick using BitLocker To Go
+/// (`manage-bde`), for enterprises that require encrypted removable media
+/// on Windows.
+pub struct BitLockerEncryptor;
+
+impl BitLockerEncryptor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Enable BitLocker on `drive_letter` (e.g. `"E:"`) and save the
+    /// numerical recovery key to `recovery_key_path`.
+    #[cfg(target_os = "windows")]
+    pub async fn encrypt_partition(&self, drive_letter: &str, recovery_key_path: &str) -> Result<()> {
+        let output = AsyncCommand::new("manage-bde")
+            .args(&["-on", drive_letter, "-RecoveryPassword"])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Failed to enable BitLocker on {}: {}",
+                drive_letter,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        let export_output = AsyncCommand::new("manage-bde")
+            .args(&["-protectors", "-get", drive_letter])
+            .output()
+            .await?;
+
+        if !export_output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Failed to read BitLocker recovery key for {}: {}",
+                drive_letter,
+                String::from_utf8_lossy(&export_output.stderr)
+            )));
+        }
+
+        std::fs::write(recovery_key_path, export_output.stdout)?;
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub async fn encrypt_partition(&self, _drive_letter: &str, _recovery_key_path: &str) -> Result<()> {
+        Err(WowUsbError::platform(
+            "BitLocker To Go encryption is only available on Windows",
+        ))
+    }
+}
+
+impl Default for BitLockerEncryptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creates an encrypted VeraCrypt container file on the data partition via
+/// the VeraCrypt CLI, for cross-platform encrypted storage on the same
+/// stick regardless of host OS.
+pub struct VeraCryptEncryptor;
+
+impl VeraCryptEncryptor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn is_available(&self) -> bool {
+        AsyncCommand::new("veracrypt")
+            .arg("--version")
+            .output()
+            .await
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Create a `size_mb` container file at `container_path` protected by
+    /// `password`, formatted with `filesystem` (e.g. `"exFAT"`).
+    pub async fn create_container(
+        &self,
+        container_path: &str,
+        size_mb: u64,
+        password: &str,
+        filesystem: &str,
+    ) -> Result<()> {
+        if !self.is_available().await {
+            return Err(WowUsbError::not_implemented(
+                "VeraCrypt CLI is not installed on this system",
+            ));
+        }
+
+        let mut child = AsyncCommand::new("veracrypt")
+            .args(&[
+                "--text",
+                "--create",
+                container_path,
+                "--volume-type=normal",
+                &format!("--size={}M", size_mb),
+                &format!("--filesystem={}", filesystem),
+                "--encryption=AES",
+                "--hash=SHA-512",
+                "--pim=0",
+                "--random-source=/dev/urandom",
+                "--non-interactive",
+                "--stdin",
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        // Password goes over stdin rather than argv, so it never shows up
+        // in `ps`/`/proc/<pid>/cmdline` for the life of the process.
+        let mut stdin = child.stdin.take().expect("stdin was piped");
+        stdin.write_all(password.as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+        drop(stdin);
+
+        let output = child.wait_with_output().await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Failed to create VeraCrypt container: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for VeraCryptEncryptor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Host OS, kernel, tool-version, and USB controller details captured
+/// alongside every [`crate::report::CreationReport`]. Most boot-failure bug
+/// reports hinge on exactly these details, and asking for them after the
+/// fact loses information once a tool gets upgraded or the drive is
+/// unplugged.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/bindings/")]
+pub struct EnvironmentSnapshot {
+    pub os: String,
+    pub os_family: String,
+    pub arch: String,
+    pub kernel_version: Option<String>,
+    pub app_version: String,
+    pub tool_versions: Vec<(String, String)>,
+    pub usb_controller_info: Option<String>,
+}
+
+impl EnvironmentSnapshot {
+    pub fn collect() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            os_family: std::env::consts::FAMILY.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            kernel_version: kernel_version(),
+            app_version: crate::version::VERSION.to_string(),
+            tool_versions: tool_versions(),
+            usb_controller_info: usb_controller_info(),
+        }
+    }
+}
+
+fn kernel_version() -> Option<String> {
+    let output = std::process::Command::new("uname").arg("-r").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Tools whose exact version most often explains a boot failure that's
+/// otherwise unreproducible on the maintainer's own machine.
+const PROBED_TOOLS: &[(&str, &[&str])] = &[
+    ("parted", &["--version"]),
+    ("mkfs.fat", &["--help"]),
+    ("mkfs.ntfs", &["--version"]),
+    ("7z", &["--help"]),
+    ("grub-install", &["--version"]),
+    ("grub2-install", &["--version"]),
+];
+
+fn tool_versions() -> Vec<(String, String)> {
+    let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+    PROBED_TOOLS
+        .iter()
+        .filter_map(|(tool, args)| {
+            let output = std::process::Command::new(tool_paths.resolve(tool)).args(*args).output().ok()?;
+            let version = String::from_utf8_lossy(&output.stdout).lines().next().unwrap_or_default().to_string();
+            Some((tool.to_string(), version))
+        })
+        .collect()
+}
+
+/// USB host controller listing, since many boot failures on real hardware
+/// trace back to a specific xHCI/EHCI controller mishandling the stick
+/// rather than anything WowUSB wrote to it.
+#[cfg(target_os = "linux")]
+fn usb_controller_info() -> Option<String> {
+    let output = std::process::Command::new("lspci").arg("-nn").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let controllers: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| line.to_lowercase().contains("usb"))
+        .map(|line| line.to_string())
+        .collect();
+
+    if controllers.is_empty() {
+        None
+    } else {
+        Some(controllers.join("\n"))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn usb_controller_info() -> Option<String> {
+    None
+}
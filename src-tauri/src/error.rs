@@ -31,6 +31,9 @@ pub enum WowUsbError {
 
     #[error("Not implemented: {0}")]
     NotImplemented(String),
+
+    #[error("Target device {0} disappeared during the operation")]
+    DeviceRemoved(String),
 }
 
 impl WowUsbError {
@@ -65,6 +68,47 @@ impl WowUsbError {
     pub fn not_implemented(msg: impl Into<String>) -> Self {
         Self::NotImplemented(msg.into())
     }
+
+    pub fn device_removed(device: impl Into<String>) -> Self {
+        Self::DeviceRemoved(device.into())
+    }
+
+    /// Stable machine-readable identifier for this error's variant, used to
+    /// group failures (e.g. in metrics or support bundles) without matching
+    /// on the human-readable message text.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::Io(_) => "io",
+            Self::DeviceOperation(_) => "device_operation",
+            Self::Filesystem(_) => "filesystem",
+            Self::Platform(_) => "platform",
+            Self::Validation(_) => "validation",
+            Self::IsoProcessing(_) => "iso_processing",
+            Self::Progress(_) => "progress",
+            Self::Configuration(_) => "configuration",
+            Self::Cancelled => "cancelled",
+            Self::NotImplemented(_) => "not_implemented",
+            Self::DeviceRemoved(_) => "device_removed",
+        }
+    }
+
+    /// The [`error_code`](Self::error_code) and display message bundled as a
+    /// serializable value, for commands that want the frontend to branch on
+    /// the error kind instead of matching against `.to_string()` text.
+    pub fn to_payload(&self) -> ErrorPayload {
+        ErrorPayload {
+            code: self.error_code().to_string(),
+            message: self.to_string(),
+        }
+    }
+}
+
+/// See [`WowUsbError::to_payload`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ts_rs::TS)]
+#[ts(export, export_to = "../../src/bindings/")]
+pub struct ErrorPayload {
+    pub code: String,
+    pub message: String,
 }
 
 pub type Result<T> = std::result::Result<T, WowUsbError>;
\ No newline at end of file
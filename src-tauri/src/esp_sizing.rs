@@ -0,0 +1,136 @@
+use std::path::{Path, PathBuf};
+
+/// Directories/files, relative to the app's bundled resources, that
+/// [`crate::disk::DiskManager`] may copy onto a support ESP — GRUB modules
+/// for every firmware/architecture combination it targets, the UEFI:NTFS
+/// driver stubs used to boot an NTFS payload on firmware that can't read it
+/// natively, and the bundled memtest86 EFI binary. Kept in one place so
+/// sizing and whatever eventually stages these files onto the ESP can't
+/// silently drift apart.
+const ESP_PAYLOAD_PATHS: &[&str] = &[
+    crate::grub_tooling::BUNDLED_I386_PC_MODULES_DIR,
+    "grub-modules/x86_64-efi",
+    "grub-modules/i386-efi",
+    "grub-modules/arm64-efi",
+    "uefi-ntfs/ntfs_x64.efi",
+    "uefi-ntfs/ntfs_ia32.efi",
+    "uefi-ntfs/ntfs_aa64.efi",
+    "memtest86/memtest.efi",
+];
+
+/// Added on top of the measured payload for `grub.cfg`, FAT32 cluster
+/// overhead, and any other small files staged alongside the binaries above.
+const ESP_SIZE_MARGIN_MB: u64 = 8;
+
+/// Floor under which shrinking the ESP further isn't worth it: small FAT32
+/// partitions waste an outsized fraction of their space on filesystem
+/// overhead, and some firmware refuses to boot from an ESP below ~32 MB.
+const ESP_SIZE_FLOOR_MB: u64 = 32;
+
+/// Used when the bundled resources can't be found (a dev build run outside
+/// its packaged layout, or a platform this hasn't been ported to), so
+/// partitioning still gets a working size instead of failing outright. This
+/// is deliberately the same value the ESP was hardcoded to before this
+/// module existed.
+pub const ESP_SIZE_FALLBACK_MB: u64 = 512;
+
+/// Sum the on-disk size of everything under `resources_dir` that might be
+/// copied onto a support ESP, plus a margin, so a stick only pays for the
+/// EFI payload it actually carries instead of a hardcoded 512 MB — most of
+/// which sat empty on sticks whose payload filesystem boots UEFI natively.
+pub fn estimated_esp_size_mb(resources_dir: &str) -> u64 {
+    let root = Path::new(resources_dir);
+    if !root.exists() {
+        return ESP_SIZE_FALLBACK_MB;
+    }
+
+    let payload_bytes: u64 = ESP_PAYLOAD_PATHS.iter().map(|relative| dir_or_file_size(&root.join(relative))).sum();
+    let payload_mb = payload_bytes.div_ceil(1024 * 1024);
+
+    (payload_mb + ESP_SIZE_MARGIN_MB).max(ESP_SIZE_FLOOR_MB)
+}
+
+fn dir_or_file_size(path: &Path) -> u64 {
+    let metadata = match std::fs::metadata(path) {
+        Ok(m) => m,
+        Err(_) => return 0,
+    };
+
+    if metadata.is_file() {
+        metadata.len()
+    } else if metadata.is_dir() {
+        walk_dir_size(path)
+    } else {
+        0
+    }
+}
+
+fn walk_dir_size(dir: &Path) -> u64 {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return 0,
+    };
+
+    entries.filter_map(|e| e.ok()).map(|entry| dir_or_file_size(&entry.path())).sum()
+}
+
+/// `resources/` next to WowUSB's own executable, mirroring
+/// [`crate::tool_paths::ToolPaths`]'s vendored-tools convention for a
+/// portable install that carries its own bundled assets.
+fn resources_dir() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    Some(exe_dir.join("resources"))
+}
+
+/// [`estimated_esp_size_mb`] using this install's own bundled resources
+/// directory, falling back to [`ESP_SIZE_FALLBACK_MB`] when it can't be
+/// resolved (e.g. `cargo test`, or a dev build run from the build output
+/// directly).
+pub fn detect_esp_size_mb() -> u64 {
+    match resources_dir() {
+        Some(dir) => estimated_esp_size_mb(&dir.to_string_lossy()),
+        None => ESP_SIZE_FALLBACK_MB,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wowusb_esp_sizing_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn falls_back_when_resources_dir_is_missing() {
+        let missing = std::env::temp_dir().join(format!("wowusb_esp_sizing_missing_{}", std::process::id()));
+        assert_eq!(estimated_esp_size_mb(&missing.to_string_lossy()), ESP_SIZE_FALLBACK_MB);
+    }
+
+    #[test]
+    fn sums_payload_and_stays_above_the_floor_for_a_small_payload() {
+        let root = temp_dir("small");
+        std::fs::create_dir_all(root.join("grub-modules/i386-pc")).unwrap();
+        std::fs::write(root.join("grub-modules/i386-pc/normal.mod"), vec![0u8; 1024]).unwrap();
+
+        assert_eq!(estimated_esp_size_mb(&root.to_string_lossy()), ESP_SIZE_FLOOR_MB);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn grows_past_the_floor_for_a_large_multi_architecture_payload() {
+        let root = temp_dir("large");
+        std::fs::create_dir_all(root.join("grub-modules/i386-pc")).unwrap();
+        std::fs::create_dir_all(root.join("grub-modules/x86_64-efi")).unwrap();
+        std::fs::write(root.join("grub-modules/i386-pc/normal.mod"), vec![0u8; 40 * 1024 * 1024]).unwrap();
+        std::fs::write(root.join("grub-modules/x86_64-efi/normal.mod"), vec![0u8; 40 * 1024 * 1024]).unwrap();
+
+        let size = estimated_esp_size_mb(&root.to_string_lossy());
+        assert_eq!(size, 80 + ESP_SIZE_MARGIN_MB);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
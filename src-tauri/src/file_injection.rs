@@ -0,0 +1,168 @@
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A single file to place on the medium after extraction, overriding
+/// whatever the ISO shipped at that path (or adding a new one) — the
+/// declarative building block behind repeatable customized sticks
+/// (wallpapers, scripts, config files) that would otherwise need a manual
+/// post-copy edit every time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileInjection {
+    /// Path relative to the medium's root, e.g. `"boot/grub/grub.cfg"`.
+    pub medium_path: String,
+    /// Local file to copy from.
+    pub source_path: String,
+    /// Whether `{{hostname}}`/`{{serial}}`/`{{date}}` placeholders in this
+    /// file's contents should be resolved per [`TemplateContext`] before
+    /// writing it to the medium. Left off for binary files (wallpapers,
+    /// icons) where substitution would just corrupt the data.
+    #[serde(default)]
+    pub template: bool,
+}
+
+/// Per-device values substituted into templated injections, so a batch
+/// provisioning run (see [`crate::batch::DuplicatorJob`]) gives every
+/// stick it images a distinct hostname without the caller having to
+/// pre-render one config file per device.
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    pub hostname: String,
+    pub serial: String,
+    pub date: String,
+}
+
+impl TemplateContext {
+    /// Derive a context for `target_device`. `serial` is a short
+    /// deterministic digest of the device path rather than a real hardware
+    /// serial (not reliably obtainable across platforms), which is enough
+    /// to give repeat imaging runs of the same device slot a stable,
+    /// distinct-looking identifier.
+    pub fn for_device(target_device: &str, now: chrono::DateTime<chrono::Utc>) -> Self {
+        let serial = Self::derive_serial(target_device);
+        Self {
+            hostname: format!("wowusb-{}", serial),
+            serial,
+            date: now.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    fn derive_serial(target_device: &str) -> String {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        target_device.hash(&mut hasher);
+        format!("{:08x}", hasher.finish() as u32)
+    }
+
+    fn resolve(&self, contents: &str) -> String {
+        contents
+            .replace("{{hostname}}", &self.hostname)
+            .replace("{{serial}}", &self.serial)
+            .replace("{{date}}", &self.date)
+    }
+}
+
+/// Copy each injection's `source_path` onto `medium_root` at its
+/// `medium_path`, creating parent directories as needed and overwriting
+/// whatever extraction already placed there. Applied after extraction so
+/// injected boot configs and the like take effect before the bootloader
+/// install step reads them.
+pub fn apply(medium_root: impl AsRef<Path>, injections: &[FileInjection], context: &TemplateContext) -> Result<()> {
+    for injection in injections {
+        let dest = medium_root.as_ref().join(&injection.medium_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if injection.template {
+            let contents = std::fs::read_to_string(&injection.source_path).map_err(|e| {
+                WowUsbError::filesystem(format!(
+                    "Failed to read templated injection {}: {}", injection.source_path, e
+                ))
+            })?;
+            std::fs::write(&dest, context.resolve(&contents))?;
+        } else {
+            std::fs::copy(&injection.source_path, &dest).map_err(|e| {
+                WowUsbError::filesystem(format!(
+                    "Failed to inject {} -> {}: {}", injection.source_path, injection.medium_path, e
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> TemplateContext {
+        TemplateContext {
+            hostname: "wowusb-test".to_string(),
+            serial: "deadbeef".to_string(),
+            date: "2026-08-09".to_string(),
+        }
+    }
+
+    #[test]
+    fn applies_injection_creating_parent_dirs() {
+        let base = std::env::temp_dir().join(format!("wowusb_inject_test_{}", std::process::id()));
+        let medium_root = base.join("medium");
+        let source = base.join("wallpaper.png");
+        std::fs::create_dir_all(&medium_root).unwrap();
+        std::fs::write(&source, b"fake image bytes").unwrap();
+
+        let injections = vec![FileInjection {
+            medium_path: "boot/grub/wallpaper.png".to_string(),
+            source_path: source.to_string_lossy().to_string(),
+            template: false,
+        }];
+
+        apply(&medium_root, &injections, &context()).unwrap();
+
+        let dest = medium_root.join("boot/grub/wallpaper.png");
+        assert_eq!(std::fs::read(&dest).unwrap(), b"fake image bytes");
+
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn missing_source_file_errors() {
+        let base = std::env::temp_dir().join(format!("wowusb_inject_missing_{}", std::process::id()));
+        std::fs::create_dir_all(&base).unwrap();
+
+        let injections = vec![FileInjection {
+            medium_path: "does/not/matter.cfg".to_string(),
+            source_path: base.join("nonexistent.cfg").to_string_lossy().to_string(),
+            template: false,
+        }];
+
+        assert!(apply(&base, &injections, &context()).is_err());
+
+        std::fs::remove_dir_all(&base).ok();
+    }
+
+    #[test]
+    fn templated_injection_resolves_placeholders() {
+        let base = std::env::temp_dir().join(format!("wowusb_inject_template_{}", std::process::id()));
+        let medium_root = base.join("medium");
+        let source = base.join("cloud-init.yaml");
+        std::fs::create_dir_all(&medium_root).unwrap();
+        std::fs::write(&source, "hostname: {{hostname}}\nserial: {{serial}}\ncreated: {{date}}\n").unwrap();
+
+        let injections = vec![FileInjection {
+            medium_path: "cloud-init.yaml".to_string(),
+            source_path: source.to_string_lossy().to_string(),
+            template: true,
+        }];
+
+        apply(&medium_root, &injections, &context()).unwrap();
+
+        let rendered = std::fs::read_to_string(medium_root.join("cloud-init.yaml")).unwrap();
+        assert_eq!(rendered, "hostname: wowusb-test\nserial: deadbeef\ncreated: 2026-08-09\n");
+
+        std::fs::remove_dir_all(&base).ok();
+    }
+}
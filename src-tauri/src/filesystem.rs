@@ -1,11 +1,39 @@
 use crate::error::{WowUsbError, Result};
+use crate::target_os::TargetOs;
 use std::collections::HashMap;
 
 pub trait PlatformFilesystemOps: Send + Sync {
     fn get_available_filesystems(&self) -> Result<Vec<String>>;
     fn check_filesystem_support(&self, fs_type: &str) -> Result<bool>;
     fn get_filesystem_info(&self, fs_type: &str) -> Result<FilesystemInfo>;
-    fn get_optimal_filesystem(&self, has_large_files: bool, target_os: &str) -> Result<String>;
+    fn get_optimal_filesystem(&self, has_large_files: bool, target_os: TargetOs) -> Result<String>;
+}
+
+/// Whether UEFI firmware can boot a system from a filesystem directly, or
+/// needs a small FAT32 "support" ESP alongside it holding the actual boot
+/// files, or has no UEFI boot path at all (BIOS/CSM only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UefiBootability {
+    /// Firmware reads boot files straight off this filesystem.
+    Native,
+    /// Firmware can't read this filesystem; needs a FAT32 ESP alongside it.
+    RequiresEsp,
+    /// No UEFI boot path; only bootable via legacy BIOS/CSM.
+    Unsupported,
+}
+
+/// Classify a filesystem's UEFI bootability by name. Standalone (rather
+/// than a [`FilesystemInfo`] field lookup) because [`DiskManager`]'s
+/// partition planning only has the filesystem name to go on, not an
+/// instantiated, platform-specific [`FilesystemManager`].
+///
+/// [`DiskManager`]: crate::disk::DiskManager
+pub fn uefi_bootability_for(fs_type: &str) -> UefiBootability {
+    match fs_type.to_lowercase().as_str() {
+        "fat32" => UefiBootability::Native,
+        "ntfs" | "exfat" | "apfs" => UefiBootability::RequiresEsp,
+        _ => UefiBootability::Unsupported,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -15,6 +43,7 @@ pub struct FilesystemInfo {
     pub max_file_size: u64,
     pub max_volume_size: u64,
     pub recommended_for: Vec<String>,
+    pub uefi_bootability: UefiBootability,
     pros: Vec<String>,
     cons: Vec<String>,
 }
@@ -35,6 +64,9 @@ impl FilesystemManager {
         #[cfg(target_os = "macos")]
         let ops = Box::new(crate::filesystem::macos::MacOSFilesystemOps::new());
 
+        #[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+        let ops = Box::new(crate::filesystem::bsd::BsdFilesystemOps::new());
+
         Self {
             ops,
             filesystem_cache: std::sync::Mutex::new(HashMap::new()),
@@ -70,24 +102,70 @@ impl FilesystemManager {
         Ok(info)
     }
 
-    pub fn get_optimal_filesystem(&self, has_large_files: bool, target_os: &str) -> Result<String> {
+    pub fn get_optimal_filesystem(&self, has_large_files: bool, target_os: TargetOs) -> Result<String> {
         self.ops.get_optimal_filesystem(has_large_files, target_os)
     }
 
     pub fn format_size_bytes(&self, bytes: u64) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-        let mut size = bytes as f64;
-        let mut unit_index = 0;
+        crate::units::format_size_bytes(bytes)
+    }
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "openbsd"))]
+pub mod bsd {
+    use super::*;
+
+    pub struct BsdFilesystemOps;
+
+    impl BsdFilesystemOps {
+        pub fn new() -> Self {
+            Self
+        }
+    }
+
+    impl PlatformFilesystemOps for BsdFilesystemOps {
+        fn get_available_filesystems(&self) -> Result<Vec<String>> {
+            Ok(vec!["FAT32".to_string(), "exFAT".to_string(), "UFS".to_string()])
+        }
 
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
+        fn check_filesystem_support(&self, fs_type: &str) -> Result<bool> {
+            match fs_type.to_lowercase().as_str() {
+                "fat32" => Ok(std::path::Path::new("/sbin/newfs_msdos").exists()),
+                "exfat" => Ok(std::path::Path::new("/usr/local/sbin/mkexfatfs").exists()),
+                "ufs" => Ok(std::path::Path::new("/sbin/newfs").exists()),
+                _ => Ok(false),
+            }
         }
 
-        if unit_index == 0 {
-            format!("{} {}", bytes, UNITS[unit_index])
-        } else {
-            format!("{:.1} {}", size, UNITS[unit_index])
+        fn get_filesystem_info(&self, fs_type: &str) -> Result<FilesystemInfo> {
+            match fs_type.to_lowercase().as_str() {
+                "ufs" => Ok(FilesystemInfo {
+                    name: "UFS".to_string(),
+                    supports_large_files: true,
+                    max_file_size: 8 * 1024 * 1024 * 1024 * 1024, // 8TB
+                    max_volume_size: 8 * 1024 * 1024 * 1024 * 1024,
+                    recommended_for: vec!["FreeBSD".to_string(), "OpenBSD".to_string()],
+                    uefi_bootability: UefiBootability::Unsupported,
+                    pros: vec!["Native BSD support".to_string(), "Mature and stable".to_string()],
+                    cons: vec!["No support outside the BSDs".to_string()],
+                }),
+                _ => {
+                    let windows_ops = super::windows::WindowsFilesystemOps::new();
+                    windows_ops.get_filesystem_info(fs_type)
+                }
+            }
+        }
+
+        fn get_optimal_filesystem(&self, has_large_files: bool, target_os: TargetOs) -> Result<String> {
+            // No BSD variant in `TargetOs` (the BSD backend is selected by the
+            // host running WowUSB, not by what the stick boots into), so
+            // there's nothing BSD-specific left to special-case here.
+            let _ = target_os;
+            if has_large_files {
+                Ok("exFAT".to_string())
+            } else {
+                Ok("FAT32".to_string())
+            }
         }
     }
 }
@@ -129,6 +207,7 @@ pub mod windows {
                     max_file_size: 16 * 1024 * 1024 * 1024 * 1024, // 16TB
                     max_volume_size: 256 * 1024 * 1024 * 1024 * 1024, // 256TB
                     recommended_for: vec!["Windows".to_string(), "Large files".to_string()],
+                    uefi_bootability: UefiBootability::RequiresEsp,
                     pros: vec![
                         "Excellent large file support".to_string(),
                         "Built-in Windows support".to_string(),
@@ -146,6 +225,7 @@ pub mod windows {
                     max_file_size: 4 * 1024 * 1024 * 1024 - 1, // 4GB - 1 byte
                     max_volume_size: 2 * 1024 * 1024 * 1024, // 2TB
                     recommended_for: vec!["Maximum compatibility".to_string()],
+                    uefi_bootability: UefiBootability::Native,
                     pros: vec![
                         "Universal compatibility".to_string(),
                         "Simple and reliable".to_string(),
@@ -163,6 +243,7 @@ pub mod windows {
                     max_file_size: 128 * 1024 * 1024 * 1024 * 1024, // 128EB (theoretical)
                     max_volume_size: 128 * 1024 * 1024 * 1024 * 1024, // 128EB
                     recommended_for: vec!["Cross-platform".to_string(), "Flash drives".to_string()],
+                    uefi_bootability: UefiBootability::RequiresEsp,
                     pros: vec![
                         "Large file support".to_string(),
                         "Good cross-platform support".to_string(),
@@ -179,8 +260,8 @@ pub mod windows {
             }
         }
 
-        fn get_optimal_filesystem(&self, has_large_files: bool, target_os: &str) -> Result<String> {
-            if target_os.to_lowercase() == "windows" {
+        fn get_optimal_filesystem(&self, has_large_files: bool, target_os: TargetOs) -> Result<String> {
+            if target_os == TargetOs::Windows {
                 Ok("NTFS".to_string())
             } else if has_large_files {
                 Ok("exFAT".to_string())
@@ -246,6 +327,7 @@ pub mod linux {
                     max_file_size: 16 * 1024 * 1024 * 1024 * 1024, // 16TB
                     max_volume_size: 16 * 1024 * 1024 * 1024 * 1024, // 16TB
                     recommended_for: vec!["Flash drives".to_string(), "SSD".to_string(), "Linux".to_string()],
+                    uefi_bootability: UefiBootability::Unsupported,
                     pros: vec![
                         "Optimized for flash memory".to_string(),
                         "Excellent performance".to_string(),
@@ -262,6 +344,7 @@ pub mod linux {
                     max_file_size: 16 * 1024 * 1024 * 1024 * 1024, // 16EB
                     max_volume_size: 16 * 1024 * 1024 * 1024 * 1024, // 16EB
                     recommended_for: vec!["Advanced features".to_string(), "Linux".to_string()],
+                    uefi_bootability: UefiBootability::Unsupported,
                     pros: vec![
                         "Copy-on-write".to_string(),
                         "Snapshots".to_string(),
@@ -279,6 +362,7 @@ pub mod linux {
                     max_file_size: 16 * 1024 * 1024 * 1024 * 1024, // 16TB
                     max_volume_size: 1 * 1024 * 1024 * 1024 * 1024, // 1EB
                     recommended_for: vec!["Linux".to_string(), "Reliability".to_string()],
+                    uefi_bootability: UefiBootability::Unsupported,
                     pros: vec![
                         "Mature and stable".to_string(),
                         "Good performance".to_string(),
@@ -297,10 +381,10 @@ pub mod linux {
             }
         }
 
-        fn get_optimal_filesystem(&self, has_large_files: bool, target_os: &str) -> Result<String> {
-            match target_os.to_lowercase().as_str() {
-                "windows" => Ok("NTFS".to_string()),
-                "linux" => {
+        fn get_optimal_filesystem(&self, has_large_files: bool, target_os: TargetOs) -> Result<String> {
+            match target_os {
+                TargetOs::Windows => Ok("NTFS".to_string()),
+                TargetOs::LinuxLive | TargetOs::LinuxInstall => {
                     if has_large_files {
                         // Check for F2FS first, then fallback to others
                         if self.check_filesystem_support("F2FS").unwrap_or(false) {
@@ -356,6 +440,7 @@ pub mod macos {
                     max_file_size: 8 * 1024 * 1024 * 1024 * 1024, // 8EB
                     max_volume_size: 8 * 1024 * 1024 * 1024 * 1024, // 8EB
                     recommended_for: vec!["macOS".to_string(), "Modern features".to_string()],
+                    uefi_bootability: UefiBootability::RequiresEsp,
                     pros: vec![
                         "Native macOS support".to_string(),
                         "Snapshots and clones".to_string(),
@@ -375,9 +460,9 @@ pub mod macos {
             }
         }
 
-        fn get_optimal_filesystem(&self, has_large_files: bool, target_os: &str) -> Result<String> {
-            match target_os.to_lowercase().as_str() {
-                "macos" => Ok("APFS".to_string()),
+        fn get_optimal_filesystem(&self, has_large_files: bool, target_os: TargetOs) -> Result<String> {
+            match target_os {
+                TargetOs::MacOs => Ok("APFS".to_string()),
                 _ => {
                     if has_large_files {
                         Ok("exFAT".to_string())
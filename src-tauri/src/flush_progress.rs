@@ -0,0 +1,56 @@
+/// Bytes of dirty (not-yet-written-back) page cache, so the [`crate::progress::Stage::Flush`]
+/// stage can report real progress instead of sitting at a fixed percentage
+/// while `unmount`/`sync` blocks on the kernel writing everything out.
+///
+/// Only implemented where the OS exposes this cheaply (`/proc/meminfo` on
+/// Linux); other platforms report `None` and the caller falls back to a
+/// time-based message.
+#[cfg(target_os = "linux")]
+pub fn dirty_page_cache_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    parse_dirty_line(&meminfo)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn dirty_page_cache_bytes() -> Option<u64> {
+    None
+}
+
+fn parse_dirty_line(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|l| l.starts_with("Dirty:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// A human-readable status line for the flush stage, given the last known
+/// dirty-page-cache size (if this platform can observe it at all).
+pub fn flush_message(dirty_bytes: Option<u64>) -> String {
+    match dirty_bytes {
+        Some(0) => "Flushing writes to device (page cache clear)".to_string(),
+        Some(bytes) => format!("Flushing writes to device ({} MB still cached)", bytes / (1024 * 1024)),
+        None => "Flushing writes to device (this can take a while on slow media)".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dirty_line_from_meminfo() {
+        let meminfo = "MemTotal:       16384000 kB\nDirty:              1024 kB\nWriteback:             0 kB\n";
+        assert_eq!(parse_dirty_line(meminfo), Some(1024 * 1024));
+    }
+
+    #[test]
+    fn missing_dirty_line_yields_none() {
+        let meminfo = "MemTotal:       16384000 kB\n";
+        assert_eq!(parse_dirty_line(meminfo), None);
+    }
+
+    #[test]
+    fn message_mentions_remaining_megabytes() {
+        assert_eq!(flush_message(Some(5 * 1024 * 1024)), "Flushing writes to device (5 MB still cached)");
+        assert!(flush_message(None).contains("this can take a while"));
+    }
+}
@@ -0,0 +1,47 @@
+use crate::disk::PartitionConfig;
+use crate::error::{WowUsbError, Result};
+
+/// Minimum viable EFI System Partition size; smaller ESPs are rejected by
+/// some firmware and by `mkfs.fat` itself.
+const MIN_ESP_SIZE_MB: u64 = 100;
+
+/// Fixed overhead added on top of the raw ISO size to account for
+/// filesystem metadata and slack space when sizing the Windows payload
+/// partition.
+const PAYLOAD_OVERHEAD_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Validate that a proposed partition layout actually fits on the target
+/// device and meets basic firmware constraints, before any destructive
+/// `parted`/`Clear-Disk` command runs.
+pub fn validate_layout(partitions: &[PartitionConfig], device_size_bytes: u64, iso_size_bytes: u64) -> Result<()> {
+    let mut total_mb: u64 = 0;
+
+    for partition in partitions {
+        if partition.filesystem == "fat32" && partition.label.eq_ignore_ascii_case("EFI") && partition.size_mb < MIN_ESP_SIZE_MB {
+            return Err(WowUsbError::validation(format!(
+                "EFI System Partition must be at least {} MB, got {} MB",
+                MIN_ESP_SIZE_MB, partition.size_mb
+            )));
+        }
+
+        total_mb += partition.size_mb;
+    }
+
+    let device_size_mb = device_size_bytes / (1024 * 1024);
+    if total_mb > 0 && total_mb > device_size_mb {
+        return Err(WowUsbError::validation(format!(
+            "Requested partitions require {} MB but the target device is only {} MB",
+            total_mb, device_size_mb
+        )));
+    }
+
+    let required_payload_bytes = iso_size_bytes + PAYLOAD_OVERHEAD_BYTES;
+    if required_payload_bytes > device_size_bytes {
+        return Err(WowUsbError::validation(format!(
+            "Selected ISO ({} bytes) plus overhead does not fit on the target device ({} bytes)",
+            iso_size_bytes, device_size_bytes
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use crate::error::{Result, WowUsbError};
+use std::process::Command;
+
+/// Fedora, openSUSE, and RHEL package GRUB's tools under a `grub2-` prefix
+/// (reserving the unprefixed `grub-*` names for the old GRUB Legacy, which
+/// they don't ship); Debian, Ubuntu, and Arch do the opposite. Hardcoding
+/// `grub-install` breaks bootloader installation on the whole first family
+/// of distros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrubToolset {
+    Unprefixed,
+    Grub2Prefixed,
+}
+
+impl GrubToolset {
+    pub fn install_binary(self) -> &'static str {
+        match self {
+            Self::Unprefixed => "grub-install",
+            Self::Grub2Prefixed => "grub2-install",
+        }
+    }
+
+    pub fn mkimage_binary(self) -> &'static str {
+        match self {
+            Self::Unprefixed => "grub-mkimage",
+            Self::Grub2Prefixed => "grub2-mkimage",
+        }
+    }
+}
+
+/// Probe `PATH` (after applying [`crate::tool_paths::ToolPaths`] overrides)
+/// for a usable GRUB toolset, preferring the unprefixed names most distros
+/// use.
+pub fn detect_toolset() -> Result<GrubToolset> {
+    let tool_paths = crate::tool_paths::ToolPaths::load()?;
+
+    for toolset in [GrubToolset::Unprefixed, GrubToolset::Grub2Prefixed] {
+        if binary_on_path(&tool_paths.resolve(toolset.install_binary())) {
+            return Ok(toolset);
+        }
+    }
+
+    Err(WowUsbError::not_implemented(
+        "Neither grub-install nor grub2-install found on PATH; install your distro's GRUB package",
+    ))
+}
+
+fn binary_on_path(name: &str) -> bool {
+    Command::new(name)
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Whether a `grub-install --target=i386-pc` failure looks like the host's
+/// GRUB package is UEFI-only and never installed the i386-pc module set
+/// (common on Fedora/openSUSE hosts that ship `grub2-efi` alone), as
+/// opposed to some other failure a module fallback wouldn't fix.
+pub fn is_missing_i386_pc_modules(stderr: &str) -> bool {
+    let lowered = stderr.to_lowercase();
+    lowered.contains("i386-pc") && (lowered.contains("no such file") || lowered.contains("cannot find"))
+}
+
+/// Directory, relative to the app's bundled resources, holding a copy of
+/// GRUB's i386-pc modules for `grub-mkimage` to draw on when the host's own
+/// package doesn't ship them.
+pub const BUNDLED_I386_PC_MODULES_DIR: &str = "grub-modules/i386-pc";
+
+/// Build the `grub-mkimage` invocation that assembles a BIOS `core.img`
+/// straight into `output_path` from `modules_dir`, embedding just enough
+/// modules to chainload the rest of the GRUB install at `boot_directory` —
+/// used when the host's own `grub-install --target=i386-pc` can't run
+/// because its i386-pc module set is missing.
+pub fn mkimage_core_img_args<'a>(modules_dir: &'a str, boot_directory: &'a str, output_path: &'a str) -> Vec<&'a str> {
+    vec![
+        "-O", "i386-pc",
+        "-d", modules_dir,
+        "-o", output_path,
+        "-p", boot_directory,
+        "biosdisk", "part_gpt", "part_msdos", "fat", "ext2", "normal", "configfile",
+    ]
+}
+
+/// Build the `--target=x86_64-efi` install args, shared between the real
+/// `install_bootloader` invocation and `preview_pipeline_commands` so the
+/// preview can't drift from what actually runs.
+pub fn install_efi_args(device: &str, efi_mountpoint: &str, boot_mountpoint: &str) -> Vec<String> {
+    vec![
+        "--target=x86_64-efi".to_string(),
+        format!("--efi-directory={}", efi_mountpoint),
+        format!("--boot-directory={}/boot", boot_mountpoint),
+        "--removable".to_string(),
+        device.to_string(),
+    ]
+}
+
+/// Build the `--target=i386-pc` install args.
+pub fn install_bios_args(device: &str, boot_mountpoint: &str) -> Vec<String> {
+    vec![
+        "--target=i386-pc".to_string(),
+        format!("--boot-directory={}/boot", boot_mountpoint),
+        "--removable".to_string(),
+        device.to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toolset_binaries_match_distro_naming() {
+        assert_eq!(GrubToolset::Unprefixed.install_binary(), "grub-install");
+        assert_eq!(GrubToolset::Unprefixed.mkimage_binary(), "grub-mkimage");
+        assert_eq!(GrubToolset::Grub2Prefixed.install_binary(), "grub2-install");
+        assert_eq!(GrubToolset::Grub2Prefixed.mkimage_binary(), "grub2-mkimage");
+    }
+
+    #[test]
+    fn recognizes_missing_i386_pc_modules_error() {
+        assert!(is_missing_i386_pc_modules(
+            "/usr/lib/grub/i386-pc/modinfo.sh: No such file or directory"
+        ));
+        assert!(!is_missing_i386_pc_modules(
+            "grub-install: error: failed to get canonical path"
+        ));
+    }
+
+    #[test]
+    fn mkimage_args_target_i386_pc() {
+        let args = mkimage_core_img_args("/opt/wowusb/grub-modules/i386-pc", "/mnt/stick/boot", "/tmp/core.img");
+        assert_eq!(args[0], "-O");
+        assert_eq!(args[1], "i386-pc");
+        assert!(args.contains(&"biosdisk"));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::error::{WowUsbError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Name of the local socket a future privileged helper would listen on, so
+/// the unprivileged UI process could hand it destructive operations
+/// (partitioning, formatting, bootloader install) instead of running them
+/// as root/administrator itself.
+///
+/// No such helper exists yet — there is no server binary, nothing in
+/// [`crate::disk`] calls [`HelperClient`], and [`HelperClient::send`] does
+/// not authenticate its peer, so connecting to this socket today would not
+/// be safe even if something were listening on it. Destructive operations
+/// still run in-process. Actually splitting privilege requires: a helper
+/// binary that owns this socket, peer-credential checking on its accept
+/// loop (`SO_PEERCRED`/`getsockopt(LOCAL_PEERCRED)`), and rewiring
+/// [`crate::disk::PlatformDiskOps`]'s destructive methods to dispatch
+/// through [`HelperClient`] instead of running locally.
+#[cfg(unix)]
+pub const HELPER_SOCKET_PATH: &str = "/run/wowusb-helper.sock";
+
+#[cfg(windows)]
+pub const HELPER_PIPE_NAME: &str = r"\\.\pipe\wowusb-helper";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HelperRequest {
+    CreatePartitions { device: String },
+    FormatPartition { partition: String, filesystem: String, label: String },
+    InstallBootloader { device: String, bootloader_type: String },
+    WipeDevice { device: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HelperResponse {
+    Ok,
+    Error(String),
+}
+
+/// Thin client for dispatching a destructive request to a privileged
+/// helper over its local socket, once one exists to receive it. See
+/// [`HELPER_SOCKET_PATH`] for what's still missing before this moves any
+/// operation out of the GUI process.
+pub struct HelperClient;
+
+impl HelperClient {
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[cfg(unix)]
+    pub async fn send(&self, request: &HelperRequest) -> Result<HelperResponse> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::UnixStream;
+
+        let mut stream = UnixStream::connect(HELPER_SOCKET_PATH)
+            .await
+            .map_err(|e| WowUsbError::platform(format!("Failed to reach privileged helper: {}", e)))?;
+
+        let payload = serde_json::to_vec(request)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to encode helper request: {}", e)))?;
+        stream.write_all(&payload).await?;
+        stream.shutdown().await?;
+
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).await?;
+
+        serde_json::from_slice(&buf)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to decode helper response: {}", e)))
+    }
+
+    #[cfg(windows)]
+    pub async fn send(&self, _request: &HelperRequest) -> Result<HelperResponse> {
+        Err(WowUsbError::not_implemented(
+            "Privileged helper IPC over named pipes is not yet implemented on Windows",
+        ))
+    }
+}
+
+impl Default for HelperClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
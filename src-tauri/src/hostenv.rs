@@ -0,0 +1,86 @@
+use crate::error::{WowUsbError, Result};
+
+/// Detect whether the process is running inside WSL, where raw block
+/// device access to USB drives usually isn't possible without the device
+/// having been explicitly attached via `usbipd-win`.
+pub fn is_wsl() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .map(|release| release.to_lowercase().contains("microsoft"))
+        .unwrap_or(false)
+}
+
+/// Return a precise, actionable error instead of a confusing `lsblk`
+/// failure when a device operation can't proceed because we're in WSL and
+/// the device hasn't been passed through yet.
+pub fn wsl_device_passthrough_hint(device: &str) -> WowUsbError {
+    WowUsbError::platform(format!(
+        "Running under WSL: {} is not visible until attached with usbipd. \
+        On the Windows host, run `usbipd list` to find the device's bus ID, then \
+        `usbipd bind --busid <ID>` and `usbipd attach --wsl --busid <ID>`.",
+        device
+    ))
+}
+
+/// The sandbox (if any) the process is confined to. Direct `/dev` access
+/// and shelling out to `parted`/`mkfs.*` fails opaquely under these, so
+/// callers should prefer a portal/udisks2 path when one is detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxKind {
+    Flatpak,
+    Snap,
+    None,
+}
+
+pub fn detect_sandbox() -> SandboxKind {
+    if std::path::Path::new("/.flatpak-info").exists() {
+        return SandboxKind::Flatpak;
+    }
+
+    if std::env::var("SNAP").is_ok() {
+        return SandboxKind::Snap;
+    }
+
+    SandboxKind::None
+}
+
+/// A precise, remediation-bearing error for the case where a sandboxed
+/// build attempted direct `/dev` access instead of going through
+/// udisks2/portals.
+pub fn sandbox_permission_hint(sandbox: SandboxKind, device: &str) -> WowUsbError {
+    let remediation = match sandbox {
+        SandboxKind::Flatpak => {
+            "Flatpak builds must request the udisks2 D-Bus interface or the \
+            org.freedesktop.portal.Device portal; add `--device=all` is not sufficient \
+            and will not be granted by Flathub review."
+        }
+        SandboxKind::Snap => {
+            "Snap builds must connect the `hardware-observe` and `removable-media` \
+            (or `block-devices`) interfaces: `snap connect wowusb-ds9:removable-media`."
+        }
+        SandboxKind::None => "Not running in a known sandbox; check host device permissions instead.",
+    };
+
+    WowUsbError::platform(format!(
+        "Cannot access {} directly from within the sandbox. {}",
+        device, remediation
+    ))
+}
+
+/// Whether the current process's user is a member of `group`, e.g. `disk`
+/// on Linux or `operator` on the BSDs — both grant raw block device access
+/// without needing full root.
+pub fn current_user_in_group(group: &str) -> bool {
+    std::process::Command::new("id")
+        .arg("-nG")
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .any(|g| g == group)
+        })
+        .unwrap_or(false)
+}
@@ -1,4 +1,5 @@
 use crate::error::{WowUsbError, Result};
+use crate::target_os::TargetOs;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -13,16 +14,42 @@ pub struct IsoInfo {
     pub bootable: bool,
     pub supports_uefi: bool,
     pub supports_legacy: bool,
+    /// Exact distro name/version read from the squashfs's `/etc/os-release`,
+    /// rather than guessed from the ISO's file-name substrings.
+    #[serde(default)]
+    pub distro_name: Option<String>,
+    #[serde(default)]
+    pub distro_version: Option<String>,
+    #[serde(default)]
+    pub desktop_environment: Option<String>,
 }
 
 pub struct IsoProcessor {
     temp_dir: String,
+    tool_paths: crate::tool_paths::ToolPaths,
 }
 
 impl IsoProcessor {
     pub fn new() -> Self {
-        let temp_dir = format!("/tmp/wowusb_iso_{}", std::process::id());
-        Self { temp_dir }
+        Self::with_staging_dir(None)
+    }
+
+    /// Build a processor staging into `staging_override` (or the platform
+    /// temp directory when `None`) instead of a hardcoded Unix path.
+    pub fn with_staging_dir(staging_override: Option<&str>) -> Self {
+        let staging = crate::staging::StagingDirectory::resolve(staging_override);
+        let temp_dir = staging
+            .job_dir(&format!("iso_{}", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+        Self { temp_dir, tool_paths }
+    }
+
+    /// The `7z` executable to invoke, honoring
+    /// [`crate::tool_paths::ToolPaths`] overrides.
+    fn sevenzip(&self) -> String {
+        self.tool_paths.resolve("7z")
     }
 
     pub async fn analyze_iso(&self, iso_path: &str) -> Result<IsoInfo> {
@@ -43,6 +70,12 @@ impl IsoProcessor {
         let has_large_files = self.check_for_large_files(iso_path).await?;
         let (supports_uefi, supports_legacy) = self.check_boot_support(iso_path).await?;
 
+        let (distro_name, distro_version, desktop_environment) = if os_type != "Windows" {
+            self.inspect_squashfs_metadata(iso_path).await.unwrap_or((None, None, None))
+        } else {
+            (None, None, None)
+        };
+
         Ok(IsoInfo {
             path: iso_path.to_string(),
             size,
@@ -53,12 +86,66 @@ impl IsoProcessor {
             bootable: supports_uefi || supports_legacy,
             supports_uefi,
             supports_legacy,
+            distro_name,
+            distro_version,
+            desktop_environment,
         })
     }
 
+    /// Read `/etc/os-release` out of the ISO's live squashfs and the
+    /// installed-package manifest alongside it, for an exact distro
+    /// name/version/desktop instead of guessing from file-name substrings.
+    /// Best-effort: returns `Ok((None, None, None))` for ISOs that don't
+    /// carry a live squashfs (e.g. server/netinst images).
+    async fn inspect_squashfs_metadata(&self, iso_path: &str) -> Result<(Option<String>, Option<String>, Option<String>)> {
+        std::fs::create_dir_all(&self.temp_dir)?;
+        const SQUASHFS_CANDIDATES: &[&str] = &["casper/filesystem.squashfs", "live/filesystem.squashfs"];
+
+        let mut squashfs_path = None;
+        for candidate in SQUASHFS_CANDIDATES {
+            let dest = Path::new(&self.temp_dir).join("filesystem.squashfs");
+            let output = tokio::process::Command::new(self.sevenzip())
+                .args(&["e", iso_path, candidate, &crate::platform_paths::sevenzip_output_flag(&self.temp_dir), "-y"])
+                .output()
+                .await?;
+
+            if output.status.success() && dest.exists() {
+                squashfs_path = Some(dest);
+                break;
+            }
+        }
+
+        let Some(squashfs_path) = squashfs_path else {
+            return Ok((None, None, None));
+        };
+
+        let os_release = crate::squashfs_inspect::read_file(&squashfs_path.to_string_lossy(), "etc/os-release").ok();
+        let (distro_name, distro_version) = match &os_release {
+            Some(bytes) => {
+                let fields = crate::squashfs_inspect::parse_os_release(&String::from_utf8_lossy(bytes));
+                (fields.get("NAME").cloned(), fields.get("VERSION_ID").cloned())
+            }
+            None => (None, None),
+        };
+
+        let manifest_output = tokio::process::Command::new(self.sevenzip())
+            .args(&["e", iso_path, "casper/filesystem.manifest", "-so"])
+            .output()
+            .await?;
+        let desktop_environment = if manifest_output.status.success() {
+            crate::squashfs_inspect::desktop_environment_from_manifest(&String::from_utf8_lossy(&manifest_output.stdout))
+        } else {
+            None
+        };
+
+        std::fs::remove_file(&squashfs_path).ok();
+
+        Ok((distro_name, distro_version, desktop_environment))
+    }
+
     async fn detect_os_type(&self, iso_path: &str) -> Result<String> {
         // Use 7z to list contents and analyze
-        let output = tokio::process::Command::new("7z")
+        let output = tokio::process::Command::new(self.sevenzip())
             .args(&["l", iso_path])
             .output()
             .await?;
@@ -70,29 +157,7 @@ impl IsoProcessor {
         }
 
         let contents = String::from_utf8(output.stdout)?;
-        let contents_lower = contents.to_lowercase();
-
-        // Detect OS type based on file patterns
-        if contents_lower.contains("windows") || contents_lower.contains("sources/install.wim") || contents_lower.contains("sources/install.esd") {
-            Ok("Windows".to_string())
-        } else if contents_lower.contains("ubuntu") || contents_lower.contains("debian") || contents_lower.contains("linux") {
-            // More specific detection
-            if contents_lower.contains("ubuntu") {
-                Ok("Ubuntu".to_string())
-            } else if contents_lower.contains("debian") {
-                Ok("Debian".to_string())
-            } else if contents_lower.contains("fedora") {
-                Ok("Fedora".to_string())
-            } else if contents_lower.contains("arch") {
-                Ok("Arch Linux".to_string())
-            } else {
-                Ok("Linux".to_string())
-            }
-        } else if contents_lower.contains("install") {
-            Ok("Unknown (but likely bootable)".to_string())
-        } else {
-            Ok("Unknown".to_string())
-        }
+        Ok(crate::iso_listing::os_type_from_listing(&contents))
     }
 
     async fn extract_version(&self, iso_path: &str, os_type: &str) -> Result<Option<String>> {
@@ -106,19 +171,14 @@ impl IsoProcessor {
 
     async fn extract_windows_version(&self, iso_path: &str) -> Result<Option<String>> {
         // For Windows, we can extract from sources/idwbinfo.txt or similar files
-        let output = tokio::process::Command::new("7z")
+        let output = tokio::process::Command::new(self.sevenzip())
             .args(&["e", iso_path, "sources/idwbinfo.txt", "-so"])
             .output()
             .await?;
 
         if output.status.success() {
             let content = String::from_utf8(output.stdout)?;
-            // Parse Windows version from idwbinfo.txt
-            for line in content.lines() {
-                if line.contains("Version") || line.contains("Build") {
-                    return Ok(Some(line.trim().to_string()));
-                }
-            }
+            return Ok(crate::iso_listing::windows_version_from_idwbinfo(&content));
         }
 
         Ok(None)
@@ -126,7 +186,7 @@ impl IsoProcessor {
 
     async fn extract_ubuntu_version(&self, iso_path: &str) -> Result<Option<String>> {
         // For Ubuntu, check .disk/info or casper/vmlinuz version info
-        let output = tokio::process::Command::new("7z")
+        let output = tokio::process::Command::new(self.sevenzip())
             .args(&["e", iso_path, ".disk/info", "-so"])
             .output()
             .await?;
@@ -146,7 +206,7 @@ impl IsoProcessor {
 
     async fn detect_architecture(&self, iso_path: &str) -> Result<Option<String>> {
         // Check for architecture-specific files
-        let output = tokio::process::Command::new("7z")
+        let output = tokio::process::Command::new(self.sevenzip())
             .args(&["l", iso_path])
             .output()
             .await?;
@@ -156,23 +216,11 @@ impl IsoProcessor {
         }
 
         let contents = String::from_utf8(output.stdout)?;
-        let contents_lower = contents.to_lowercase();
-
-        if contents_lower.contains("x64") || contents_lower.contains("amd64") {
-            Ok(Some("x86_64".to_string()))
-        } else if contents_lower.contains("x86") || contents_lower.contains("i386") {
-            Ok(Some("i386".to_string()))
-        } else if contents_lower.contains("arm64") || contents_lower.contains("aarch64") {
-            Ok(Some("aarch64".to_string()))
-        } else if contents_lower.contains("arm") {
-            Ok(Some("ARM".to_string()))
-        } else {
-            Ok(None)
-        }
+        Ok(crate::iso_listing::architecture_from_listing(&contents))
     }
 
     async fn check_for_large_files(&self, iso_path: &str) -> Result<bool> {
-        let output = tokio::process::Command::new("7z")
+        let output = tokio::process::Command::new(self.sevenzip())
             .args(&["l", iso_path])
             .output()
             .await?;
@@ -184,30 +232,11 @@ impl IsoProcessor {
         }
 
         let contents = String::from_utf8(output.stdout)?;
-        const FOUR_GB: u64 = 4 * 1024 * 1024 * 1024;
-
-        for line in contents.lines() {
-            if line.trim().is_empty() || line.starts_with('-') {
-                continue;
-            }
-
-            // Parse file size (this is a simplified parser)
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 4 {
-                let size_str = parts[3];
-                if let Ok(size_bytes) = self.parse_size_string(size_str) {
-                    if size_bytes > FOUR_GB {
-                        return Ok(true);
-                    }
-                }
-            }
-        }
-
-        Ok(false)
+        Ok(crate::iso_listing::has_large_file_in_listing(&contents))
     }
 
     async fn check_boot_support(&self, iso_path: &str) -> Result<(bool, bool)> {
-        let output = tokio::process::Command::new("7z")
+        let output = tokio::process::Command::new(self.sevenzip())
             .args(&["l", iso_path])
             .output()
             .await?;
@@ -217,49 +246,20 @@ impl IsoProcessor {
         }
 
         let contents = String::from_utf8(output.stdout)?;
-        let contents_lower = contents.to_lowercase();
-
-        let supports_uefi = contents_lower.contains("efi") ||
-                          contents_lower.contains("boot") ||
-                          contents_lower.contains("efi/boot");
-
-        let supports_legacy = contents_lower.contains("boot") ||
-                             contents_lower.contains("syslinux") ||
-                             contents_lower.contains("grub");
-
-        Ok((supports_uefi, supports_legacy))
+        Ok(crate::iso_listing::boot_support_from_listing(&contents))
     }
 
     fn parse_size_string(&self, size_str: &str) -> Result<u64> {
-        // Parse size strings like "123456789", "123M", "1.2G", etc.
-        let size_str = size_str.trim().to_uppercase();
-
-        if size_str.ends_with('G') {
-            let numeric_part = &size_str[..size_str.len() - 1];
-            let size_gb: f64 = numeric_part.parse()
-                .map_err(|_| WowUsbError::validation(format!("Invalid size format: {}", size_str)))?;
-            Ok((size_gb * 1024.0 * 1024.0 * 1024.0) as u64)
-        } else if size_str.ends_with('M') {
-            let numeric_part = &size_str[..size_str.len() - 1];
-            let size_mb: f64 = numeric_part.parse()
-                .map_err(|_| WowUsbError::validation(format!("Invalid size format: {}", size_str)))?;
-            Ok((size_mb * 1024.0 * 1024.0) as u64)
-        } else if size_str.ends_with('K') {
-            let numeric_part = &size_str[..size_str.len() - 1];
-            let size_kb: f64 = numeric_part.parse()
-                .map_err(|_| WowUsbError::validation(format!("Invalid size format: {}", size_str)))?;
-            Ok((size_kb * 1024.0) as u64)
-        } else {
-            // Assume bytes
-            size_str.parse::<u64>()
-                .map_err(|_| WowUsbError::validation(format!("Invalid size format: {}", size_str)))
-        }
+        crate::units::parse_size_string(size_str)
     }
 
-    pub async fn validate_iso_for_target(&self, iso_info: &IsoInfo, target_os: &str) -> Result<bool> {
-        match (iso_info.os_type.as_str(), target_os.to_lowercase().as_str()) {
-            ("Windows", "linux") | ("Windows", "windows") => Ok(true),
-            ("Ubuntu" | "Debian" | "Fedora" | "Arch Linux" | "Linux", "linux") => Ok(true),
+    pub async fn validate_iso_for_target(&self, iso_info: &IsoInfo, target_os: TargetOs) -> Result<bool> {
+        match (iso_info.os_type.as_str(), target_os) {
+            ("Windows", TargetOs::LinuxLive | TargetOs::LinuxInstall | TargetOs::Windows) => Ok(true),
+            (
+                "Ubuntu" | "Debian" | "Fedora" | "Arch Linux" | "Linux",
+                TargetOs::LinuxLive | TargetOs::LinuxInstall,
+            ) => Ok(true),
             _ => Ok(false), // Mismatched OS types
         }
     }
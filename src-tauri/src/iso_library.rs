@@ -0,0 +1,386 @@
+use crate::error::{Result, WowUsbError};
+use crate::iso::{IsoInfo, IsoProcessor};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const LIBRARY_INDEX_FILENAME: &str = "library.json";
+
+/// One ISO imported into the library, stored under its own hash rather
+/// than the name it was imported under — two imports of the same release
+/// (even downloaded from different mirrors, or renamed) collapse to one
+/// entry instead of taking up the space twice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryEntry {
+    pub sha256: String,
+    pub info: IsoInfo,
+    pub imported_at: chrono::DateTime<chrono::Utc>,
+    /// The options this entry was last built with, if any, so a stale
+    /// catalog-sourced entry can be replaced with a fresh download and
+    /// rebuilt the same way without the user re-entering their choices.
+    #[serde(default)]
+    pub creation_profile: Option<crate::config::CreateConfig>,
+}
+
+/// Result of comparing a library entry's recorded distro/version against
+/// the checksum database's currently known release for that distro.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StaleCheck {
+    /// Either this entry isn't distro-identified, or the checksum database
+    /// has no known release for it to compare against.
+    Unknown,
+    UpToDate,
+    Stale {
+        latest_version: String,
+        download_url: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct LibraryIndex {
+    entries: HashMap<String, LibraryEntry>,
+}
+
+/// An optional, user-managed store of imported ISOs, content-addressed by
+/// SHA-256 so the same release is never kept twice — surfaced in the UI as
+/// a "my images" list alongside [`crate::updater::BundledAsset::IsoCatalog`]'s
+/// downloadable one.
+pub struct IsoLibrary {
+    root: PathBuf,
+}
+
+impl IsoLibrary {
+    /// Use `override_path` if given, otherwise the platform's default
+    /// application-data location.
+    pub fn resolve(override_path: Option<&str>) -> Self {
+        let root = match override_path {
+            Some(path) => PathBuf::from(path),
+            None => Self::default_root(),
+        };
+        Self { root }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn default_root() -> PathBuf {
+        PathBuf::from(r"C:\ProgramData\WowUSB\iso_library")
+    }
+
+    #[cfg(target_os = "macos")]
+    fn default_root() -> PathBuf {
+        PathBuf::from("/Library/Application Support/WowUSB/iso_library")
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn default_root() -> PathBuf {
+        PathBuf::from("/etc/wowusb/iso_library")
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.root.join(LIBRARY_INDEX_FILENAME)
+    }
+
+    fn stored_path(&self, sha256: &str) -> PathBuf {
+        self.root.join(format!("{}.iso", sha256))
+    }
+
+    fn load_index(&self) -> Result<LibraryIndex> {
+        let path = self.index_path();
+        if !path.exists() {
+            return Ok(LibraryIndex::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| WowUsbError::configuration(format!("Invalid ISO library index: {}", e)))
+    }
+
+    fn save_index(&self, index: &LibraryIndex) -> Result<()> {
+        let contents = serde_json::to_string_pretty(index)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize ISO library index: {}", e)))?;
+        std::fs::write(self.index_path(), contents)?;
+        Ok(())
+    }
+
+    /// Copy `iso_path` into the library under its hash and analyze it,
+    /// unless a copy with the same hash is already present — in which case
+    /// the existing entry is returned untouched and nothing is copied.
+    pub async fn import(&self, iso_path: &str) -> Result<LibraryEntry> {
+        let sha256 = crate::hashing::sha256_file(iso_path).await?;
+
+        let mut index = self.load_index()?;
+        if let Some(existing) = index.entries.get(&sha256) {
+            return Ok(existing.clone());
+        }
+
+        std::fs::create_dir_all(&self.root)?;
+        let info = IsoProcessor::new().analyze_iso(iso_path).await?;
+        tokio::fs::copy(iso_path, self.stored_path(&sha256)).await?;
+
+        let entry = LibraryEntry {
+            sha256: sha256.clone(),
+            info,
+            imported_at: chrono::Utc::now(),
+            creation_profile: None,
+        };
+        index.entries.insert(sha256, entry.clone());
+        self.save_index(&index)?;
+
+        Ok(entry)
+    }
+
+    /// List every imported ISO for the "my images" UI.
+    pub fn list(&self) -> Result<Vec<LibraryEntry>> {
+        Ok(self.load_index()?.entries.into_values().collect())
+    }
+
+    /// Remove an entry and its stored copy from the library.
+    pub fn remove(&self, sha256: &str) -> Result<()> {
+        let mut index = self.load_index()?;
+        if index.entries.remove(sha256).is_none() {
+            return Err(WowUsbError::validation(format!("No library entry for hash {}", sha256)));
+        }
+
+        let stored_path = self.stored_path(sha256);
+        if stored_path.exists() {
+            std::fs::remove_file(stored_path)?;
+        }
+
+        self.save_index(&index)
+    }
+
+    /// Record the options an entry was last built with, so a later
+    /// [`Self::replace`] can reuse them.
+    pub fn save_creation_profile(&self, sha256: &str, profile: crate::config::CreateConfig) -> Result<()> {
+        let mut index = self.load_index()?;
+        let entry = index
+            .entries
+            .get_mut(sha256)
+            .ok_or_else(|| WowUsbError::validation(format!("No library entry for hash {}", sha256)))?;
+        entry.creation_profile = Some(profile);
+        self.save_index(&index)
+    }
+
+    /// Compare `entry`'s recorded distro/version (from `analyze_iso`)
+    /// against `db`'s currently known release for that distro. Any
+    /// difference is treated as stale — see
+    /// [`crate::checksum_db::ChecksumDatabase::latest_for_distro`] for why
+    /// this doesn't attempt a semver-aware comparison.
+    pub fn check_staleness(entry: &LibraryEntry, db: &crate::checksum_db::ChecksumDatabase) -> StaleCheck {
+        let Some(distro) = &entry.info.distro_name else {
+            return StaleCheck::Unknown;
+        };
+        let Some(known) = db.latest_for_distro(distro) else {
+            return StaleCheck::Unknown;
+        };
+
+        let current_version = entry.info.distro_version.as_deref().unwrap_or_default();
+        if current_version.eq_ignore_ascii_case(&known.version) {
+            StaleCheck::UpToDate
+        } else {
+            StaleCheck::Stale {
+                latest_version: known.version.clone(),
+                download_url: known.download_url.clone(),
+            }
+        }
+    }
+
+    /// [`Self::check_staleness`] run over every entry currently in the
+    /// library, for a frontend timer to call on its own schedule rather
+    /// than this needing a background task of its own.
+    pub fn check_all_staleness(&self, db: &crate::checksum_db::ChecksumDatabase) -> Result<Vec<(LibraryEntry, StaleCheck)>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .map(|entry| {
+                let check = Self::check_staleness(&entry, db);
+                (entry, check)
+            })
+            .collect())
+    }
+
+    /// Download-and-replace: import `new_iso_path` under its own hash,
+    /// carry over `old_sha256`'s saved creation profile if it had one, and
+    /// remove the old entry (unless the "new" download turned out to be
+    /// byte-identical to what was already there).
+    pub async fn replace(&self, old_sha256: &str, new_iso_path: &str) -> Result<LibraryEntry> {
+        let old_profile = self
+            .load_index()?
+            .entries
+            .get(old_sha256)
+            .and_then(|e| e.creation_profile.clone());
+
+        let mut new_entry = self.import(new_iso_path).await?;
+
+        if let Some(profile) = old_profile {
+            new_entry.creation_profile = Some(profile);
+            let mut index = self.load_index()?;
+            index.entries.insert(new_entry.sha256.clone(), new_entry.clone());
+            self.save_index(&index)?;
+        }
+
+        if old_sha256 != new_entry.sha256 {
+            self.remove(old_sha256)?;
+        }
+
+        Ok(new_entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_library() -> (IsoLibrary, PathBuf) {
+        let root = std::env::temp_dir().join(format!("wowusb_iso_library_test_{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        (IsoLibrary::resolve(Some(root.to_str().unwrap())), root)
+    }
+
+    fn sample_info(path: &str) -> IsoInfo {
+        IsoInfo {
+            path: path.to_string(),
+            size: 1024,
+            os_type: "Ubuntu".to_string(),
+            version: Some("24.04".to_string()),
+            architecture: Some("x86_64".to_string()),
+            has_large_files: false,
+            bootable: true,
+            supports_uefi: true,
+            supports_legacy: true,
+            distro_name: Some("Ubuntu".to_string()),
+            distro_version: Some("24.04".to_string()),
+            desktop_environment: None,
+        }
+    }
+
+    #[test]
+    fn list_is_empty_for_a_fresh_library() {
+        let (library, root) = temp_library();
+        assert!(library.list().unwrap().is_empty());
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    fn sample_entry(sha256: &str) -> LibraryEntry {
+        LibraryEntry {
+            sha256: sha256.to_string(),
+            info: sample_info("ubuntu.iso"),
+            imported_at: chrono::DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().into(),
+            creation_profile: None,
+        }
+    }
+
+    #[test]
+    fn list_and_remove_round_trip_through_the_index() {
+        let (library, root) = temp_library();
+
+        let mut index = LibraryIndex::default();
+        index.entries.insert("deadbeef".to_string(), sample_entry("deadbeef"));
+        library.save_index(&index).unwrap();
+
+        let listed = library.list().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].sha256, "deadbeef");
+
+        library.remove("deadbeef").unwrap();
+        assert!(library.list().unwrap().is_empty());
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn removing_an_unknown_hash_is_an_error() {
+        let (library, root) = temp_library();
+        assert!(library.remove("not-in-the-library").is_err());
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn save_creation_profile_requires_an_existing_entry() {
+        let (library, root) = temp_library();
+        let config = crate::config::CreateConfig {
+            target_os: crate::target_os::TargetOs::LinuxLive,
+            filesystem: "fat32".to_string(),
+            drive_label: "WOWUSB".to_string(),
+            wintogo_enabled: false,
+            hardware_profile: crate::wintogo_profiles::HardwareProfile::default(),
+            enable_multiboot: false,
+            enable_persistence: false,
+            persistence_mode: crate::persistence_overlay::PersistenceMode::default(),
+            persistence_overlay_size_mb: None,
+            menu_appearance: crate::bootloader::MenuAppearance::default(),
+            ei_config: None,
+            product_key: None,
+            oem_folder_path: None,
+            compact_os_enabled: false,
+            sync_policy: crate::write_cache::SyncPolicy::default(),
+            file_injections: Vec::new(),
+            suspend_realtime_scanning: false,
+            clean_os_litter: false,
+            write_mode: crate::config::WriteMode::Extract,
+            undo_grace_period_seconds: None,
+            io_throttle: None,
+            encryption: None,
+        };
+
+        assert!(library.save_creation_profile("deadbeef", config.clone()).is_err());
+
+        let mut index = LibraryIndex::default();
+        index.entries.insert("deadbeef".to_string(), sample_entry("deadbeef"));
+        library.save_index(&index).unwrap();
+
+        library.save_creation_profile("deadbeef", config).unwrap();
+        let listed = library.list().unwrap();
+        assert!(listed[0].creation_profile.is_some());
+
+        std::fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn staleness_is_unknown_without_a_matching_catalog_entry() {
+        let entry = sample_entry("deadbeef");
+        let db = crate::checksum_db::ChecksumDatabase::default();
+        assert_eq!(IsoLibrary::check_staleness(&entry, &db), StaleCheck::Unknown);
+    }
+
+    #[test]
+    fn staleness_flags_a_version_mismatch() {
+        let entry = sample_entry("deadbeef");
+        let mut db = crate::checksum_db::ChecksumDatabase::default();
+        db.insert(
+            "ubuntu-24.10.iso",
+            9_000_000_000,
+            crate::checksum_db::KnownRelease {
+                distro: "Ubuntu".to_string(),
+                version: "24.10".to_string(),
+                sha256: "cafef00d".to_string(),
+                download_url: Some("https://example.com/ubuntu-24.10.iso".to_string()),
+            },
+        );
+
+        match IsoLibrary::check_staleness(&entry, &db) {
+            StaleCheck::Stale { latest_version, download_url } => {
+                assert_eq!(latest_version, "24.10");
+                assert_eq!(download_url.as_deref(), Some("https://example.com/ubuntu-24.10.iso"));
+            }
+            other => panic!("expected Stale, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn staleness_is_up_to_date_on_a_matching_version() {
+        let entry = sample_entry("deadbeef");
+        let mut db = crate::checksum_db::ChecksumDatabase::default();
+        db.insert(
+            "ubuntu-24.04.iso",
+            9_000_000_000,
+            crate::checksum_db::KnownRelease {
+                distro: "Ubuntu".to_string(),
+                version: "24.04".to_string(),
+                sha256: "cafef00d".to_string(),
+                download_url: None,
+            },
+        );
+
+        assert_eq!(IsoLibrary::check_staleness(&entry, &db), StaleCheck::UpToDate);
+    }
+}
@@ -0,0 +1,132 @@
+//! Pure parsers over `7z l`'s listing text (and the handful of small files
+//! extracted from an ISO, like `sources/idwbinfo.txt`). Kept free of I/O so
+//! they can be unit tested and fuzzed directly with attacker-controlled
+//! ISO contents, without needing `7z` or a real ISO file in the loop.
+
+/// Guess the OS family an ISO belongs to from its file listing.
+pub fn os_type_from_listing(contents: &str) -> String {
+    let contents_lower = contents.to_lowercase();
+
+    if contents_lower.contains("windows")
+        || contents_lower.contains("sources/install.wim")
+        || contents_lower.contains("sources/install.esd")
+    {
+        "Windows".to_string()
+    } else if contents_lower.contains("ubuntu") || contents_lower.contains("debian") || contents_lower.contains("linux") {
+        if contents_lower.contains("ubuntu") {
+            "Ubuntu".to_string()
+        } else if contents_lower.contains("debian") {
+            "Debian".to_string()
+        } else if contents_lower.contains("fedora") {
+            "Fedora".to_string()
+        } else if contents_lower.contains("arch") {
+            "Arch Linux".to_string()
+        } else {
+            "Linux".to_string()
+        }
+    } else if contents_lower.contains("install") {
+        "Unknown (but likely bootable)".to_string()
+    } else {
+        "Unknown".to_string()
+    }
+}
+
+/// Extract the Windows version/build line from `sources/idwbinfo.txt`.
+pub fn windows_version_from_idwbinfo(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find(|line| line.contains("Version") || line.contains("Build"))
+        .map(|line| line.trim().to_string())
+}
+
+/// Guess target architecture from an ISO's file listing.
+pub fn architecture_from_listing(contents: &str) -> Option<String> {
+    let contents_lower = contents.to_lowercase();
+
+    if contents_lower.contains("x64") || contents_lower.contains("amd64") {
+        Some("x86_64".to_string())
+    } else if contents_lower.contains("x86") || contents_lower.contains("i386") {
+        Some("i386".to_string())
+    } else if contents_lower.contains("arm64") || contents_lower.contains("aarch64") {
+        Some("aarch64".to_string())
+    } else if contents_lower.contains("arm") {
+        Some("ARM".to_string())
+    } else {
+        None
+    }
+}
+
+/// Whether any file in the listing is larger than 4 GB (relevant for FAT32
+/// targets, which can't hold a single file that big).
+pub fn has_large_file_in_listing(contents: &str) -> bool {
+    const FOUR_GB: u64 = 4 * 1024 * 1024 * 1024;
+
+    for line in contents.lines() {
+        if line.trim().is_empty() || line.starts_with('-') {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() >= 4 {
+            let size_str = parts[3];
+            if let Ok(size_bytes) = crate::units::parse_size_string(size_str) {
+                if size_bytes > FOUR_GB {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Whether the listing suggests UEFI and/or legacy BIOS boot support.
+pub fn boot_support_from_listing(contents: &str) -> (bool, bool) {
+    let contents_lower = contents.to_lowercase();
+
+    let supports_uefi = contents_lower.contains("efi")
+        || contents_lower.contains("boot")
+        || contents_lower.contains("efi/boot");
+
+    let supports_legacy = contents_lower.contains("boot")
+        || contents_lower.contains("syslinux")
+        || contents_lower.contains("grub");
+
+    (supports_uefi, supports_legacy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_windows_from_install_wim() {
+        assert_eq!(os_type_from_listing("sources/install.wim"), "Windows");
+    }
+
+    #[test]
+    fn detects_ubuntu() {
+        assert_eq!(os_type_from_listing("casper/ubuntu.squashfs"), "Ubuntu");
+    }
+
+    #[test]
+    fn unknown_on_empty_listing() {
+        assert_eq!(os_type_from_listing(""), "Unknown");
+    }
+
+    #[test]
+    fn extracts_windows_version_line() {
+        let content = "Some Header\nBuild: 22631\nOther Line\n";
+        assert_eq!(windows_version_from_idwbinfo(content), Some("Build: 22631".to_string()));
+    }
+
+    #[test]
+    fn no_version_line_returns_none() {
+        assert_eq!(windows_version_from_idwbinfo("nothing relevant here"), None);
+    }
+
+    #[test]
+    fn large_file_detection_ignores_malformed_lines() {
+        assert!(!has_large_file_in_listing("not a real listing\n---\n"));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::error::{Result, WowUsbError};
+use std::path::Path;
+use tokio::process::Command as AsyncCommand;
+
+/// Builds a bootable ISO from a prepared directory tree — the reverse of
+/// [`crate::iso::IsoProcessor`]'s extraction, for users who customized an
+/// extracted image's files and want to re-master it into a fresh ISO
+/// instead of writing the raw directory straight to a stick.
+pub struct IsoMaster;
+
+impl IsoMaster {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Which of `xorriso`/`genisoimage`/`mkisofs` is on `PATH`, preferring
+    /// `xorriso` since it's the actively maintained implementation and the
+    /// only one of the three still packaged by every major distro.
+    fn detect_tool() -> Result<&'static str> {
+        for tool in ["xorriso", "genisoimage", "mkisofs"] {
+            let found = std::process::Command::new(tool)
+                .arg("--version")
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false);
+            if found {
+                return Ok(tool);
+            }
+        }
+
+        Err(WowUsbError::not_implemented(
+            "None of xorriso, genisoimage, or mkisofs found; install one to master an ISO",
+        ))
+    }
+
+    /// Build an ISO from `source_dir`, writing it to `output_path`. Adds
+    /// El Torito BIOS boot from `isolinux/isolinux.bin` and/or a UEFI
+    /// hybrid boot image from `EFI/boot/efiboot.img` when present under
+    /// `source_dir` — the same layout WowUSB's own extraction leaves
+    /// behind — so a directory a user only edited files in stays bootable,
+    /// while a plain data directory still masters into a valid data-only ISO.
+    pub async fn master_iso(&self, source_dir: &str, output_path: &str, volume_label: &str) -> Result<()> {
+        if !Path::new(source_dir).is_dir() {
+            return Err(WowUsbError::validation(format!("{} is not a directory", source_dir)));
+        }
+
+        let tool = Self::detect_tool()?;
+        let args = Self::build_args(tool, source_dir, output_path, volume_label);
+
+        let output = AsyncCommand::new(tool).args(&args).output().await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(format!(
+                "{} failed to master ISO: {}", tool, String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn build_args(tool: &str, source_dir: &str, output_path: &str, volume_label: &str) -> Vec<String> {
+        let mut args = Vec::new();
+
+        // genisoimage/mkisofs take these flags directly; xorriso needs its
+        // mkisofs-compatibility front-end selected first.
+        if tool == "xorriso" {
+            args.push("-as".to_string());
+            args.push("mkisofs".to_string());
+        }
+
+        args.push("-iso-level".to_string());
+        args.push("3".to_string());
+        args.push("-full-iso9660-filenames".to_string());
+        args.push("-volid".to_string());
+        args.push(volume_label.to_string());
+
+        let has_isolinux = Path::new(source_dir).join("isolinux/isolinux.bin").exists();
+        let has_efi = Path::new(source_dir).join("EFI/boot/efiboot.img").exists();
+
+        if has_isolinux {
+            args.push("-eltorito-boot".to_string());
+            args.push("isolinux/isolinux.bin".to_string());
+            args.push("-eltorito-catalog".to_string());
+            args.push("isolinux/boot.cat".to_string());
+            args.push("-no-emul-boot".to_string());
+            args.push("-boot-load-size".to_string());
+            args.push("4".to_string());
+            args.push("-boot-info-table".to_string());
+        }
+
+        if has_efi {
+            if has_isolinux {
+                args.push("-eltorito-alt-boot".to_string());
+            }
+            args.push("-e".to_string());
+            args.push("EFI/boot/efiboot.img".to_string());
+            args.push("-no-emul-boot".to_string());
+            args.push("-isohybrid-gpt-basdat".to_string());
+        }
+
+        args.push("-output".to_string());
+        args.push(output_path.to_string());
+        args.push(source_dir.to_string());
+
+        args
+    }
+}
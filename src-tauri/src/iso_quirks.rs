@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+
+/// A remediation this crate knows how to apply (or at least suggest) when
+/// a [`QuirkRule`] matches an ISO.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Remediation {
+    /// Append extra kernel/GRUB boot parameters, e.g. openSUSE's installer
+    /// needing `install=...` spelled out explicitly on some hardware.
+    ExtraBootParams { params: String },
+    /// This ISO is a raw disk image (e.g. a FreeBSD memstick) and should be
+    /// dd'd sector-by-sector rather than partitioned and extracted.
+    ForceWriteMode { write_mode: String },
+    /// Use an alternate boot file/loader path, e.g. Windows ARM64 media
+    /// booting `bootaa64.efi` instead of `bootx64.efi`.
+    UseBootFile { path: String },
+}
+
+/// A single data-driven quirk: match criteria against ISO metadata, and
+/// the remediation to apply or suggest when it matches. Shipped with a
+/// [`QuirkRuleSet::builtin`] default set and refreshable independently via
+/// [`crate::updater::BundledAsset::IsoQuirkRules`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuirkRule {
+    pub id: String,
+    pub description: String,
+    #[serde(default)]
+    pub os_type_contains: Option<String>,
+    #[serde(default)]
+    pub file_name_contains: Option<String>,
+    #[serde(default)]
+    pub architecture: Option<String>,
+    /// Whether [`QuirkRuleSet::matching`] should treat this as safe to
+    /// apply automatically, versus one to merely surface as a suggestion.
+    #[serde(default)]
+    pub auto_apply: bool,
+    pub remediation: Remediation,
+}
+
+impl QuirkRule {
+    fn matches(&self, iso_info: &crate::iso::IsoInfo, file_name: &str) -> bool {
+        if let Some(needle) = &self.os_type_contains {
+            if !iso_info.os_type.to_lowercase().contains(&needle.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if let Some(needle) = &self.file_name_contains {
+            if !file_name.to_lowercase().contains(&needle.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if let Some(arch) = &self.architecture {
+            match &iso_info.architecture {
+                Some(iso_arch) if iso_arch.eq_ignore_ascii_case(arch) => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// The set of quirk rules currently in effect, either the built-in
+/// defaults or one loaded from the asset channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuirkRuleSet {
+    rules: Vec<QuirkRule>,
+}
+
+impl QuirkRuleSet {
+    /// Known-quirky ISOs this crate ships remediations for out of the box.
+    pub fn builtin() -> Self {
+        Self {
+            rules: vec![
+                QuirkRule {
+                    id: "opensuse-boot-params".to_string(),
+                    description: "openSUSE installers need explicit install= boot parameters on some hardware".to_string(),
+                    os_type_contains: Some("opensuse".to_string()),
+                    file_name_contains: None,
+                    architecture: None,
+                    auto_apply: false,
+                    remediation: Remediation::ExtraBootParams { params: "install=hd:/?device=disk".to_string() },
+                },
+                QuirkRule {
+                    id: "freebsd-memstick-raw".to_string(),
+                    description: "FreeBSD memstick images are raw disk images and must be written sector-by-sector".to_string(),
+                    os_type_contains: None,
+                    file_name_contains: Some("memstick".to_string()),
+                    architecture: None,
+                    auto_apply: true,
+                    remediation: Remediation::ForceWriteMode { write_mode: "raw".to_string() },
+                },
+                QuirkRule {
+                    id: "windows-arm64-boot-file".to_string(),
+                    description: "Windows ARM64 media boots via bootaa64.efi instead of bootx64.efi".to_string(),
+                    os_type_contains: Some("windows".to_string()),
+                    file_name_contains: None,
+                    architecture: Some("aarch64".to_string()),
+                    auto_apply: true,
+                    remediation: Remediation::UseBootFile { path: "efi/boot/bootaa64.efi".to_string() },
+                },
+            ],
+        }
+    }
+
+    pub fn from_rules(rules: Vec<QuirkRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Rules matching this ISO's metadata and file name, in declaration
+    /// order.
+    pub fn matching<'a>(&'a self, iso_info: &crate::iso::IsoInfo, file_name: &str) -> Vec<&'a QuirkRule> {
+        self.rules.iter().filter(|rule| rule.matches(iso_info, file_name)).collect()
+    }
+}
+
+impl Default for QuirkRuleSet {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iso::IsoInfo;
+
+    fn iso_info(os_type: &str, architecture: Option<&str>) -> IsoInfo {
+        IsoInfo {
+            path: "test.iso".to_string(),
+            size: 0,
+            os_type: os_type.to_string(),
+            version: None,
+            architecture: architecture.map(|a| a.to_string()),
+            has_large_files: false,
+            bootable: true,
+            supports_uefi: true,
+            supports_legacy: false,
+            distro_name: None,
+            distro_version: None,
+            desktop_environment: None,
+        }
+    }
+
+    #[test]
+    fn matches_freebsd_memstick_by_filename() {
+        let rules = QuirkRuleSet::builtin();
+        let matches = rules.matching(&iso_info("Unknown", None), "FreeBSD-14.0-RELEASE-amd64-memstick.img");
+        assert!(matches.iter().any(|r| r.id == "freebsd-memstick-raw"));
+    }
+
+    #[test]
+    fn matches_windows_arm64_by_os_and_architecture() {
+        let rules = QuirkRuleSet::builtin();
+        let matches = rules.matching(&iso_info("Windows", Some("aarch64")), "windows11-arm64.iso");
+        assert!(matches.iter().any(|r| r.id == "windows-arm64-boot-file"));
+    }
+
+    #[test]
+    fn no_match_for_unrelated_iso() {
+        let rules = QuirkRuleSet::builtin();
+        let matches = rules.matching(&iso_info("Ubuntu", Some("x86_64")), "ubuntu-24.04.iso");
+        assert!(matches.is_empty());
+    }
+}
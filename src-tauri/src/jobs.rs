@@ -0,0 +1,77 @@
+use crate::config::CreateConfig;
+use serde::{Deserialize, Serialize};
+
+/// What has to become true before a queued job's destructive work starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JobTrigger {
+    /// Start as soon as the jobs subsystem picks it up.
+    Immediate,
+    /// Start at (or after) an absolute time, e.g. queuing overnight imaging
+    /// of a large Windows To Go stick at the end of the workday.
+    At(chrono::DateTime<chrono::Utc>),
+    /// Start once a device matching this path/serial is inserted.
+    OnDeviceInserted { device_hint: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    pub id: String,
+    pub source_path: String,
+    pub target_device: String,
+    pub config: CreateConfig,
+    pub trigger: JobTrigger,
+    /// Where to send a completion notification once the job finishes, if
+    /// anywhere. See [`crate::notify::Notifier`].
+    #[serde(default)]
+    pub notify: Option<crate::notify::NotificationTarget>,
+}
+
+impl ScheduledJob {
+    /// Whether the trigger condition is satisfied given the current time
+    /// and, for device-triggered jobs, the device that was just inserted.
+    pub fn is_ready(&self, now: chrono::DateTime<chrono::Utc>, inserted_device: Option<&str>) -> bool {
+        match &self.trigger {
+            JobTrigger::Immediate => true,
+            JobTrigger::At(scheduled_time) => now >= *scheduled_time,
+            JobTrigger::OnDeviceInserted { device_hint } => {
+                inserted_device.map(|d| d == device_hint).unwrap_or(false)
+            }
+        }
+    }
+}
+
+/// FIFO queue of jobs waiting on their trigger, polled by the jobs
+/// subsystem rather than started immediately by the GUI command.
+pub struct JobQueue {
+    jobs: Vec<ScheduledJob>,
+}
+
+impl JobQueue {
+    pub fn new() -> Self {
+        Self { jobs: Vec::new() }
+    }
+
+    pub fn enqueue(&mut self, job: ScheduledJob) {
+        self.jobs.push(job);
+    }
+
+    /// Remove and return jobs whose trigger condition is currently met.
+    pub fn drain_ready(&mut self, now: chrono::DateTime<chrono::Utc>, inserted_device: Option<&str>) -> Vec<ScheduledJob> {
+        let (ready, pending): (Vec<_>, Vec<_>) = self
+            .jobs
+            .drain(..)
+            .partition(|job| job.is_ready(now, inserted_device));
+        self.jobs = pending;
+        ready
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.jobs.len()
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,9 @@
+//! Library surface exposing the pure, I/O-free parsing modules so they can
+//! be fuzzed and unit tested outside the Tauri binary. The binary (`main.rs`)
+//! includes these same files directly and is otherwise unaffected.
+
+pub mod error;
+pub mod iso_listing;
+pub mod target_os;
+pub mod units;
+pub mod write_cache;
@@ -0,0 +1,97 @@
+use crate::error::{Result, WowUsbError};
+use std::path::Path;
+
+/// Files/directories other operating systems drop onto a mounted volume
+/// while browsing or writing to it, unrelated to anything the ISO itself
+/// shipped. Left in place, these show up as spurious extras when later
+/// diffing a stick against its source (see
+/// [`crate::stick_contents::list_contents`]), or just clutter the stick for
+/// anyone who browses it.
+const LITTER_ENTRIES: &[&str] = &[
+    "System Volume Information",
+    "$RECYCLE.BIN",
+    "desktop.ini",
+    "Thumbs.db",
+    ".Trashes",
+    ".Trash-1000",
+    ".DS_Store",
+    ".fseventsd",
+    ".Spotlight-V100",
+    ".TemporaryItems",
+];
+
+/// Removes every entry in [`LITTER_ENTRIES`] found anywhere under
+/// `mountpoint`, opt-in via
+/// [`crate::config::CreateConfig::clean_os_litter`].
+pub async fn clean(mountpoint: &str) -> Result<()> {
+    let mountpoint = mountpoint.to_string();
+    tokio::task::spawn_blocking(move || remove_litter_recursive(Path::new(&mountpoint)))
+        .await
+        .map_err(|e| WowUsbError::filesystem(format!("Litter cleanup task panicked: {}", e)))?
+}
+
+fn remove_litter_recursive(dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let metadata = entry.metadata()?;
+
+        if LITTER_ENTRIES.iter().any(|litter| litter.eq_ignore_ascii_case(&name)) {
+            if metadata.is_dir() {
+                std::fs::remove_dir_all(&path)?;
+            } else {
+                std::fs::remove_file(&path)?;
+            }
+            continue;
+        }
+
+        if metadata.is_dir() {
+            remove_litter_recursive(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wowusb_litter_cleanup_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn removes_known_litter_but_keeps_real_content() {
+        let root = temp_dir("litter");
+        std::fs::create_dir_all(root.join("System Volume Information")).unwrap();
+        std::fs::write(root.join("System Volume Information/tracking.log"), b"junk").unwrap();
+        std::fs::write(root.join(".DS_Store"), b"finder metadata").unwrap();
+        std::fs::create_dir_all(root.join("sources")).unwrap();
+        std::fs::write(root.join("sources/Thumbs.db"), b"thumbnail cache").unwrap();
+        std::fs::write(root.join("sources/install.wim"), b"real content").unwrap();
+
+        clean(root.to_str().unwrap()).await.unwrap();
+
+        assert!(!root.join("System Volume Information").exists());
+        assert!(!root.join(".DS_Store").exists());
+        assert!(!root.join("sources/Thumbs.db").exists());
+        assert!(root.join("sources/install.wim").exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn no_op_on_a_clean_tree() {
+        let root = temp_dir("clean");
+        std::fs::write(root.join("readme.txt"), b"hello").unwrap();
+
+        clean(root.to_str().unwrap()).await.unwrap();
+
+        assert!(root.join("readme.txt").exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,49 @@
+use crate::error::Result;
+use tokio::process::Command as AsyncCommand;
+
+/// The Linux Security Module active on the host, if any. Mounting and
+/// copying to the stick can otherwise fail, or produce files with the
+/// wrong security context, on hardened distros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LsmKind {
+    SeLinux,
+    AppArmor,
+    None,
+}
+
+pub fn detect_lsm() -> LsmKind {
+    if std::path::Path::new("/sys/fs/selinux").exists() {
+        return LsmKind::SeLinux;
+    }
+
+    if std::path::Path::new("/sys/kernel/security/apparmor").exists() {
+        return LsmKind::AppArmor;
+    }
+
+    LsmKind::None
+}
+
+/// Extra mount options to pass so the created filesystem doesn't inherit a
+/// restrictive default context under SELinux.
+pub fn mount_options_for(lsm: LsmKind) -> Vec<&'static str> {
+    match lsm {
+        LsmKind::SeLinux => vec!["context=system_u:object_r:removable_t:s0"],
+        LsmKind::AppArmor | LsmKind::None => Vec::new(),
+    }
+}
+
+/// Restore the distro's default SELinux context on files copied onto the
+/// stick; a no-op (and not an error) when SELinux isn't active or
+/// `restorecon` isn't installed.
+pub async fn restore_default_context(path: &str) -> Result<()> {
+    if detect_lsm() != LsmKind::SeLinux {
+        return Ok(());
+    }
+
+    let _ = AsyncCommand::new("restorecon")
+        .args(&["-R", path])
+        .output()
+        .await;
+
+    Ok(())
+}
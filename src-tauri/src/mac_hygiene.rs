@@ -0,0 +1,107 @@
+use crate::error::{Result, WowUsbError};
+use std::path::Path;
+
+/// Marker file Spotlight's `mds`/`mdworker` honor to skip indexing a whole
+/// volume; without it, mounting a freshly-written stick under macOS
+/// triggers indexing that can keep `mds` busy on it long enough to make the
+/// subsequent unmount fail with "resource busy".
+const METADATA_NEVER_INDEX: &str = ".metadata_never_index";
+
+/// Marker file inside `.fseventsd` telling the file system events daemon
+/// not to keep a change log for this volume, so pulling the stick doesn't
+/// leave macOS re-scanning it for events on every future mount.
+const FSEVENTSD_NO_LOG: &str = ".fseventsd/no_log";
+
+/// Suppresses Spotlight indexing and `.fseventsd` change-logging on a
+/// freshly-written stick, and cleans up the AppleDouble (`._*`) sidecar
+/// files and `.DS_Store` that macOS itself may have dropped onto it while
+/// the mounted volume was being browsed or copied to. A no-op everywhere
+/// except macOS, since none of this applies elsewhere.
+pub async fn apply(mountpoint: &str) -> Result<()> {
+    if !cfg!(target_os = "macos") {
+        return Ok(());
+    }
+
+    let mountpoint = mountpoint.to_string();
+    tokio::task::spawn_blocking(move || apply_blocking(&mountpoint))
+        .await
+        .map_err(|e| WowUsbError::filesystem(format!("macOS hygiene cleanup task panicked: {}", e)))?
+}
+
+fn apply_blocking(mountpoint: &str) -> Result<()> {
+    let root = Path::new(mountpoint);
+
+    std::fs::write(root.join(METADATA_NEVER_INDEX), b"")?;
+
+    let fseventsd_marker = root.join(FSEVENTSD_NO_LOG);
+    std::fs::create_dir_all(fseventsd_marker.parent().unwrap())?;
+    std::fs::write(&fseventsd_marker, b"")?;
+
+    remove_apple_double_files(root)
+}
+
+/// Recursively deletes `._*` AppleDouble sidecar files and `.DS_Store`
+/// under `dir`, but leaves `.metadata_never_index` and `.fseventsd` (just
+/// written above) alone.
+fn remove_apple_double_files(dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let metadata = entry.metadata()?;
+
+        if metadata.is_dir() {
+            if name != ".fseventsd" {
+                remove_apple_double_files(&path)?;
+            }
+        } else if name.starts_with("._") || name == ".DS_Store" {
+            std::fs::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wowusb_mac_hygiene_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn removes_apple_double_files_recursively() {
+        let root = temp_dir("apple_double");
+        std::fs::write(root.join("._readme.txt"), b"resource fork").unwrap();
+        std::fs::write(root.join(".DS_Store"), b"finder metadata").unwrap();
+        std::fs::write(root.join("readme.txt"), b"real content").unwrap();
+        std::fs::create_dir_all(root.join("sources")).unwrap();
+        std::fs::write(root.join("sources/._install.wim"), b"resource fork").unwrap();
+
+        remove_apple_double_files(&root).unwrap();
+
+        assert!(!root.join("._readme.txt").exists());
+        assert!(!root.join(".DS_Store").exists());
+        assert!(root.join("readme.txt").exists());
+        assert!(!root.join("sources/._install.wim").exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn apply_writes_index_and_fsevents_markers() {
+        if !cfg!(target_os = "macos") {
+            return;
+        }
+
+        let root = temp_dir("markers");
+        apply(root.to_str().unwrap()).await.unwrap();
+
+        assert!(root.join(METADATA_NEVER_INDEX).exists());
+        assert!(root.join(FSEVENTSD_NO_LOG).exists());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -4,21 +4,87 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tauri::Manager;
 
+mod audit_log;
+mod batch;
+mod boot_params;
+mod boot_verify;
+mod bootloader;
+mod busy_mount;
+mod cancellation;
+mod checksum_db;
+mod cmdrunner;
+mod compact_os;
+mod compat_reports;
+mod config;
+mod defender;
+mod device_rules;
+mod device_watch;
 mod disk;
+mod encryption;
+mod env_snapshot;
+mod esp_sizing;
+mod file_injection;
 mod filesystem;
+mod flush_progress;
+mod geometry;
+mod grub_tooling;
+mod hashing;
+mod helper;
+mod hostenv;
 mod iso;
+mod iso_library;
+mod iso_listing;
+mod iso_mastering;
+mod iso_quirks;
+mod jobs;
+mod litter_cleanup;
+mod lsm;
+mod mac_hygiene;
+mod manifest;
+mod metrics;
+mod notify;
+mod optical;
+mod persistence_overlay;
 mod platform;
+mod platform_paths;
+mod policy;
+mod prewipe;
 mod progress;
+mod provenance;
+mod rawwrite;
+mod report;
+mod scheduler;
+mod session_recovery;
+mod sim_disk;
+mod slipstream;
+mod squashfs_inspect;
+mod staging;
+mod stick_contents;
+mod support_bundle;
+mod target_os;
+mod tool_paths;
+mod undo_window;
+mod units;
+mod updater;
+mod windows_lang;
+mod windows_unattend;
+mod wintogo_profiles;
+mod wipe_certificate;
+mod write_cache;
 mod error;
 mod version;
 
+use config::CreateConfig;
 use disk::{DiskManager, PlatformDiskOps};
 use filesystem::{FilesystemManager, PlatformFilesystemOps};
+use metrics::MetricsRegistry;
 use progress::{ProgressManager, ProgressUpdate};
+use provenance::ProvenanceManifest;
 use error::WowUsbError;
 use version;
 
-#[derive(Clone, serde::Serialize)]
+#[derive(Clone, serde::Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../../src/bindings/")]
 struct DeviceInfo {
     name: String,
     size: String,
@@ -27,23 +93,23 @@ struct DeviceInfo {
     mountpoint: Option<String>,
     is_removable: bool,
     is_usb: bool,
+    bus_type: Option<String>,
+    preselected: bool,
 }
 
-#[derive(Clone, serde::Serialize, serde::Deserialize)]
-struct CreateConfig {
-    target_os: String,
-    filesystem: String,
-    enable_persistence: bool,
-    enable_multiboot: bool,
-    wintogo_enabled: bool,
-    drive_label: String,
+#[derive(Clone, serde::Serialize)]
+struct SlipstreamProgressEvent {
+    applied: usize,
+    total: usize,
+    package_path: String,
 }
 
-#[derive(Clone, serde::Serialize)]
+#[derive(Clone, serde::Serialize, ts_rs::TS)]
+#[ts(export, export_to = "../../src/bindings/")]
 struct ProgressEvent {
     progress: u8,
     message: String,
-    stage: String,
+    stage: progress::Stage,
     timestamp: String,
 }
 
@@ -52,11 +118,29 @@ struct AppState {
     disk_manager: Arc<DiskManager>,
     filesystem_manager: Arc<FilesystemManager>,
     progress_manager: Arc<RwLock<ProgressManager>>,
+    metrics: Arc<MetricsRegistry>,
+    failure_injection: Arc<RwLock<sim_disk::FailureInjectionPlan>>,
+    last_creation_report: Arc<RwLock<Option<report::CreationReport>>>,
+    device_watcher: Arc<device_watch::DeviceWatcher>,
+    /// Set for the duration of a [`create_bootable_usb`] call, so a window
+    /// close or SIGTERM knows whether there's a write in flight to cancel
+    /// and wait on instead of exiting out from under it.
+    job_in_flight: Arc<std::sync::atomic::AtomicBool>,
+    /// Cancellation for the currently running (or next) job, kept separate
+    /// from `progress_manager` so it can be reset per-job without touching
+    /// the progress event hub.
+    cancellation: cancellation::CancellationToken,
+    /// The current job's pre-destructive grace period, if it requested one,
+    /// so `abort_undo_window` has something to abort. `None` between jobs
+    /// and for jobs that didn't request a grace period.
+    undo_window: Arc<RwLock<Option<Arc<undo_window::UndoWindow>>>>,
+    iso_library: Arc<iso_library::IsoLibrary>,
+    job_queue: Arc<RwLock<jobs::JobQueue>>,
 }
 
 #[tauri::command]
 async fn list_devices(state: tauri::State<'_, AppState>) -> Result<Vec<DeviceInfo>, String> {
-    let devices = state.disk_manager.list_devices().await
+    let devices = state.device_watcher.poll(&state.disk_manager).await
         .map_err(|e| e.to_string())?;
 
     Ok(devices.into_iter().map(|d| DeviceInfo {
@@ -67,9 +151,37 @@ async fn list_devices(state: tauri::State<'_, AppState>) -> Result<Vec<DeviceInf
         mountpoint: d.mountpoint,
         is_removable: d.is_removable,
         is_usb: d.is_usb,
+        bus_type: d.bus_type,
+        preselected: d.preselected,
     }).collect())
 }
 
+/// Mark `serial` as never to be listed or operated on, e.g. the user's
+/// backup drive.
+#[tauri::command]
+async fn deny_device(serial: String) -> Result<(), String> {
+    let mut rules = device_rules::DeviceRules::load(device_rules::device_rules_path()).map_err(|e| e.to_string())?;
+    rules.deny(serial);
+    rules.save(device_rules::device_rules_path()).map_err(|e| e.to_string())
+}
+
+/// Mark `serial` to always be preselected in the device picker, e.g. a
+/// stick permanently dedicated to WowUSB.
+#[tauri::command]
+async fn preselect_device(serial: String) -> Result<(), String> {
+    let mut rules = device_rules::DeviceRules::load(device_rules::device_rules_path()).map_err(|e| e.to_string())?;
+    rules.preselect(serial);
+    rules.save(device_rules::device_rules_path()).map_err(|e| e.to_string())
+}
+
+/// Remove any deny/preselect rule recorded for `serial`.
+#[tauri::command]
+async fn clear_device_rule(serial: String) -> Result<(), String> {
+    let mut rules = device_rules::DeviceRules::load(device_rules::device_rules_path()).map_err(|e| e.to_string())?;
+    rules.clear(&serial);
+    rules.save(device_rules::device_rules_path()).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn verify_device(
     device: String,
@@ -81,6 +193,27 @@ async fn verify_device(
     Ok(result)
 }
 
+/// Non-destructively check whether `device` can actually be written to
+/// before the user spends time configuring a whole job around it.
+#[tauri::command]
+async fn check_permissions(
+    device: String,
+    state: tauri::State<'_, AppState>
+) -> Result<disk::PermissionCheck, String> {
+    state.disk_manager.check_permissions(&device).await
+        .map_err(|e| e.to_string())
+}
+
+/// Whether Defender's real-time scanning would slow down a copy to
+/// `mountpoint`, for warning users who don't opt into
+/// `suspend_realtime_scanning`. Always `false` outside Windows. See
+/// [`crate::defender::realtime_scanning_active_for`].
+#[tauri::command]
+async fn check_realtime_scanning_slowdown_risk(mountpoint: String) -> Result<bool, String> {
+    defender::realtime_scanning_active_for(&mountpoint).await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn create_bootable_usb(
     source_path: String,
@@ -104,7 +237,7 @@ async fn create_bootable_usb(
             let progress_event = ProgressEvent {
                 progress: progress.progress,
                 message: progress.message.clone(),
-                stage: progress.stage.clone(),
+                stage: progress.stage,
                 timestamp: chrono::Utc::now().to_rfc3339(),
             };
 
@@ -112,14 +245,41 @@ async fn create_bootable_usb(
         }
     });
 
-    // Start the USB creation process
+    // Start the USB creation process. A previous job's cancellation must
+    // not carry over and immediately abort this one.
+    state.cancellation.reset();
+    state.job_in_flight.store(true, std::sync::atomic::Ordering::SeqCst);
+    state.metrics.record_job_started();
+
+    let undo_window = config
+        .undo_grace_period_seconds
+        .map(|seconds| Arc::new(undo_window::UndoWindow::new(seconds)));
+    *state.undo_window.write().await = undo_window.clone();
+
     let result = state.disk_manager.create_bootable_usb(
         &source_path,
         &target_device,
-        &config
-    ).await.map_err(|e| e.to_string())?;
+        &config,
+        Some(&state.progress_manager),
+        Some(&state.cancellation),
+        undo_window.as_deref(),
+    ).await;
+    *state.undo_window.write().await = None;
+    state.job_in_flight.store(false, std::sync::atomic::Ordering::SeqCst);
 
-    Ok(result)
+    match result {
+        Ok((message, creation_report)) => {
+            state.metrics.record_bytes_written(
+                creation_report.stages.iter().map(|s| s.bytes).sum()
+            );
+            *state.last_creation_report.write().await = Some(creation_report);
+            Ok(message)
+        }
+        Err(e) => {
+            state.metrics.record_job_failed(e.error_code());
+            Err(e.to_string())
+        }
+    }
 }
 
 #[tauri::command]
@@ -143,14 +303,110 @@ async fn validate_iso(
     Ok(result)
 }
 
+/// Import `iso_path` into the local "my images" library, deduplicated by
+/// content hash. See [`crate::iso_library::IsoLibrary::import`].
+#[tauri::command]
+async fn import_iso_to_library(
+    iso_path: String,
+    state: tauri::State<'_, AppState>
+) -> Result<iso_library::LibraryEntry, String> {
+    state.iso_library.import(&iso_path).await.map_err(|e| e.to_string())
+}
+
+/// List every ISO currently in the library, for the "my images" UI.
+#[tauri::command]
+async fn list_iso_library(
+    state: tauri::State<'_, AppState>
+) -> Result<Vec<iso_library::LibraryEntry>, String> {
+    state.iso_library.list().map_err(|e| e.to_string())
+}
+
+/// Remove an entry (and its stored copy) from the library.
+#[tauri::command]
+async fn remove_from_iso_library(
+    sha256: String,
+    state: tauri::State<'_, AppState>
+) -> Result<(), String> {
+    state.iso_library.remove(&sha256).map_err(|e| e.to_string())
+}
+
+/// Compare every library entry against the locally-cached checksum
+/// database and flag which ones have a newer release available. See
+/// [`crate::iso_library::IsoLibrary::check_all_staleness`].
+#[tauri::command]
+async fn check_iso_library_staleness(
+    state: tauri::State<'_, AppState>
+) -> Result<Vec<(iso_library::LibraryEntry, iso_library::StaleCheck)>, String> {
+    let db = checksum_db::ChecksumDatabase::load_from_cache(
+        &checksum_db::checksum_db_cache_path().to_string_lossy()
+    ).map_err(|e| e.to_string())?;
+
+    state.iso_library.check_all_staleness(&db).map_err(|e| e.to_string())
+}
+
+/// Burn `iso_path` to an optical drive instead of a USB stick, for BIOS
+/// recovery workflows that still expect a CD/DVD/BD. See
+/// [`crate::optical::OpticalBurnTarget`].
+#[tauri::command]
+async fn burn_optical_media(iso_path: String, burner_device: String) -> Result<(), String> {
+    optical::OpticalBurnTarget::new()
+        .burn(&iso_path, &burner_device)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn cancel_operation(
     state: tauri::State<'_, AppState>
 ) -> Result<bool, String> {
-    let result = state.progress_manager.write().await.cancel().await
-        .map_err(|e| e.to_string())?;
+    state.cancellation.cancel();
+    Ok(true)
+}
 
-    Ok(result)
+/// Queue a job to run once its trigger condition is satisfied, instead of
+/// starting immediately like `create_bootable_usb`. Picked up by the
+/// background poller spawned in `main`. See [`crate::jobs::JobQueue`].
+#[tauri::command]
+async fn enqueue_job(
+    source_path: String,
+    target_device: String,
+    config: CreateConfig,
+    trigger: jobs::JobTrigger,
+    notify: Option<notify::NotificationTarget>,
+    state: tauri::State<'_, AppState>
+) -> Result<String, String> {
+    let id = format!("job_{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default());
+    state.job_queue.write().await.enqueue(jobs::ScheduledJob {
+        id: id.clone(),
+        source_path,
+        target_device,
+        config,
+        trigger,
+        notify,
+    });
+    Ok(id)
+}
+
+/// How many queued jobs are still waiting on their trigger.
+#[tauri::command]
+async fn pending_job_count(state: tauri::State<'_, AppState>) -> Result<usize, String> {
+    Ok(state.job_queue.read().await.pending_count())
+}
+
+/// Abort the current job's pre-destructive grace period, if it's still
+/// waiting one out. A no-op (returning `false`) once the grace period has
+/// already elapsed or the job didn't request one.
+#[tauri::command]
+async fn abort_undo_window(
+    state: tauri::State<'_, AppState>
+) -> Result<bool, String> {
+    match state.undo_window.read().await.as_ref() {
+        Some(undo_window) => {
+            undo_window.abort();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
 }
 
 #[tauri::command]
@@ -158,31 +414,592 @@ async fn get_version() -> Result<String, String> {
     Ok(version::VERSION.to_string())
 }
 
+#[tauri::command]
+async fn read_manifest(stick_root: String) -> Result<Option<ProvenanceManifest>, String> {
+    ProvenanceManifest::read_from(&stick_root).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_metrics_snapshot(state: tauri::State<'_, AppState>) -> Result<String, String> {
+    Ok(state.metrics.render())
+}
+
+#[tauri::command]
+async fn generate_support_bundle(
+    session_log: String,
+    output_path: String,
+    last_operation_report: Option<serde_json::Value>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let devices = state.disk_manager.list_devices().await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(support_bundle::SanitizedDevice::from)
+        .collect();
+
+    let last_operation_report = match last_operation_report {
+        Some(report) => Some(report),
+        None => state.last_creation_report.read().await.as_ref()
+            .and_then(|r| serde_json::to_value(r).ok()),
+    };
+
+    let mut builder = support_bundle::SupportBundleBuilder::new(session_log)
+        .with_devices(devices)
+        .with_last_operation_report(last_operation_report);
+
+    let tool_paths = tool_paths::ToolPaths::load().unwrap_or_default();
+    for (tool, args) in [("parted", &["--version"][..]), ("7z", &["--help"][..]), ("grub-install", &["--version"][..])] {
+        if let Ok(output) = std::process::Command::new(tool_paths.resolve(tool)).args(args).output() {
+            let version = String::from_utf8_lossy(&output.stdout).lines().next().unwrap_or_default().to_string();
+            builder = builder.with_tool_version(tool, version);
+        }
+    }
+
+    builder.write_to(&output_path).map_err(|e| e.to_string())?;
+
+    Ok(output_path)
+}
+
+#[tauri::command]
+async fn check_updates(
+    channel: updater::UpdateChannel,
+    manifest_url_base: String,
+) -> Result<updater::UpdateCheckResult, String> {
+    let checker = updater::UpdateChecker::new(manifest_url_base);
+    checker
+        .check_app_update(version::VERSION, channel)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn mount_iso_readonly(
+    iso_path: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let staging = staging::StagingDirectory::resolve(None);
+    let mountpoint = staging
+        .job_dir(&format!("iso_browse_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+
+    state.disk_manager.mount_iso_readonly(&iso_path, &mountpoint).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn unmount_iso(
+    mountpoint: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    match state.disk_manager.unmount_iso(&mountpoint).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let busy = state.disk_manager.list_busy_processes(&mountpoint).await.unwrap_or_default();
+            if busy.is_empty() {
+                Err(e.to_string())
+            } else {
+                let holders: Vec<String> = busy.iter().map(|p| format!("{} (pid {})", p.command, p.pid)).collect();
+                Err(format!("{} — held open by: {}", e, holders.join(", ")))
+            }
+        }
+    }
+}
+
+/// Unmount `mountpoint` even if a process still has files open on it,
+/// after a regular [`unmount_iso`] has already failed and the operator has
+/// been shown who's holding it open.
+#[tauri::command]
+async fn force_unmount_iso(
+    mountpoint: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.disk_manager.force_unmount_partition(&mountpoint).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn extract_iso_file(
+    iso_path: String,
+    internal_path: String,
+    dest: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    state.disk_manager.extract_iso_file(&iso_path, &internal_path, &dest).await
+        .map_err(|e| e.to_string())?;
+    Ok(dest)
+}
+
+#[tauri::command]
+async fn slipstream_windows_updates(
+    wim_path: String,
+    image_index: u32,
+    update_paths: Vec<String>,
+    window: tauri::Window,
+) -> Result<(), String> {
+    let packages: Vec<slipstream::UpdatePackage> = update_paths
+        .into_iter()
+        .map(|path| slipstream::UpdatePackage { path })
+        .collect();
+
+    slipstream::UpdateSlipstreamer::new()
+        .apply_updates(&wim_path, image_index, &packages, |applied, total, package_path| {
+            let _ = window.emit("slipstream_progress", &SlipstreamProgressEvent {
+                applied,
+                total,
+                package_path: package_path.to_string(),
+            });
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn list_windows_languages(wim_path: String, image_index: u32) -> Result<Vec<String>, String> {
+    windows_lang::LanguageSelector::new()
+        .list_available(&wim_path, image_index)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn select_windows_languages(
+    wim_path: String,
+    image_index: u32,
+    keep_languages: Vec<String>,
+) -> Result<(), String> {
+    windows_lang::LanguageSelector::new()
+        .apply_selection(&wim_path, image_index, &keep_languages)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn detect_iso_quirks(iso_path: String) -> Result<Vec<iso_quirks::QuirkRule>, String> {
+    let processor = iso::IsoProcessor::new();
+    let iso_info = processor.analyze_iso(&iso_path).await.map_err(|e| e.to_string())?;
+
+    let file_name = std::path::Path::new(&iso_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| iso_path.clone());
+
+    let rules = iso_quirks::QuirkRuleSet::builtin();
+    Ok(rules.matching(&iso_info, &file_name).into_iter().cloned().collect())
+}
+
+/// Boot parameters [`boot_params::BootParamsDatabase`] recommends for
+/// `iso_path`'s distro family, both the ones a multiboot entry applies
+/// automatically and ones only worth suggesting (e.g. `nomodeset`).
+#[tauri::command]
+async fn recommended_boot_params(iso_path: String) -> Result<Vec<boot_params::BootParamRule>, String> {
+    let processor = iso::IsoProcessor::new();
+    let iso_info = processor.analyze_iso(&iso_path).await.map_err(|e| e.to_string())?;
+
+    let db = boot_params::BootParamsDatabase::builtin();
+    Ok(db.matching(&iso_info.os_type).into_iter().cloned().collect())
+}
+
+#[tauri::command]
+async fn master_iso(source_dir: String, output_path: String, volume_label: String) -> Result<(), String> {
+    iso_mastering::IsoMaster::new()
+        .master_iso(&source_dir, &output_path, &volume_label)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn reverify_stick(
+    stick_root: String,
+    target_device: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    let verified = state.disk_manager.verify_device(&target_device).await
+        .map_err(|e| e.to_string())?;
+
+    let event = audit_log::AuditEvent::new(
+        audit_log::AuditAction::Reverified,
+        None,
+        format!("Re-verified {} on {}: {}", stick_root, target_device, if verified { "present" } else { "missing" }),
+    );
+    audit_log::AuditLog::append_to_stick(&stick_root, &event).map_err(|e| e.to_string())?;
+    audit_log::AuditLog::append_to_local_history(audit_log::local_history_path(), &event).map_err(|e| e.to_string())?;
+
+    Ok(verified)
+}
+
+/// A fast, ~30-second alternative to hashing the whole stick: check just
+/// the files a boot actually depends on. See [`boot_verify::quick_verify`].
+#[tauri::command]
+async fn quick_verify_stick(
+    iso_path: String,
+    stick_root: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<boot_verify::BootFileVerification>, String> {
+    let staging = staging::StagingDirectory::resolve(None);
+    let iso_mountpoint = staging
+        .job_dir(&format!("quick_verify_{}", std::process::id()))
+        .to_string_lossy()
+        .to_string();
+
+    let mounted_at = state.disk_manager.mount_iso_readonly(&iso_path, &iso_mountpoint).await
+        .map_err(|e| e.to_string())?;
+
+    let results = boot_verify::quick_verify(&mounted_at, &stick_root).await;
+
+    // Best-effort: the result of the comparison matters more than a
+    // leftover mount, and we still want to return it if unmounting fails.
+    let _ = state.disk_manager.unmount_iso(&mounted_at).await;
+
+    results.map_err(|e| e.to_string())
+}
+
+/// List everything actually written to `device` by mounting it read-only
+/// and hashing every file, for auditing a stick found in an unknown state
+/// without risking a write to it. See
+/// [`crate::disk::DiskManager::list_usb_contents`].
+#[tauri::command]
+async fn list_usb_contents(
+    device: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<stick_contents::ContentEntry, String> {
+    state.disk_manager.list_usb_contents(&device).await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn read_audit_history() -> Result<Vec<audit_log::AuditEvent>, String> {
+    audit_log::AuditLog::read_all(audit_log::local_history_path()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn record_compatibility_report(
+    report: compat_reports::CompatibilityReport,
+) -> Result<(), String> {
+    compat_reports::CompatReportLog::append(compat_reports::local_reports_path(), &report)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn read_compatibility_reports(iso_sha256: String) -> Result<Vec<compat_reports::CompatibilityReport>, String> {
+    compat_reports::CompatReportLog::for_iso(compat_reports::local_reports_path(), &iso_sha256)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn preview_pipeline_commands(
+    source_path: String,
+    target_device: String,
+    config: CreateConfig,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<cmdrunner::PlannedCommand>, String> {
+    state.disk_manager.preview_commands(&source_path, &target_device, &config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn repair_bootloader(
+    target_device: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state.disk_manager.repair_bootloader(&target_device).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn check_filesystem(
+    partition: String,
+    filesystem: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<disk::FsckReport, String> {
+    state.disk_manager.check_filesystem(&partition, &filesystem).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn estimate_creation_duration(
+    source_path: String,
+    target_device: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<f64, String> {
+    state.disk_manager.estimate_duration_seconds(&source_path, &target_device).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn generate_wipe_certificate(
+    device_model: String,
+    device_serial: Option<String>,
+    method: String,
+    passes: u32,
+    operator: Option<String>,
+    output_json_path: String,
+    output_pdf_path: String,
+) -> Result<wipe_certificate::WipeCertificate, String> {
+    let certificate = wipe_certificate::WipeCertificate::new(device_model, device_serial, method, passes, operator);
+    certificate.write_json_to(&output_json_path).map_err(|e| e.to_string())?;
+    certificate.write_pdf_to(&output_pdf_path).map_err(|e| e.to_string())?;
+    Ok(certificate)
+}
+
+#[tauri::command]
+async fn list_orphaned_sessions() -> Result<Vec<session_recovery::OrphanedSession>, String> {
+    let staging_root = std::env::temp_dir();
+    session_recovery::scan(&staging_root).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn list_orphaned_loop_devices() -> Result<Vec<String>, String> {
+    Ok(session_recovery::find_orphaned_loop_devices(&std::env::temp_dir()))
+}
+
+#[tauri::command]
+async fn clean_orphaned_sessions(
+    sessions: Vec<session_recovery::OrphanedSession>,
+    loop_devices: Vec<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let results = session_recovery::clean_up(&sessions, state.disk_manager.ops()).await;
+    let failures: Vec<String> = results
+        .iter()
+        .filter_map(|(path, outcome)| outcome.as_ref().err().map(|e| format!("{}: {}", path, e)))
+        .collect();
+
+    if !failures.is_empty() {
+        return Err(failures.join("; "));
+    }
+
+    let mut cleaned: Vec<String> = results.into_iter().map(|(path, _)| path).collect();
+    cleaned.extend(session_recovery::release_orphaned_loop_devices(&loop_devices));
+    Ok(cleaned)
+}
+
+#[tauri::command]
+async fn configure_failure_injection(
+    plan: sim_disk::FailureInjectionPlan,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    *state.failure_injection.write().await = plan;
+    Ok(())
+}
+
+/// Run a job drained from [`jobs::JobQueue`] to completion, then notify
+/// `job.notify`'s target (if any) — this is the only caller that ever sees
+/// a scheduled job's outcome, since nothing kicked it off interactively.
+async fn run_scheduled_job(disk_manager: &Arc<DiskManager>, job: jobs::ScheduledJob) {
+    log::info!("Running scheduled job {} ({} -> {})", job.id, job.source_path, job.target_device);
+    let result = disk_manager
+        .create_bootable_usb(&job.source_path, &job.target_device, &job.config, None, None, None)
+        .await;
+
+    let (succeeded, operation_report_json) = match &result {
+        Ok((message, report)) => {
+            log::info!("Scheduled job {} finished: {}", job.id, message);
+            (true, serde_json::to_value(report).unwrap_or(serde_json::Value::Null))
+        }
+        Err(e) => {
+            log::error!("Scheduled job {} failed: {}", job.id, e);
+            (false, serde_json::json!({ "error": e.to_string() }))
+        }
+    };
+
+    if let Some(target) = &job.notify {
+        let notification = notify::JobCompletionNotification {
+            job_id: job.id.clone(),
+            succeeded,
+            operation_report_json,
+        };
+        if let Err(e) = notify::Notifier::new().send(target, &notification).await {
+            log::error!("Failed to send completion notification for job {}: {}", job.id, e);
+        }
+    }
+}
+
+/// Resolve once the process receives Ctrl+C or (on Unix) SIGTERM, so
+/// `main` can cancel an in-flight write and wait for its rollback instead
+/// of exiting out from under it.
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate = signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {},
+            _ = terminate.recv() => {},
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
 fn main() {
     // Initialize logging
     env_logger::init();
 
     // Create shared state
-    let disk_manager = Arc::new(DiskManager::new());
+    let failure_injection = Arc::new(RwLock::new(sim_disk::FailureInjectionPlan::default()));
+
+    // WOWUSB_SIMULATE swaps in an in-memory disk backend driven by
+    // `failure_injection`, so the GUI can be developed and its error/retry
+    // flows exercised without real hardware.
+    let disk_manager = if std::env::var("WOWUSB_SIMULATE").is_ok() {
+        Arc::new(DiskManager::new_simulated(failure_injection.clone()))
+    } else {
+        Arc::new(DiskManager::new())
+    };
     let filesystem_manager = Arc::new(FilesystemManager::new());
     let progress_manager = Arc::new(RwLock::new(ProgressManager::new()));
+    let metrics = Arc::new(MetricsRegistry::new());
+    let last_creation_report = Arc::new(RwLock::new(None));
+    let job_in_flight = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let cancellation = cancellation::CancellationToken::new();
+
+    // Daemon-mode stations can set WOWUSB_METRICS_ADDR (e.g. "127.0.0.1:9273")
+    // to expose a Prometheus-scrapable /metrics endpoint alongside the GUI.
+    if let Ok(addr) = std::env::var("WOWUSB_METRICS_ADDR") {
+        let metrics_for_server = metrics.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = metrics::serve_metrics(metrics_for_server, &addr).await {
+                log::error!("Metrics endpoint failed: {}", e);
+            }
+        });
+    }
 
     let app_state = AppState {
         disk_manager,
         filesystem_manager,
         progress_manager,
+        metrics,
+        failure_injection,
+        last_creation_report,
+        device_watcher: Arc::new(device_watch::DeviceWatcher::new(std::time::Duration::from_secs(3))),
+        job_in_flight,
+        cancellation,
+        undo_window: Arc::new(RwLock::new(None)),
+        iso_library: Arc::new(iso_library::IsoLibrary::resolve(None)),
+        job_queue: Arc::new(RwLock::new(jobs::JobQueue::new())),
     };
 
+    // SIGTERM/Ctrl+C during an in-flight write: cancel it and wait for
+    // `create_bootable_usb`'s rollback to finish unmounting before the
+    // process actually exits, rather than letting the OS tear the write
+    // down mid-copy and leave a corrupted stick behind.
+    {
+        let cancellation = app_state.cancellation.clone();
+        let job_in_flight = app_state.job_in_flight.clone();
+        tauri::async_runtime::spawn(async move {
+            wait_for_shutdown_signal().await;
+            log::warn!("Shutdown requested; cancelling any in-flight write and waiting for cleanup.");
+            cancellation.cancel();
+            while job_in_flight.load(std::sync::atomic::Ordering::SeqCst) {
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            }
+            std::process::exit(0);
+        });
+    }
+
+    // Background poller for `jobs::JobQueue`: runs immediate/time-triggered
+    // jobs on a fixed tick, and device-triggered jobs as soon as the device
+    // they're waiting for shows up in a `device_watcher` broadcast. Job
+    // outcomes are logged rather than returned anywhere, since nothing is
+    // necessarily listening the way a foreground `create_bootable_usb`
+    // caller's window is.
+    {
+        let disk_manager = app_state.disk_manager.clone();
+        let job_queue = app_state.job_queue.clone();
+        let mut device_changes = app_state.device_watcher.subscribe();
+        tauri::async_runtime::spawn(async move {
+            let mut tick = tokio::time::interval(std::time::Duration::from_secs(5));
+            loop {
+                tokio::select! {
+                    _ = tick.tick() => {
+                        let ready = job_queue.write().await.drain_ready(chrono::Utc::now(), None);
+                        for job in ready {
+                            run_scheduled_job(&disk_manager, job).await;
+                        }
+                    }
+                    Ok(event) = device_changes.recv() => {
+                        for device in &event.devices {
+                            let ready = job_queue.write().await.drain_ready(chrono::Utc::now(), Some(&device.name));
+                            for job in ready {
+                                run_scheduled_job(&disk_manager, job).await;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+    }
+
     tauri::Builder::default()
         .manage(app_state)
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                let state = window.state::<AppState>();
+                if state.job_in_flight.load(std::sync::atomic::Ordering::SeqCst) {
+                    api.prevent_close();
+                    log::warn!("Window close requested while a write is in flight; cancelling and waiting for cleanup.");
+                    let window = window.clone();
+                    let cancellation = state.cancellation.clone();
+                    let job_in_flight = state.job_in_flight.clone();
+                    tauri::async_runtime::spawn(async move {
+                        cancellation.cancel();
+                        while job_in_flight.load(std::sync::atomic::Ordering::SeqCst) {
+                            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                        }
+                        window.close().ok();
+                    });
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             list_devices,
             verify_device,
+            check_permissions,
+            check_realtime_scanning_slowdown_risk,
+            deny_device,
+            preselect_device,
+            clear_device_rule,
             create_bootable_usb,
             get_filesystem_info,
             validate_iso,
+            import_iso_to_library,
+            list_iso_library,
+            remove_from_iso_library,
+            check_iso_library_staleness,
+            burn_optical_media,
             cancel_operation,
-            get_version
+            abort_undo_window,
+            enqueue_job,
+            pending_job_count,
+            get_version,
+            read_manifest,
+            get_metrics_snapshot,
+            generate_support_bundle,
+            check_updates,
+            configure_failure_injection,
+            mount_iso_readonly,
+            unmount_iso,
+            force_unmount_iso,
+            extract_iso_file,
+            slipstream_windows_updates,
+            list_windows_languages,
+            select_windows_languages,
+            detect_iso_quirks,
+            recommended_boot_params,
+            master_iso,
+            reverify_stick,
+            quick_verify_stick,
+            list_usb_contents,
+            read_audit_history,
+            record_compatibility_report,
+            read_compatibility_reports,
+            preview_pipeline_commands,
+            repair_bootloader,
+            check_filesystem,
+            estimate_creation_duration,
+            generate_wipe_certificate,
+            list_orphaned_sessions,
+            list_orphaned_loop_devices,
+            clean_orphaned_sessions
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
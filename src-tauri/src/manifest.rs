@@ -0,0 +1,184 @@
+use crate::bootloader::{BootEntry, BootloaderConfigGenerator, MenuAppearance};
+use crate::error::{WowUsbError, Result};
+use crate::persistence_overlay;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest file persisted at the root of a managed multiboot
+/// stick so its entry order and titles survive between sessions.
+const MULTIBOOT_MANIFEST_FILENAME: &str = ".wowusb-multiboot.json";
+
+/// Per-ISO persistence for one managed boot entry. Each entry that opts in
+/// gets its own overlay file, so e.g. Ubuntu and Kali on the same stick
+/// keep independent persistent data instead of fighting over one overlay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EntryPersistence {
+    /// Name of the overlay file at the root of the payload partition; see
+    /// [`overlay_name_for`].
+    overlay_name: String,
+    overlay_size_mb: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManagedBootEntry {
+    title: String,
+    iso_path: String,
+    iso_size_bytes: u64,
+    #[serde(default)]
+    persistence: Option<EntryPersistence>,
+    /// [`crate::iso::IsoInfo::os_type`] for this entry, used to look up
+    /// [`crate::boot_params::BootParamsDatabase`] recommendations when
+    /// regenerating `grub.cfg`. `None` for entries added before this field
+    /// existed, or when the caller didn't have it on hand.
+    #[serde(default)]
+    os_type: Option<String>,
+}
+
+/// Derive a per-entry overlay filename from the ISO's own filename, so it's
+/// stable across reorders/renames and distinct from the single-ISO
+/// [`persistence_overlay::OVERLAY_FILENAME`].
+fn overlay_name_for(iso_path: &str) -> String {
+    let stem = Path::new(iso_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "entry".to_string());
+    format!("{}-{}", persistence_overlay::OVERLAY_FILENAME, stem)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct MultibootManifest {
+    entries: Vec<ManagedBootEntry>,
+}
+
+/// Manages the ordered list of boot entries on a multiboot stick, keeping
+/// the on-disk manifest and the generated `grub.cfg` in sync.
+pub struct MultibootManager {
+    stick_root: PathBuf,
+    generator: BootloaderConfigGenerator,
+}
+
+impl MultibootManager {
+    pub fn new(stick_root: impl Into<PathBuf>) -> Self {
+        Self {
+            stick_root: stick_root.into(),
+            generator: BootloaderConfigGenerator::new(),
+        }
+    }
+
+    fn manifest_path(&self) -> PathBuf {
+        self.stick_root.join(MULTIBOOT_MANIFEST_FILENAME)
+    }
+
+    fn load_manifest(&self) -> Result<MultibootManifest> {
+        let path = self.manifest_path();
+        if !path.exists() {
+            return Ok(MultibootManifest::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| WowUsbError::configuration(format!("Invalid multiboot manifest: {}", e)))
+    }
+
+    fn save_manifest(&self, manifest: &MultibootManifest) -> Result<()> {
+        let contents = serde_json::to_string_pretty(manifest)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize manifest: {}", e)))?;
+        std::fs::write(self.manifest_path(), contents)?;
+        Ok(())
+    }
+
+    /// List boot entry titles in their current on-stick order.
+    pub fn list_entries(&self) -> Result<Vec<String>> {
+        Ok(self.load_manifest()?.entries.into_iter().map(|e| e.title).collect())
+    }
+
+    /// Add a new boot entry for `iso_path` and regenerate `grub.cfg`. When
+    /// `overlay_size_mb` is `Some`, a dedicated persistence overlay file is
+    /// created for this entry alone, so it doesn't share (or fight over)
+    /// state with any other ISO on the same stick. Per-ISO persistence is
+    /// only supported as an overlay file — a multiboot stick's partition
+    /// table is fixed at creation time, so there's nowhere to put a
+    /// per-entry dedicated partition. `os_type`, when known, is recorded so
+    /// `grub.cfg` regeneration can look up
+    /// [`crate::boot_params::BootParamsDatabase`] recommendations for it.
+    pub async fn add_entry(&self, title: &str, iso_path: &str, iso_size_bytes: u64, overlay_size_mb: Option<u64>, os_type: Option<&str>) -> Result<String> {
+        let persistence = match overlay_size_mb {
+            Some(overlay_size_mb) => {
+                let overlay_name = overlay_name_for(iso_path);
+                let payload_root = self.stick_root.to_string_lossy().to_string();
+                persistence_overlay::create_overlay_file(&payload_root, &overlay_name, overlay_size_mb).await?;
+                Some(EntryPersistence { overlay_name, overlay_size_mb })
+            }
+            None => None,
+        };
+
+        let mut manifest = self.load_manifest()?;
+        manifest.entries.push(ManagedBootEntry {
+            title: title.to_string(),
+            iso_path: iso_path.to_string(),
+            iso_size_bytes,
+            persistence,
+            os_type: os_type.map(|s| s.to_string()),
+        });
+        self.save_manifest(&manifest)?;
+        self.regenerate_grub_cfg().await
+    }
+
+    /// Rename the entry at `index` and regenerate `grub.cfg`.
+    pub async fn rename_entry(&self, index: usize, new_title: &str) -> Result<String> {
+        let mut manifest = self.load_manifest()?;
+        let entry = manifest
+            .entries
+            .get_mut(index)
+            .ok_or_else(|| WowUsbError::validation(format!("No boot entry at index {}", index)))?;
+        entry.title = new_title.to_string();
+        self.save_manifest(&manifest)?;
+        self.regenerate_grub_cfg().await
+    }
+
+    /// Move the entry at `from` to position `to`, shifting the rest, and
+    /// regenerate `grub.cfg`.
+    pub async fn reorder_entry(&self, from: usize, to: usize) -> Result<String> {
+        let mut manifest = self.load_manifest()?;
+        if from >= manifest.entries.len() || to >= manifest.entries.len() {
+            return Err(WowUsbError::validation("Boot entry index out of range"));
+        }
+        let entry = manifest.entries.remove(from);
+        manifest.entries.insert(to, entry);
+        self.save_manifest(&manifest)?;
+        self.regenerate_grub_cfg().await
+    }
+
+    /// Regenerate `grub.cfg` from the current manifest order and titles.
+    pub async fn regenerate_grub_cfg(&self) -> Result<String> {
+        let manifest = self.load_manifest()?;
+        let mut cfg = self.generator.render_menu_header(&MenuAppearance::default());
+
+        for entry in &manifest.entries {
+            // `persistent-path` tells casper (and casper-derived live-boot
+            // stacks, e.g. Kali's) to use this entry's own overlay file
+            // instead of scanning for the first `casper-rw` it finds.
+            let mut args = Vec::new();
+            if let Some(p) = &entry.persistence {
+                args.push(format!("persistent persistent-path=/{}", p.overlay_name));
+            }
+            if let Some(os_type) = &entry.os_type {
+                args.extend(crate::boot_params::BootParamsDatabase::builtin().auto_apply_params(os_type));
+            }
+            let kernel_args = if args.is_empty() { None } else { Some(args.join(" ")) };
+            let boot_entry = BootEntry {
+                title: entry.title.clone(),
+                iso_path: entry.iso_path.clone(),
+                kernel_args,
+                iso_size_bytes: entry.iso_size_bytes,
+            };
+            let iso_root = Path::new(&entry.iso_path)
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            cfg.push_str(&self.generator.render_entry(&boot_entry, &iso_root).await?);
+        }
+
+        Ok(cfg)
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+
+/// Counters and histograms exposed on the optional HTTP server in daemon
+/// mode, so USB duplication stations can be monitored with standard
+/// Prometheus tooling instead of watching the GUI.
+pub struct MetricsRegistry {
+    jobs_run: AtomicU64,
+    jobs_failed: AtomicU64,
+    bytes_written: AtomicU64,
+    failures_by_code: Mutex<std::collections::HashMap<String, u64>>,
+    throughput_samples_bps: Mutex<Vec<u64>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self {
+            jobs_run: AtomicU64::new(0),
+            jobs_failed: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+            failures_by_code: Mutex::new(std::collections::HashMap::new()),
+            throughput_samples_bps: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn record_job_started(&self) {
+        self.jobs_run.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_job_failed(&self, error_code: &str) {
+        self.jobs_failed.fetch_add(1, Ordering::Relaxed);
+        let mut failures = self.failures_by_code.lock().unwrap();
+        *failures.entry(error_code.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn record_bytes_written(&self, bytes: u64) {
+        self.bytes_written.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn record_throughput_sample(&self, bytes_per_second: u64) {
+        self.throughput_samples_bps.lock().unwrap().push(bytes_per_second);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP wowusb_jobs_run_total Total jobs started\n");
+        out.push_str("# TYPE wowusb_jobs_run_total counter\n");
+        out.push_str(&format!("wowusb_jobs_run_total {}\n", self.jobs_run.load(Ordering::Relaxed)));
+
+        out.push_str("# HELP wowusb_jobs_failed_total Total jobs that failed\n");
+        out.push_str("# TYPE wowusb_jobs_failed_total counter\n");
+        out.push_str(&format!("wowusb_jobs_failed_total {}\n", self.jobs_failed.load(Ordering::Relaxed)));
+
+        out.push_str("# HELP wowusb_bytes_written_total Total bytes written across all jobs\n");
+        out.push_str("# TYPE wowusb_bytes_written_total counter\n");
+        out.push_str(&format!("wowusb_bytes_written_total {}\n", self.bytes_written.load(Ordering::Relaxed)));
+
+        out.push_str("# HELP wowusb_failures_by_code_total Failures broken down by error code\n");
+        out.push_str("# TYPE wowusb_failures_by_code_total counter\n");
+        for (code, count) in self.failures_by_code.lock().unwrap().iter() {
+            out.push_str(&format!("wowusb_failures_by_code_total{{code=\"{}\"}} {}\n", code, count));
+        }
+
+        out.push_str("# HELP wowusb_write_throughput_bytes_per_second Observed write throughput samples\n");
+        out.push_str("# TYPE wowusb_write_throughput_bytes_per_second histogram\n");
+        let samples = self.throughput_samples_bps.lock().unwrap();
+        let sum: u64 = samples.iter().sum();
+        out.push_str(&format!("wowusb_write_throughput_bytes_per_second_sum {}\n", sum));
+        out.push_str(&format!("wowusb_write_throughput_bytes_per_second_count {}\n", samples.len()));
+
+        out
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serve `/metrics` in the Prometheus text exposition format on `bind_addr`
+/// (e.g. `"127.0.0.1:9273"`), so a duplication station can be scraped by
+/// standard tooling instead of the GUI. Runs until the process exits; the
+/// caller is expected to `tokio::spawn` it.
+pub async fn serve_metrics(registry: Arc<MetricsRegistry>, bind_addr: &str) -> crate::error::Result<()> {
+    let listener = TcpListener::bind(bind_addr).await?;
+
+    loop {
+        let (mut socket, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                log::warn!("Metrics endpoint accept failed: {}", e);
+                continue;
+            }
+        };
+
+        let registry = registry.clone();
+        tokio::spawn(async move {
+            let body = registry.render();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        });
+    }
+}
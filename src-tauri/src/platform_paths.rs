@@ -0,0 +1,34 @@
+/// Platform-correct device/partition naming and external-tool argument
+/// formatting, so shared pipeline code (`DiskManager`) doesn't bake in
+/// Linux-isms like `/dev/sdb1` or `/tmp` that silently break the Windows
+/// and macOS builds.
+pub fn main_partition_name(device: &str) -> String {
+    partition_name(device, 1)
+}
+
+/// The `index`th partition (1-based) of `device`, in this platform's
+/// naming scheme.
+pub fn partition_name(device: &str, index: u32) -> String {
+    if cfg!(target_os = "windows") {
+        // Windows partitions are addressed by drive letter or via
+        // diskpart's "select partition", not by appending a number to the
+        // physical drive path.
+        return device.to_string();
+    }
+
+    // Linux/BSD: nvme/mmcblk devices need a `p` separator before the
+    // partition number (`nvme0n1p1`), plain `sdX`/`vdX` devices do not.
+    let last_char_numeric = device.chars().last().map(|c| c.is_numeric()).unwrap_or(false);
+    if last_char_numeric {
+        format!("{}p{}", device, index)
+    } else {
+        format!("{}{}", device, index)
+    }
+}
+
+/// Build the `7z` destination flag for `target_path`; `7z` expects
+/// `-o<path>` with no separator, which needs explicit concatenation rather
+/// than a bare format placeholder.
+pub fn sevenzip_output_flag(target_path: &str) -> String {
+    format!("-o{}", target_path)
+}
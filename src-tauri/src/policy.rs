@@ -0,0 +1,81 @@
+use crate::config::CreateConfig;
+use crate::error::{WowUsbError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Well-known locations an admin-provided policy file may be dropped,
+/// checked in order.
+#[cfg(target_os = "windows")]
+const POLICY_SEARCH_PATHS: &[&str] = &[r"C:\ProgramData\WowUSB\policy.json"];
+
+#[cfg(target_os = "macos")]
+const POLICY_SEARCH_PATHS: &[&str] = &["/Library/Application Support/WowUSB/policy.json"];
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const POLICY_SEARCH_PATHS: &[&str] = &["/etc/wowusb/policy.json"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DevicePolicy {
+    pub min_size_bytes: Option<u64>,
+    pub max_size_bytes: Option<u64>,
+    #[serde(default)]
+    pub allowed_filesystems: Vec<String>,
+    #[serde(default)]
+    pub mandatory_verification: bool,
+}
+
+impl DevicePolicy {
+    /// Load the first policy file found in the platform's well-known
+    /// locations, or `None` if no admin policy has been installed.
+    pub fn load() -> Result<Option<Self>> {
+        for candidate in POLICY_SEARCH_PATHS {
+            let path = PathBuf::from(candidate);
+            if path.exists() {
+                let contents = std::fs::read_to_string(&path)?;
+                let policy = serde_json::from_str(&contents)
+                    .map_err(|e| WowUsbError::configuration(format!("Invalid policy file {}: {}", candidate, e)))?;
+                return Ok(Some(policy));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Enforce this policy against a proposed creation config, independent
+    /// of what the GUI requested.
+    ///
+    /// There used to be a VID:PID allowlist check here, but no platform
+    /// backend ever produced a real vendor:product ID for a device — the
+    /// value was always `None`, so as soon as an admin set one it
+    /// rejected every device. Dropped from the schema until a platform
+    /// backend can actually populate it (see
+    /// [`crate::disk::PlatformDiskOps::device_serial`] for the analogous
+    /// per-OS pattern a real implementation would follow).
+    pub fn enforce(&self, config: &CreateConfig, device_size_bytes: u64) -> Result<()> {
+        if let Some(min) = self.min_size_bytes {
+            if device_size_bytes < min {
+                return Err(WowUsbError::validation("Target device is smaller than the policy minimum size"));
+            }
+        }
+
+        if let Some(max) = self.max_size_bytes {
+            if device_size_bytes > max {
+                return Err(WowUsbError::validation("Target device is larger than the policy maximum size"));
+            }
+        }
+
+        if !self.allowed_filesystems.is_empty()
+            && !self
+                .allowed_filesystems
+                .iter()
+                .any(|fs| fs.eq_ignore_ascii_case(&config.filesystem))
+        {
+            return Err(WowUsbError::validation(format!(
+                "Filesystem {} is not permitted by enterprise policy",
+                config.filesystem
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A record of what was on a device immediately before it was wiped, so
+/// users can later answer "what did I just erase?" and support can debug
+/// wrong-device incidents.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/bindings/")]
+pub struct PreWipeSnapshot {
+    pub device: String,
+    pub partition_labels: Vec<String>,
+    pub used_space_bytes: u64,
+    pub top_level_files: Vec<String>,
+    pub captured_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl PreWipeSnapshot {
+    /// Capture whatever is cheaply observable about `mountpoint` before it
+    /// gets wiped: partition labels already known to the caller, used
+    /// space, and a shallow file listing.
+    pub fn capture(device: &str, partition_labels: Vec<String>, mountpoint: Option<&str>) -> Result<Self> {
+        let (used_space_bytes, top_level_files) = match mountpoint {
+            Some(path) => (Self::used_space(path), Self::top_level_listing(path)),
+            None => (0, Vec::new()),
+        };
+
+        Ok(Self {
+            device: device.to_string(),
+            partition_labels,
+            used_space_bytes,
+            top_level_files,
+            captured_at: chrono::Utc::now(),
+        })
+    }
+
+    #[cfg(unix)]
+    fn used_space(mountpoint: &str) -> u64 {
+        nix::sys::statvfs::statvfs(mountpoint)
+            .map(|stat| (stat.blocks() - stat.blocks_free()) * stat.fragment_size())
+            .unwrap_or(0)
+    }
+
+    #[cfg(not(unix))]
+    fn used_space(_mountpoint: &str) -> u64 {
+        0
+    }
+
+    fn top_level_listing(mountpoint: &str) -> Vec<String> {
+        std::fs::read_dir(mountpoint)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
@@ -1,64 +1,316 @@
 use std::sync::Arc;
 use tokio::sync::{RwLock, broadcast};
 use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A step of the `create_bootable_usb` pipeline, serialized under a stable
+/// name so frontends and automation can key off it reliably (rather than
+/// matching on free-form message text) and so per-stage weights can drive
+/// an overall progress bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "../../src/bindings/", rename_all = "snake_case")]
+pub enum Stage {
+    Validate,
+    Partition,
+    Format,
+    Copy,
+    Bootloader,
+    Verify,
+    /// Waiting for the OS to flush cached writes to the stick and unmount
+    /// it, tracked separately from [`Stage::Cleanup`] because "stuck at
+    /// 100%" is otherwise the most common complaint: the copy step reports
+    /// done while gigabytes still sit in the page cache.
+    Flush,
+    Cleanup,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProgressUpdate {
     pub progress: u8,
     pub message: String,
-    pub stage: String,
+    pub stage: Stage,
+    /// Wall-clock time this update was emitted, for display/logging only —
+    /// an NTP adjustment mid-job can move it backward or jump it forward,
+    /// so ETAs and stage durations must be computed from `elapsed_ms`
+    /// instead.
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Monotonic milliseconds since this job's [`ProgressManager`] was
+    /// created, immune to clock-skew from NTP adjustments during a
+    /// multi-hour job. Use this, not `timestamp`, for ETA/duration math.
+    pub elapsed_ms: u64,
+}
+
+/// A single instantaneous throughput sample, emitted alongside regular
+/// progress updates so a frontend can plot a live speed graph.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpeedSample {
+    pub bytes_per_second: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl Stage {
+    /// All stages in pipeline order, for building a default weight table.
+    pub const ALL: [Stage; 8] = [
+        Stage::Validate,
+        Stage::Partition,
+        Stage::Format,
+        Stage::Copy,
+        Stage::Bootloader,
+        Stage::Verify,
+        Stage::Flush,
+        Stage::Cleanup,
+    ];
+}
+
+/// Estimated share of total wall-clock time each stage will take, so the
+/// single overall percentage moves smoothly instead of jumping 0→60→100.
+/// Weights need not sum to any particular value; they're normalized when
+/// converted to a percentage.
+#[derive(Clone, Debug)]
+pub struct StageWeights {
+    weights: std::collections::HashMap<Stage, f64>,
 }
 
+impl StageWeights {
+    /// Reasonable defaults when no size/speed probe is available: copying
+    /// dominates, formatting and bootloader installation are comparatively
+    /// quick.
+    pub fn default_weights() -> Self {
+        let mut weights = std::collections::HashMap::new();
+        weights.insert(Stage::Validate, 2.0);
+        weights.insert(Stage::Partition, 3.0);
+        weights.insert(Stage::Format, 5.0);
+        weights.insert(Stage::Copy, 75.0);
+        weights.insert(Stage::Bootloader, 5.0);
+        weights.insert(Stage::Verify, 8.0);
+        weights.insert(Stage::Flush, 5.0);
+        weights.insert(Stage::Cleanup, 2.0);
+        Self { weights }
+    }
+
+    /// Estimated seconds the Copy stage will take, from the ISO size and a
+    /// rough device write speed (bytes/sec, from a quick probe). Falls back
+    /// to an assumed 20 MB/s when no probe is available.
+    fn copy_seconds(iso_size_bytes: u64, device_write_bytes_per_sec: u64) -> f64 {
+        if device_write_bytes_per_sec > 0 {
+            iso_size_bytes as f64 / device_write_bytes_per_sec as f64
+        } else {
+            iso_size_bytes as f64 / (20.0 * 1024.0 * 1024.0) // assume 20 MB/s
+        }
+    }
+
+    /// Derive weights from an ISO size and a rough device write speed
+    /// (bytes/sec, from a quick probe), so a slow USB 2.0 stick and a large
+    /// ISO shift more of the bar into the copy stage instead of using the
+    /// fixed defaults.
+    pub fn estimate(iso_size_bytes: u64, device_write_bytes_per_sec: u64) -> Self {
+        let mut weights = Self::default_weights().weights;
+
+        // Fixed-cost stages stay constant; copy scales with estimated
+        // seconds so a bigger ISO or slower device visibly dominates the bar.
+        weights.insert(Stage::Copy, Self::copy_seconds(iso_size_bytes, device_write_bytes_per_sec).max(1.0));
+
+        Self { weights }
+    }
+
+    /// Rough total wall-clock estimate, in seconds, for the whole pipeline,
+    /// shown before a run starts so users can decide whether to kick off a
+    /// long Windows To Go build now or later. The dominant Copy stage is
+    /// sized from the ISO and a device write-speed probe; every other stage
+    /// gets a fixed overhead, since none of them scale meaningfully with
+    /// ISO size.
+    pub fn estimated_total_seconds(iso_size_bytes: u64, device_write_bytes_per_sec: u64) -> f64 {
+        const OTHER_STAGES_OVERHEAD_SECONDS: f64 = 30.0;
+        Self::copy_seconds(iso_size_bytes, device_write_bytes_per_sec) + OTHER_STAGES_OVERHEAD_SECONDS
+    }
+
+    fn total(&self) -> f64 {
+        self.weights.values().sum()
+    }
+
+    /// Overall percentage complete, given the current stage and how far
+    /// through that stage's own work we are (0.0-1.0).
+    pub fn overall_percent(&self, current_stage: Stage, stage_fraction: f64) -> u8 {
+        let stage_fraction = stage_fraction.clamp(0.0, 1.0);
+        let total = self.total();
+        if total <= 0.0 {
+            return 0;
+        }
+
+        let mut completed: f64 = 0.0;
+        for stage in Stage::ALL {
+            let weight = *self.weights.get(&stage).unwrap_or(&0.0);
+            if stage == current_stage {
+                completed += weight * stage_fraction;
+                break;
+            }
+            completed += weight;
+        }
+
+        ((completed / total) * 100.0).round().clamp(0.0, 100.0) as u8
+    }
+}
+
+/// Pure event hub: broadcasts progress and speed updates to subscribers.
+/// Cancellation is a separate concern — see [`crate::cancellation::CancellationToken`]
+/// — so a job's progress channel and its cancellation state can be
+/// recombined independently (e.g. one token shared by several jobs' progress
+/// managers, or a paused job that keeps broadcasting without being cancelled).
 pub struct ProgressManager {
     sender: broadcast::Sender<ProgressUpdate>,
-    cancelled: Arc<RwLock<bool>>,
+    speed_sender: broadcast::Sender<SpeedSample>,
+    bytes_written_since_sample: Arc<RwLock<u64>>,
+    stage_weights: RwLock<StageWeights>,
+    /// Reference point for [`ProgressUpdate::elapsed_ms`] — a monotonic
+    /// clock, so it can't be skewed backward or forward by an NTP
+    /// adjustment during a multi-hour job the way `chrono::Utc::now()` can.
+    started_at: std::time::Instant,
 }
 
 impl ProgressManager {
     pub fn new() -> Self {
         let (sender, _) = broadcast::channel(100);
+        let (speed_sender, _) = broadcast::channel(100);
 
         Self {
             sender,
-            cancelled: Arc::new(RwLock::new(false)),
+            speed_sender,
+            bytes_written_since_sample: Arc::new(RwLock::new(0)),
+            stage_weights: RwLock::new(StageWeights::default_weights()),
+            started_at: std::time::Instant::now(),
         }
     }
 
-    pub async fn update(&self, progress: u8, message: String, stage: String) -> Result<(), broadcast::error::SendError<ProgressUpdate>> {
+    /// Replace the stage weights used by [`ProgressManager::update_weighted`],
+    /// typically with [`StageWeights::estimate`] once the ISO size and a
+    /// device speed probe are known for this job.
+    pub async fn set_stage_weights(&self, weights: StageWeights) {
+        *self.stage_weights.write().await = weights;
+    }
+
+    pub async fn update(&self, progress: u8, message: String, stage: Stage) -> Result<(), broadcast::error::SendError<ProgressUpdate>> {
         let update = ProgressUpdate {
             progress,
             message,
             stage,
             timestamp: chrono::Utc::now(),
+            elapsed_ms: self.started_at.elapsed().as_millis() as u64,
         };
 
         self.sender.send(update)
     }
 
+    /// Compute the overall percentage from the configured stage weights and
+    /// broadcast it, instead of requiring callers to guess a raw 0-100
+    /// value for each stage themselves.
+    pub async fn update_weighted(&self, stage: Stage, stage_fraction: f64, message: String) -> Result<(), broadcast::error::SendError<ProgressUpdate>> {
+        let progress = self.stage_weights.read().await.overall_percent(stage, stage_fraction);
+        self.update(progress, message, stage).await
+    }
+
+    /// Record that `bytes` have been written since the last sample; call
+    /// this from the copy loop as data is transferred.
+    pub async fn record_bytes_written(&self, bytes: u64) {
+        let mut total = self.bytes_written_since_sample.write().await;
+        *total += bytes;
+    }
+
+    /// Flush the accumulated byte count as a speed sample over `elapsed`
+    /// and broadcast it to subscribers of the speed-graph channel.
+    pub async fn sample_speed(&self, elapsed: std::time::Duration) {
+        let mut total = self.bytes_written_since_sample.write().await;
+        let bytes_per_second = if elapsed.as_secs_f64() > 0.0 {
+            (*total as f64 / elapsed.as_secs_f64()) as u64
+        } else {
+            0
+        };
+        *total = 0;
+        drop(total);
+
+        let _ = self.speed_sender.send(SpeedSample {
+            bytes_per_second,
+            timestamp: chrono::Utc::now(),
+        });
+    }
+
+    pub fn subscribe_speed(&self) -> broadcast::Receiver<SpeedSample> {
+        self.speed_sender.subscribe()
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<ProgressUpdate> {
         self.sender.subscribe()
     }
 
-    pub async fn cancel(&self) -> Result<(), crate::error::WowUsbError> {
-        let mut cancelled = self.cancelled.write().await;
-        *cancelled = true;
-        Ok(())
+}
+
+impl Default for ProgressManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Routes progress and speed updates to a dedicated `ProgressManager` per
+/// job, so batch/parallel mode can report several concurrent jobs to the
+/// frontend without their events interleaving on one channel.
+pub struct MultiJobProgressManager {
+    jobs: RwLock<std::collections::HashMap<String, Arc<ProgressManager>>>,
+}
+
+impl MultiJobProgressManager {
+    pub fn new() -> Self {
+        Self {
+            jobs: RwLock::new(std::collections::HashMap::new()),
+        }
+    }
+
+    /// Get or create the `ProgressManager` for `job_id`.
+    pub async fn job(&self, job_id: &str) -> Arc<ProgressManager> {
+        if let Some(existing) = self.jobs.read().await.get(job_id) {
+            return existing.clone();
+        }
+
+        let mut jobs = self.jobs.write().await;
+        jobs.entry(job_id.to_string())
+            .or_insert_with(|| Arc::new(ProgressManager::new()))
+            .clone()
     }
 
-    pub async fn is_cancelled(&self) -> bool {
-        let cancelled = self.cancelled.read().await;
-        *cancelled
+    /// Drop the channel for a completed job so it stops holding buffered
+    /// history.
+    pub async fn remove_job(&self, job_id: &str) {
+        self.jobs.write().await.remove(job_id);
     }
 
-    pub async fn reset(&self) {
-        let mut cancelled = self.cancelled.write().await;
-        *cancelled = false;
+    pub async fn active_job_ids(&self) -> Vec<String> {
+        self.jobs.read().await.keys().cloned().collect()
     }
 }
 
-impl Default for ProgressManager {
+impl Default for MultiJobProgressManager {
     fn default() -> Self {
         Self::new()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn elapsed_ms_advances_independently_of_wall_clock_timestamp() {
+        let manager = ProgressManager::new();
+        let mut receiver = manager.subscribe();
+
+        manager.update(0, "starting".to_string(), Stage::Validate).await.unwrap();
+        let first = receiver.recv().await.unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        manager.update(50, "halfway".to_string(), Stage::Copy).await.unwrap();
+        let second = receiver.recv().await.unwrap();
+
+        assert!(second.elapsed_ms > first.elapsed_ms, "elapsed_ms should advance monotonically");
+        assert!(second.timestamp >= first.timestamp);
+    }
 }
\ No newline at end of file
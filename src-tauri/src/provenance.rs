@@ -0,0 +1,45 @@
+use crate::error::{WowUsbError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the provenance manifest written to the root of every stick
+/// WowUSB creates, so a later WowUSB instance can recognize its own work.
+pub const PROVENANCE_MANIFEST_FILENAME: &str = "wowusb.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceManifest {
+    pub tool_version: String,
+    pub iso_name: String,
+    pub iso_sha256: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub layout: String,
+    pub filesystem: String,
+    pub target_os: String,
+    /// Medium-relative paths of any files injected/overridden after
+    /// extraction, so a later repair or re-verification knows this stick
+    /// deliberately deviates from the source ISO at these paths.
+    #[serde(default)]
+    pub injected_files: Vec<String>,
+}
+
+impl ProvenanceManifest {
+    pub fn write_to(&self, stick_root: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = stick_root.as_ref().join(PROVENANCE_MANIFEST_FILENAME);
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize provenance manifest: {}", e)))?;
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    pub fn read_from(stick_root: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = stick_root.as_ref().join(PROVENANCE_MANIFEST_FILENAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        let manifest = serde_json::from_str(&contents)
+            .map_err(|e| WowUsbError::configuration(format!("Invalid provenance manifest: {}", e)))?;
+        Ok(Some(manifest))
+    }
+}
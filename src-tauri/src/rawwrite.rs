@@ -0,0 +1,201 @@
+use crate::error::{WowUsbError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Chunk size for the sector-by-sector copy — matches the block size the
+/// copy-engine benchmarks (`benches/copy_engine.rs`) found gave the best
+/// throughput for large transfers, while staying small enough that
+/// progress updates and cancellation checks land at a reasonable cadence.
+const RAW_COPY_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Stream `source_path` onto `device` sector-by-sector, untouched — for
+/// images that are already complete, bootable disk images and must not be
+/// partitioned or have their filesystem extracted. See
+/// [`crate::config::WriteMode::Raw`].
+///
+/// Reports progress through `progress` the same way the extract-and-copy
+/// path does (see [`crate::progress::ProgressManager::record_bytes_written`]),
+/// and checks `cancellation` between chunks so a cancelled job stops
+/// mid-copy instead of running to completion.
+pub async fn write_image(
+    source_path: &str,
+    device: &str,
+    progress: Option<&std::sync::Arc<tokio::sync::RwLock<crate::progress::ProgressManager>>>,
+    cancellation: &crate::cancellation::CancellationToken,
+    throttle: Option<&crate::scheduler::ThrottleSettings>,
+) -> Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    if let Some(throttle) = throttle {
+        throttle.apply_low_priority_io(std::process::id())?;
+    }
+
+    let total_bytes = tokio::fs::metadata(source_path).await?.len();
+    let mut source = tokio::fs::File::open(source_path).await?;
+    let mut destination = tokio::fs::OpenOptions::new().write(true).open(device).await?;
+
+    let mut buffer = vec![0u8; RAW_COPY_CHUNK_BYTES];
+    let mut written: u64 = 0;
+
+    loop {
+        if cancellation.is_cancelled() {
+            return Err(WowUsbError::Cancelled);
+        }
+
+        let chunk_started = std::time::Instant::now();
+        let read = source.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+
+        destination.write_all(&buffer[..read]).await?;
+        written += read as u64;
+
+        if let Some(progress) = progress {
+            let manager = progress.read().await;
+            manager.record_bytes_written(read as u64).await;
+            let fraction = if total_bytes > 0 { written as f64 / total_bytes as f64 } else { 1.0 };
+            let message = format!("Writing raw image ({} MB / {} MB)", written / (1024 * 1024), total_bytes / (1024 * 1024));
+            let _ = manager.update_weighted(crate::progress::Stage::Copy, fraction, message).await;
+        }
+
+        if let Some(throttle) = throttle {
+            let delay = throttle.delay_for_chunk(read as u64, chunk_started.elapsed());
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+
+    destination.flush().await?;
+    destination.sync_all().await?;
+
+    Ok(())
+}
+
+/// A small integrity marker recorded after a raw (dd-style) image write,
+/// so a later `verify_usb` can validate the stick without needing the
+/// original image file present.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteTrailer {
+    pub image_sha256: String,
+    pub image_length_bytes: u64,
+    pub written_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl WriteTrailer {
+    /// Hash `image_path` and build the trailer to record after writing it
+    /// to a device. See [`crate::hashing::sha256_file`] for how the hash
+    /// itself is computed.
+    pub async fn compute(image_path: &str) -> Result<Self> {
+        let image_sha256 = crate::hashing::sha256_file(image_path).await?;
+        let image_length_bytes = std::fs::metadata(image_path)?.len();
+        Ok(Self {
+            image_sha256,
+            image_length_bytes,
+            written_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Check that `device` still holds the bytes this trailer was recorded
+    /// for. Only the first `image_length_bytes` of `device` are hashed —
+    /// a device is typically much larger than the image written to it, and
+    /// what follows the image was never ours to verify.
+    pub async fn verify_against(&self, device: &str) -> Result<bool> {
+        let actual = crate::hashing::sha256_prefix(device, self.image_length_bytes).await?;
+        Ok(actual.eq_ignore_ascii_case(&self.image_sha256))
+    }
+
+    /// Persist the trailer as a JSON sidecar next to the device path, since
+    /// writing binary metadata past the end of a raw device write isn't
+    /// portable across filesystems the recipient partition might use.
+    pub fn write_sidecar(&self, device: &str) -> Result<()> {
+        let sidecar_path = Self::sidecar_path(device);
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize write trailer: {}", e)))?;
+        std::fs::write(sidecar_path, contents)?;
+        Ok(())
+    }
+
+    pub fn read_sidecar(device: &str) -> Result<Option<Self>> {
+        let sidecar_path = Self::sidecar_path(device);
+        if !sidecar_path.exists() {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read_to_string(&sidecar_path)?;
+        let trailer = serde_json::from_str(&contents)
+            .map_err(|e| WowUsbError::configuration(format!("Invalid write trailer: {}", e)))?;
+        Ok(Some(trailer))
+    }
+
+    fn sidecar_path(device: &str) -> std::path::PathBuf {
+        let sanitized: String = device.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect();
+        crate::staging::StagingDirectory::resolve(None)
+            .job_dir("trailers")
+            .join(format!("{}.json", sanitized))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wowusb_rawwrite_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn writes_the_whole_image_across_several_chunks() {
+        let dir = temp_dir("copy");
+        let source = dir.join("image.img");
+        let device = dir.join("device");
+        let payload = vec![0xABu8; RAW_COPY_CHUNK_BYTES * 2 + 37];
+        std::fs::write(&source, &payload).unwrap();
+        std::fs::write(&device, vec![0u8; payload.len()]).unwrap();
+
+        write_image(source.to_str().unwrap(), device.to_str().unwrap(), None, &crate::cancellation::CancellationToken::new(), None)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&device).unwrap(), payload);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn a_pre_cancelled_token_stops_before_writing_anything() {
+        let dir = temp_dir("cancel");
+        let source = dir.join("image.img");
+        let device = dir.join("device");
+        std::fs::write(&source, vec![0xCDu8; RAW_COPY_CHUNK_BYTES]).unwrap();
+        std::fs::write(&device, vec![0u8; RAW_COPY_CHUNK_BYTES]).unwrap();
+
+        let cancellation = crate::cancellation::CancellationToken::new();
+        cancellation.cancel();
+
+        let result = write_image(source.to_str().unwrap(), device.to_str().unwrap(), None, &cancellation, None).await;
+        assert!(matches!(result, Err(WowUsbError::Cancelled)));
+        assert_eq!(std::fs::read(&device).unwrap(), vec![0u8; RAW_COPY_CHUNK_BYTES]);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn write_trailer_round_trips_through_a_sidecar() {
+        let dir = temp_dir("trailer");
+        let source = dir.join("image.img");
+        std::fs::write(&source, vec![0x11u8; 4096]).unwrap();
+        let device_path = dir.join("device").to_string_lossy().to_string();
+
+        let trailer = WriteTrailer::compute(source.to_str().unwrap()).await.unwrap();
+        trailer.write_sidecar(&device_path).unwrap();
+
+        let loaded = WriteTrailer::read_sidecar(&device_path).unwrap().unwrap();
+        assert_eq!(loaded.image_sha256, trailer.image_sha256);
+        assert_eq!(loaded.image_length_bytes, 4096);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,91 @@
+use crate::progress::Stage;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Wall-clock duration, bytes moved, and average throughput for a single
+/// pipeline stage, so users (and we) can see whether formatting, copying,
+/// or verification dominates on their hardware.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/bindings/")]
+pub struct StageTiming {
+    pub stage: Stage,
+    pub duration_ms: u64,
+    pub bytes: u64,
+    pub throughput_bytes_per_sec: f64,
+}
+
+/// Summary of a completed (or failed) `create_bootable_usb` run, suitable
+/// for display in the UI and for attaching to a support bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "../../src/bindings/")]
+pub struct CreationReport {
+    pub target_device: String,
+    pub iso_path: String,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+    pub stages: Vec<StageTiming>,
+    pub success: bool,
+    /// Host OS, kernel, and tool versions at the time of this run, so a
+    /// boot-failure report carries the details needed to reproduce it
+    /// without a follow-up question.
+    pub environment: crate::env_snapshot::EnvironmentSnapshot,
+    /// What was on the device immediately before it was wiped, if a
+    /// snapshot could be captured. See [`crate::prewipe::PreWipeSnapshot`].
+    #[serde(default)]
+    pub pre_wipe_snapshot: Option<crate::prewipe::PreWipeSnapshot>,
+}
+
+/// Accumulates [`StageTiming`] entries as the pipeline runs. Each stage is
+/// timed independently via [`ReportBuilder::time_stage`], which also
+/// records the bytes moved during that stage.
+pub struct ReportBuilder {
+    target_device: String,
+    iso_path: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    stages: Vec<StageTiming>,
+    pre_wipe_snapshot: Option<crate::prewipe::PreWipeSnapshot>,
+}
+
+impl ReportBuilder {
+    pub fn new(target_device: impl Into<String>, iso_path: impl Into<String>) -> Self {
+        Self {
+            target_device: target_device.into(),
+            iso_path: iso_path.into(),
+            started_at: chrono::Utc::now(),
+            stages: Vec::new(),
+            pre_wipe_snapshot: None,
+        }
+    }
+
+    /// Attach a snapshot of what was on the device before it was wiped, so
+    /// [`Self::finish`] carries it into the final report.
+    pub fn set_pre_wipe_snapshot(&mut self, snapshot: crate::prewipe::PreWipeSnapshot) {
+        self.pre_wipe_snapshot = Some(snapshot);
+    }
+
+    /// Record a completed stage's duration and bytes moved.
+    pub fn record_stage(&mut self, stage: Stage, duration: std::time::Duration, bytes: u64) {
+        let seconds = duration.as_secs_f64();
+        let throughput_bytes_per_sec = if seconds > 0.0 { bytes as f64 / seconds } else { 0.0 };
+
+        self.stages.push(StageTiming {
+            stage,
+            duration_ms: duration.as_millis() as u64,
+            bytes,
+            throughput_bytes_per_sec,
+        });
+    }
+
+    pub fn finish(self, success: bool) -> CreationReport {
+        CreationReport {
+            target_device: self.target_device,
+            iso_path: self.iso_path,
+            started_at: self.started_at,
+            finished_at: chrono::Utc::now(),
+            stages: self.stages,
+            success,
+            environment: crate::env_snapshot::EnvironmentSnapshot::collect(),
+            pre_wipe_snapshot: self.pre_wipe_snapshot,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::disk::PlatformDiskOps;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What a leftover `wowusb_*` staging entry looks like, so the cleanup step
+/// knows whether it needs to unmount before removing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanKind {
+    /// Still mounted — a session that crashed mid-copy without unmounting.
+    MountedDirectory,
+    /// A plain leftover temp directory (extraction staging, WIM work) with
+    /// nothing mounted on it.
+    TempDir,
+}
+
+/// A `wowusb_*` entry under the staging root that outlived the session that
+/// created it, most likely because that session crashed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedSession {
+    pub path: String,
+    pub kind: OrphanKind,
+    pub size_bytes: u64,
+}
+
+/// Scan `staging_root` for leftover `wowusb_*` entries from a previous,
+/// presumably crashed, session. Read-only: callers decide whether and how
+/// to act on what's found, e.g. presenting it to the user before deleting
+/// anything.
+pub fn scan(staging_root: &Path) -> Result<Vec<OrphanedSession>> {
+    let mut orphans = Vec::new();
+
+    let entries = match std::fs::read_dir(staging_root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(orphans),
+        Err(e) => return Err(e.into()),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with("wowusb_") {
+            continue;
+        }
+
+        let path = entry.path();
+        let kind = if is_mountpoint(&path) { OrphanKind::MountedDirectory } else { OrphanKind::TempDir };
+        let size_bytes = directory_size(&path);
+
+        orphans.push(OrphanedSession { path: path.to_string_lossy().to_string(), kind, size_bytes });
+    }
+
+    Ok(orphans)
+}
+
+/// Unmount (if needed) and remove every entry in `sessions`. Best-effort:
+/// one failure doesn't stop the rest, and every path attempted is returned
+/// with its outcome so the caller can report partial success honestly.
+pub async fn clean_up(sessions: &[OrphanedSession], ops: &dyn PlatformDiskOps) -> Vec<(String, Result<()>)> {
+    let mut results = Vec::new();
+
+    for session in sessions {
+        let outcome = async {
+            if session.kind == OrphanKind::MountedDirectory {
+                ops.unmount_partition(&session.path).await?;
+            }
+            std::fs::remove_dir_all(&session.path)?;
+            Ok(())
+        }
+        .await;
+
+        results.push((session.path.clone(), outcome));
+    }
+
+    results
+}
+
+#[cfg(unix)]
+fn is_mountpoint(path: &Path) -> bool {
+    let (Ok(path_meta), Ok(parent_meta)) = (
+        std::fs::metadata(path),
+        path.parent().map(std::fs::metadata).unwrap_or_else(|| std::fs::metadata(path)),
+    ) else {
+        return false;
+    };
+
+    use std::os::unix::fs::MetadataExt;
+    path_meta.dev() != parent_meta.dev()
+}
+
+#[cfg(not(unix))]
+fn is_mountpoint(_path: &Path) -> bool {
+    false
+}
+
+fn directory_size(path: &Path) -> u64 {
+    let mut total = 0u64;
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.is_dir() {
+                    total += directory_size(&entry.path());
+                } else {
+                    total += metadata.len();
+                }
+            }
+        }
+    }
+    total
+}
+
+/// List loop (Linux) devices currently backed by a file under
+/// `staging_root` — left attached by a session that crashed before
+/// unmounting and releasing them. Read-only; see
+/// [`release_orphaned_loop_devices`] to actually detach them.
+#[cfg(target_os = "linux")]
+pub fn find_orphaned_loop_devices(staging_root: &Path) -> Vec<String> {
+    let Ok(output) = std::process::Command::new("losetup").args(["-a"]).output() else {
+        return Vec::new();
+    };
+
+    let staging_prefix = staging_root.to_string_lossy().to_string();
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (device, rest) = line.split_once(':')?;
+            rest.contains(&staging_prefix).then_some(device.to_string())
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn find_orphaned_loop_devices(_staging_root: &Path) -> Vec<String> {
+    Vec::new()
+}
+
+/// Detach loop devices previously found by [`find_orphaned_loop_devices`].
+/// Best-effort and Linux-specific: elsewhere (macOS `hdiutil`, Windows
+/// `Mount-DiskImage`) the OS already tracks and tears down these
+/// attachments per-process, so there's nothing to reconcile.
+#[cfg(target_os = "linux")]
+pub fn release_orphaned_loop_devices(devices: &[String]) -> Vec<String> {
+    devices
+        .iter()
+        .filter(|device| {
+            std::process::Command::new("losetup").args(["-d", device]).status().map(|s| s.success()).unwrap_or(false)
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn release_orphaned_loop_devices(_devices: &[String]) -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_finds_wowusb_prefixed_entries_only() {
+        let root = std::env::temp_dir().join(format!("wowusb_recovery_test_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("wowusb_mount_1234")).unwrap();
+        std::fs::create_dir_all(root.join("unrelated_dir")).unwrap();
+        std::fs::write(root.join("wowusb_mount_1234").join("leftover.txt"), "abc").unwrap();
+
+        let orphans = scan(&root).unwrap();
+        assert_eq!(orphans.len(), 1);
+        assert!(orphans[0].path.ends_with("wowusb_mount_1234"));
+        assert_eq!(orphans[0].kind, OrphanKind::TempDir);
+        assert_eq!(orphans[0].size_bytes, 3);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn scan_missing_root_returns_empty() {
+        let root = std::env::temp_dir().join("wowusb_recovery_test_missing_root");
+        std::fs::remove_dir_all(&root).ok();
+        assert!(scan(&root).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::disk::{Device, PartitionConfig, PlatformDiskOps};
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::Duration;
+
+/// Scripted failure injection for exercising the frontend's error and
+/// retry flows deterministically, without needing real hardware or a real
+/// broken ISO to reproduce a given failure mode.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FailureInjectionPlan {
+    /// Name of the `PlatformDiskOps` method to fail at, e.g. `"format_partition"`.
+    pub fail_at_stage: Option<String>,
+    /// Error code to fail with, matching `WowUsbError::error_code()`. Falls
+    /// back to `"device_operation"` if unset or unrecognized.
+    pub error_code: Option<String>,
+    /// How long to stall before failing (or succeeding, if `fail_at_stage`
+    /// doesn't match), to exercise stall/timeout handling in the UI.
+    pub stall_seconds: Option<u64>,
+}
+
+impl FailureInjectionPlan {
+    fn error_for(&self) -> WowUsbError {
+        match self.error_code.as_deref() {
+            Some("validation") => WowUsbError::validation("Simulated validation failure"),
+            Some("filesystem") => WowUsbError::filesystem("Simulated filesystem failure"),
+            Some("platform") => WowUsbError::platform("Simulated platform failure"),
+            Some("iso_processing") => WowUsbError::iso_processing("Simulated ISO processing failure"),
+            Some("configuration") => WowUsbError::configuration("Simulated configuration failure"),
+            Some("cancelled") => WowUsbError::Cancelled,
+            Some("not_implemented") => WowUsbError::not_implemented("Simulated not-implemented failure"),
+            _ => WowUsbError::device_operation("Simulated device operation failure"),
+        }
+    }
+}
+
+/// In-memory `PlatformDiskOps` backend for frontend development and demos:
+/// no real device or ISO is touched, and behavior at each stage is driven
+/// entirely by the shared [`FailureInjectionPlan`].
+pub struct SimulatedDiskOps {
+    plan: Arc<RwLock<FailureInjectionPlan>>,
+}
+
+impl SimulatedDiskOps {
+    pub fn new(plan: Arc<RwLock<FailureInjectionPlan>>) -> Self {
+        Self { plan }
+    }
+
+    async fn maybe_inject(&self, stage: &str) -> Result<()> {
+        let plan = self.plan.read().await.clone();
+
+        if let Some(seconds) = plan.stall_seconds {
+            if plan.fail_at_stage.as_deref() == Some(stage) {
+                tokio::time::sleep(Duration::from_secs(seconds)).await;
+            }
+        }
+
+        if plan.fail_at_stage.as_deref() == Some(stage) {
+            return Err(plan.error_for());
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl PlatformDiskOps for SimulatedDiskOps {
+    async fn list_devices(&self) -> Result<Vec<Device>> {
+        self.maybe_inject("list_devices").await?;
+        Ok(vec![Device {
+            name: "simdisk0".to_string(),
+            size: "8000000000".to_string(),
+            model: "Simulated USB Drive".to_string(),
+            filesystem: Some("fat32".to_string()),
+            mountpoint: None,
+            is_removable: true,
+            is_usb: true,
+            bus_type: Some("usb".to_string()),
+            label: None,
+            used_space_bytes: None,
+            preselected: false,
+        }])
+    }
+
+    async fn verify_device(&self, _device: &str) -> Result<bool> {
+        self.maybe_inject("verify_device").await?;
+        Ok(true)
+    }
+
+    async fn check_permissions(&self, _device: &str) -> Result<crate::disk::PermissionCheck> {
+        self.maybe_inject("check_permissions").await?;
+        Ok(crate::disk::PermissionCheck::ok())
+    }
+
+    async fn create_partitions(&self, _device: &str, _config: &[PartitionConfig]) -> Result<()> {
+        self.maybe_inject("create_partitions").await
+    }
+
+    async fn format_partition(&self, _partition: &str, _filesystem: &str, _label: &str) -> Result<()> {
+        self.maybe_inject("format_partition").await
+    }
+
+    async fn mount_partition(&self, _partition: &str, mountpoint: &str) -> Result<String> {
+        self.maybe_inject("mount_partition").await?;
+        Ok(mountpoint.to_string())
+    }
+
+    async fn unmount_partition(&self, _mountpoint: &str) -> Result<()> {
+        self.maybe_inject("unmount_partition").await
+    }
+
+    async fn wipe_device(&self, _device: &str) -> Result<()> {
+        self.maybe_inject("wipe_device").await
+    }
+
+    async fn validate_iso(&self, _iso_path: &str) -> Result<bool> {
+        self.maybe_inject("validate_iso").await?;
+        Ok(true)
+    }
+
+    async fn extract_iso(&self, _iso_path: &str, _target_path: &str, cancellation: &crate::cancellation::CancellationToken) -> Result<()> {
+        if cancellation.is_cancelled() {
+            return Err(WowUsbError::Cancelled);
+        }
+        self.maybe_inject("extract_iso").await
+    }
+
+    async fn install_bootloader(&self, _device: &str, _bootloader_type: &str, _boot_mountpoint: &str, _efi_mountpoint: &str) -> Result<()> {
+        self.maybe_inject("install_bootloader").await
+    }
+
+    async fn check_filesystem(&self, _partition: &str, _filesystem: &str) -> Result<crate::disk::FsckReport> {
+        self.maybe_inject("check_filesystem").await?;
+        Ok(crate::disk::FsckReport { clean: true, repaired: false, details: String::new() })
+    }
+
+    async fn probe_write_speed(&self, _device: &str) -> Result<u64> {
+        self.maybe_inject("probe_write_speed").await?;
+        Ok(20 * 1024 * 1024)
+    }
+
+    async fn device_serial(&self, _device: &str) -> Result<Option<String>> {
+        self.maybe_inject("device_serial").await?;
+        Ok(Some("SIM-0001".to_string()))
+    }
+
+    async fn extract_iso_file(&self, _iso_path: &str, _internal_path: &str, _dest: &str) -> Result<()> {
+        self.maybe_inject("extract_iso_file").await
+    }
+
+    async fn mount_iso_readonly(&self, _iso_path: &str, mountpoint: &str) -> Result<String> {
+        self.maybe_inject("mount_iso_readonly").await?;
+        Ok(mountpoint.to_string())
+    }
+
+    async fn unmount_iso(&self, _mountpoint: &str) -> Result<()> {
+        self.maybe_inject("unmount_iso").await
+    }
+}
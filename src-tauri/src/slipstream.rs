@@ -0,0 +1,109 @@
+use crate::error::{Result, WowUsbError};
+use tokio::process::Command as AsyncCommand;
+
+/// A Windows update package (`.msu` or `.cab`) to slipstream into an
+/// install image before it's copied onto the stick.
+#[derive(Debug, Clone)]
+pub struct UpdatePackage {
+    pub path: String,
+}
+
+/// Applies cumulative update packages to a Windows image, using DISM on
+/// Windows hosts and falling back to `wimlib-imagex` (available on
+/// Linux/macOS) where DISM isn't present.
+pub struct UpdateSlipstreamer;
+
+impl UpdateSlipstreamer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Apply `packages` in order to the image at `wim_path` (1-based
+    /// `image_index`), calling `on_progress(applied, total, package_path)`
+    /// after each package so the caller can drive a progress bar.
+    pub async fn apply_updates(
+        &self,
+        wim_path: &str,
+        image_index: u32,
+        packages: &[UpdatePackage],
+        on_progress: impl Fn(usize, usize, &str),
+    ) -> Result<()> {
+        let total = packages.len();
+
+        for (i, package) in packages.iter().enumerate() {
+            self.apply_one(wim_path, image_index, package).await?;
+            on_progress(i + 1, total, &package.path);
+        }
+
+        Ok(())
+    }
+
+    async fn apply_one(&self, wim_path: &str, image_index: u32, package: &UpdatePackage) -> Result<()> {
+        if cfg!(target_os = "windows") {
+            self.apply_via_dism(wim_path, image_index, package).await
+        } else {
+            self.apply_via_wimlib(wim_path, image_index, package).await
+        }
+    }
+
+    async fn apply_via_dism(&self, wim_path: &str, image_index: u32, package: &UpdatePackage) -> Result<()> {
+        let output = AsyncCommand::new("dism")
+            .args(&[
+                "/image:mounted",
+                &format!("/wimfile:{}", wim_path),
+                &format!("/index:{}", image_index),
+                "/add-package",
+                &format!("/packagepath:{}", package.path),
+            ])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(format!(
+                "DISM failed to apply {}: {}",
+                package.path,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn apply_via_wimlib(&self, wim_path: &str, image_index: u32, package: &UpdatePackage) -> Result<()> {
+        let output = AsyncCommand::new("wimlib-imagex")
+            .args(&[
+                "update",
+                wim_path,
+                &image_index.to_string(),
+                "--command",
+                &format!("add {} /", package.path),
+            ])
+            .output()
+            .await;
+
+        let output = match output {
+            Ok(output) => output,
+            Err(_) => {
+                return Err(WowUsbError::not_implemented(
+                    "Update slipstreaming requires either DISM (Windows) or wimlib-imagex on PATH",
+                ));
+            }
+        };
+
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(format!(
+                "wimlib-imagex failed to apply {}: {}",
+                package.path,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for UpdateSlipstreamer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,107 @@
+use crate::error::{Result, WowUsbError};
+use std::collections::HashMap;
+use std::io::Read;
+
+/// Parse an `/etc/os-release`-style file (`KEY=value`, optionally
+/// double-quoted) into a lookup of its fields.
+pub fn parse_os_release(contents: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim().trim_matches('"').to_string();
+            fields.insert(key.trim().to_string(), value);
+        }
+    }
+
+    fields
+}
+
+/// Guess the installed desktop environment from a Debian/Ubuntu-style
+/// `casper/filesystem.manifest` package list (one `package\tversion` per
+/// line), by checking for the metapackage each desktop flavor installs.
+pub fn desktop_environment_from_manifest(manifest: &str) -> Option<String> {
+    const DESKTOPS: &[(&str, &str)] = &[
+        ("ubuntu-desktop", "GNOME (Ubuntu)"),
+        ("kubuntu-desktop", "KDE Plasma (Kubuntu)"),
+        ("xubuntu-desktop", "Xfce (Xubuntu)"),
+        ("lubuntu-desktop", "LXQt (Lubuntu)"),
+        ("ubuntu-mate-desktop", "MATE (Ubuntu MATE)"),
+        ("plasma-desktop", "KDE Plasma"),
+        ("gnome-shell", "GNOME"),
+        ("xfce4", "Xfce"),
+        ("lxde-core", "LXDE"),
+    ];
+
+    for line in manifest.lines() {
+        let package = line.split_whitespace().next().unwrap_or("");
+        for (needle, label) in DESKTOPS {
+            if package == *needle {
+                return Some(label.to_string());
+            }
+        }
+    }
+
+    None
+}
+
+/// Read a single file out of a squashfs image, without extracting the
+/// whole filesystem.
+pub fn read_file(squashfs_path: &str, internal_path: &str) -> Result<Vec<u8>> {
+    let file = std::fs::File::open(squashfs_path)?;
+    let reader = backhand::FilesystemReader::from_reader(file)
+        .map_err(|e| WowUsbError::iso_processing(format!("Failed to open squashfs image: {}", e)))?;
+
+    let node = reader
+        .files()
+        .find(|node| node.fullpath.to_string_lossy().trim_start_matches('/') == internal_path.trim_start_matches('/'))
+        .ok_or_else(|| WowUsbError::iso_processing(format!("{} not found in squashfs image", internal_path)))?;
+
+    let file = node
+        .as_file()
+        .ok_or_else(|| WowUsbError::iso_processing(format!("{} is not a regular file", internal_path)))?;
+
+    let mut reader = reader.file(file).reader();
+    let mut contents = Vec::new();
+    reader
+        .read_to_end(&mut contents)
+        .map_err(|e| WowUsbError::iso_processing(format!("Failed to read {} from squashfs image: {}", internal_path, e)))?;
+
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quoted_os_release_fields() {
+        let contents = "NAME=\"Ubuntu\"\nVERSION_ID=\"24.04\"\n# comment\nID=ubuntu\n";
+        let fields = parse_os_release(contents);
+        assert_eq!(fields.get("NAME"), Some(&"Ubuntu".to_string()));
+        assert_eq!(fields.get("VERSION_ID"), Some(&"24.04".to_string()));
+        assert_eq!(fields.get("ID"), Some(&"ubuntu".to_string()));
+    }
+
+    #[test]
+    fn ignores_blank_and_comment_lines() {
+        let fields = parse_os_release("\n# just a comment\n");
+        assert!(fields.is_empty());
+    }
+
+    #[test]
+    fn detects_kubuntu_desktop() {
+        let manifest = "vim\t1.0\nkubuntu-desktop\t1:24.04\nfirefox\t126.0\n";
+        assert_eq!(desktop_environment_from_manifest(manifest), Some("KDE Plasma (Kubuntu)".to_string()));
+    }
+
+    #[test]
+    fn no_known_desktop_returns_none() {
+        assert_eq!(desktop_environment_from_manifest("busybox\t1.0\n"), None);
+    }
+}
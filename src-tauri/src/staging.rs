@@ -0,0 +1,77 @@
+use crate::error::{WowUsbError, Result};
+use std::path::{Path, PathBuf};
+
+/// Resolves the directory used to stage extracted ISO contents and WIM
+/// conversions, replacing the previously hardcoded `/tmp/wowusb_<pid>`
+/// (which also leaked into the Windows build of `IsoProcessor`).
+pub struct StagingDirectory {
+    root: PathBuf,
+}
+
+impl StagingDirectory {
+    /// Use `override_path` if given, otherwise the platform's proper temp
+    /// directory (`std::env::temp_dir()`, not a hardcoded Unix path).
+    pub fn resolve(override_path: Option<&str>) -> Self {
+        let root = match override_path {
+            Some(path) => PathBuf::from(path),
+            None => std::env::temp_dir(),
+        };
+
+        Self { root }
+    }
+
+    pub fn job_dir(&self, job_id: &str) -> PathBuf {
+        self.root.join(format!("wowusb_{}", job_id))
+    }
+
+    /// Ensure at least `required_bytes` are free on the filesystem backing
+    /// the staging root before a caller starts extracting or converting a
+    /// large image into it.
+    pub fn check_free_space(&self, required_bytes: u64) -> Result<()> {
+        let available = Self::available_bytes(&self.root)?;
+        if available < required_bytes {
+            return Err(WowUsbError::validation(format!(
+                "Staging directory {} has {} bytes free, but {} bytes are required",
+                self.root.display(),
+                available,
+                required_bytes
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn available_bytes(path: &Path) -> Result<u64> {
+        let stat = nix::sys::statvfs::statvfs(path)
+            .map_err(|e| WowUsbError::filesystem(format!("Failed to stat {}: {}", path.display(), e)))?;
+        Ok(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+    }
+
+    #[cfg(windows)]
+    fn available_bytes(path: &Path) -> Result<u64> {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::fileapi::GetDiskFreeSpaceExW;
+
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let mut free_bytes: u64 = 0;
+
+        let ok = unsafe {
+            GetDiskFreeSpaceExW(
+                wide.as_ptr(),
+                &mut free_bytes as *mut _ as *mut _,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+
+        if ok == 0 {
+            return Err(WowUsbError::filesystem(format!(
+                "Failed to query free space for {}",
+                path.display()
+            )));
+        }
+
+        Ok(free_bytes)
+    }
+}
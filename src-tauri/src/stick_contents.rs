@@ -0,0 +1,158 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// One file or directory in a listed stick's tree, with a hash for files so
+/// callers can diff two listings (e.g. to spot a write that silently
+/// dropped or corrupted a file) without re-reading both sticks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentEntry {
+    pub name: String,
+    pub relative_path: String,
+    pub is_dir: bool,
+    /// File size, or the sum of children's sizes for a directory.
+    pub size_bytes: u64,
+    /// `None` for directories.
+    pub sha256: Option<String>,
+    pub children: Vec<ContentEntry>,
+}
+
+/// Walk `root` (expected to be a read-only mounted stick, see
+/// [`crate::disk::DiskManager::list_usb_contents`]) into a [`ContentEntry`]
+/// tree with every file hashed. Hashing happens in a second pass over a
+/// flat file list rather than while recursing, so this stays a plain
+/// synchronous walk with no need to box recursive futures.
+pub async fn list_contents(root: &str) -> Result<ContentEntry> {
+    let root_path = Path::new(root);
+    let file_paths = collect_file_paths(root_path, "")?;
+
+    let mut hashes = HashMap::with_capacity(file_paths.len());
+    for (relative_path, absolute_path) in file_paths {
+        let hash = crate::hashing::sha256_file(&absolute_path.to_string_lossy()).await?;
+        hashes.insert(relative_path, hash);
+    }
+
+    build_entry(root_path, "", &hashes)
+}
+
+fn join_relative(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+fn collect_file_paths(path: &Path, relative_path: &str) -> Result<Vec<(String, PathBuf)>> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        let mut out = Vec::new();
+        let mut entries: Vec<_> = std::fs::read_dir(path)?.filter_map(|e| e.ok()).collect();
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let child_name = entry.file_name().to_string_lossy().to_string();
+            let child_relative = join_relative(relative_path, &child_name);
+            out.extend(collect_file_paths(&entry.path(), &child_relative)?);
+        }
+        Ok(out)
+    } else if metadata.is_file() {
+        Ok(vec![(relative_path.to_string(), path.to_path_buf())])
+    } else {
+        // Symlinks and other special files aren't meaningful on the FAT32/
+        // exFAT filesystems these sticks use; skip rather than fail.
+        Ok(Vec::new())
+    }
+}
+
+fn build_entry(path: &Path, relative_path: &str, hashes: &HashMap<String, String>) -> Result<ContentEntry> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| relative_path.to_string());
+    let metadata = std::fs::symlink_metadata(path)?;
+
+    if metadata.is_dir() {
+        let mut children = Vec::new();
+        let mut total_size = 0u64;
+        let mut entries: Vec<_> = std::fs::read_dir(path)?.filter_map(|e| e.ok()).collect();
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let child_name = entry.file_name().to_string_lossy().to_string();
+            let child_relative = join_relative(relative_path, &child_name);
+            let child = build_entry(&entry.path(), &child_relative, hashes)?;
+            total_size += child.size_bytes;
+            children.push(child);
+        }
+        Ok(ContentEntry {
+            name,
+            relative_path: relative_path.to_string(),
+            is_dir: true,
+            size_bytes: total_size,
+            sha256: None,
+            children,
+        })
+    } else {
+        Ok(ContentEntry {
+            name,
+            relative_path: relative_path.to_string(),
+            is_dir: false,
+            size_bytes: metadata.len(),
+            sha256: hashes.get(relative_path).cloned(),
+            children: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wowusb_stick_contents_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_file(dir: &Path, relative_path: &str, contents: &[u8]) {
+        let path = dir.join(relative_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[tokio::test]
+    async fn builds_tree_with_sizes_and_hashes() {
+        let root = temp_dir("tree");
+        write_file(&root, "readme.txt", b"hello");
+        write_file(&root, "sources/install.wim", b"install contents");
+
+        let tree = list_contents(root.to_str().unwrap()).await.unwrap();
+        assert!(tree.is_dir);
+        assert_eq!(tree.size_bytes, "hello".len() as u64 + "install contents".len() as u64);
+
+        let readme = tree.children.iter().find(|c| c.name == "readme.txt").unwrap();
+        assert!(!readme.is_dir);
+        assert_eq!(readme.size_bytes, 5);
+        assert!(readme.sha256.is_some());
+
+        let sources = tree.children.iter().find(|c| c.name == "sources").unwrap();
+        assert!(sources.is_dir);
+        let install = sources.children.iter().find(|c| c.name == "install.wim").unwrap();
+        assert_eq!(install.relative_path, "sources/install.wim");
+        assert!(install.sha256.is_some());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn empty_directory_has_no_children() {
+        let root = temp_dir("empty");
+
+        let tree = list_contents(root.to_str().unwrap()).await.unwrap();
+        assert!(tree.is_dir);
+        assert_eq!(tree.size_bytes, 0);
+        assert!(tree.children.is_empty());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,129 @@
+use crate::error::{Result, WowUsbError};
+use serde::Serialize;
+use std::io::Write;
+
+/// Everything gathered into a support bundle zip for attaching to a GitHub
+/// issue, so a maintainer can reproduce a failure without a back-and-forth
+/// asking the reporter for their environment.
+#[derive(Debug, Clone, Serialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub os_family: String,
+    pub arch: String,
+    pub app_version: String,
+}
+
+impl HostInfo {
+    pub fn collect() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            os_family: std::env::consts::FAMILY.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            app_version: crate::version::VERSION.to_string(),
+        }
+    }
+}
+
+/// A device entry stripped of anything identifying (serial numbers, exact
+/// mountpoints under a user's home directory) before it leaves the machine.
+#[derive(Debug, Clone, Serialize)]
+pub struct SanitizedDevice {
+    pub size: String,
+    pub model: String,
+    pub filesystem: Option<String>,
+    pub is_removable: bool,
+    pub is_usb: bool,
+    pub bus_type: Option<String>,
+}
+
+impl From<&crate::disk::Device> for SanitizedDevice {
+    fn from(device: &crate::disk::Device) -> Self {
+        Self {
+            size: device.size.clone(),
+            model: device.model.clone(),
+            filesystem: device.filesystem.clone(),
+            is_removable: device.is_removable,
+            is_usb: device.is_usb,
+            bus_type: device.bus_type.clone(),
+        }
+    }
+}
+
+/// Assembles a zip containing the session log, host info, tool versions,
+/// a sanitized device list, and the last operation report.
+pub struct SupportBundleBuilder {
+    session_log: String,
+    tool_versions: Vec<(String, String)>,
+    devices: Vec<SanitizedDevice>,
+    last_operation_report: Option<serde_json::Value>,
+}
+
+impl SupportBundleBuilder {
+    pub fn new(session_log: String) -> Self {
+        Self {
+            session_log,
+            tool_versions: Vec::new(),
+            devices: Vec::new(),
+            last_operation_report: None,
+        }
+    }
+
+    pub fn with_tool_version(mut self, tool: impl Into<String>, version: impl Into<String>) -> Self {
+        self.tool_versions.push((tool.into(), version.into()));
+        self
+    }
+
+    pub fn with_devices(mut self, devices: Vec<SanitizedDevice>) -> Self {
+        self.devices = devices;
+        self
+    }
+
+    pub fn with_last_operation_report(mut self, report: Option<serde_json::Value>) -> Self {
+        self.last_operation_report = report;
+        self
+    }
+
+    /// Write the assembled bundle to `output_path` as a zip file.
+    pub fn write_to(&self, output_path: &str) -> Result<()> {
+        let file = std::fs::File::create(output_path)?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        zip.start_file("session.log", options)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to write session.log: {}", e)))?;
+        zip.write_all(self.session_log.as_bytes())?;
+
+        let host_info = HostInfo::collect();
+        let host_info_json = serde_json::to_string_pretty(&host_info)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize host info: {}", e)))?;
+        zip.start_file("host_info.json", options)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to write host_info.json: {}", e)))?;
+        zip.write_all(host_info_json.as_bytes())?;
+
+        let tool_versions_json = serde_json::to_string_pretty(&self.tool_versions)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize tool versions: {}", e)))?;
+        zip.start_file("tool_versions.json", options)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to write tool_versions.json: {}", e)))?;
+        zip.write_all(tool_versions_json.as_bytes())?;
+
+        let devices_json = serde_json::to_string_pretty(&self.devices)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize device list: {}", e)))?;
+        zip.start_file("devices.json", options)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to write devices.json: {}", e)))?;
+        zip.write_all(devices_json.as_bytes())?;
+
+        if let Some(report) = &self.last_operation_report {
+            let report_json = serde_json::to_string_pretty(report)
+                .map_err(|e| WowUsbError::configuration(format!("Failed to serialize operation report: {}", e)))?;
+            zip.start_file("last_operation_report.json", options)
+                .map_err(|e| WowUsbError::configuration(format!("Failed to write last_operation_report.json: {}", e)))?;
+            zip.write_all(report_json.as_bytes())?;
+        }
+
+        zip.finish()
+            .map_err(|e| WowUsbError::configuration(format!("Failed to finalize support bundle: {}", e)))?;
+
+        Ok(())
+    }
+}
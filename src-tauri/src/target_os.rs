@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// What kind of system the destination stick is being built for.
+///
+/// Used to be threaded through as ad-hoc `&str`s (`"windows"`, `"linux"`,
+/// ...) compared with `.to_lowercase()` in [`crate::filesystem`] and
+/// [`crate::iso`]; a typo or unhandled platform string could silently fall
+/// through to the wrong branch. This enum makes the set of valid targets
+/// explicit and lets the compiler catch a missing match arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetOs {
+    Windows,
+    /// A bootable-but-not-installed Linux distro, e.g. a live or rescue ISO.
+    /// Kept as an alias for the frontend's existing `"linux"` value, which
+    /// doesn't yet distinguish live from installer media.
+    #[serde(alias = "linux")]
+    LinuxLive,
+    /// A Linux distro ISO meant to install onto another machine.
+    LinuxInstall,
+    MacOs,
+    /// Bit-for-bit image write, bypassing filesystem-aware extraction.
+    RawImage,
+    /// Multiple bootable entries chained together via a multiboot menu.
+    Multiboot,
+}
+
+impl TargetOs {
+    /// True for targets that expect a Linux-flavoured filesystem/bootloader.
+    pub fn is_linux(&self) -> bool {
+        matches!(self, TargetOs::LinuxLive | TargetOs::LinuxInstall)
+    }
+}
+
+impl std::fmt::Display for TargetOs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TargetOs::Windows => "windows",
+            TargetOs::LinuxLive => "linux_live",
+            TargetOs::LinuxInstall => "linux_install",
+            TargetOs::MacOs => "mac_os",
+            TargetOs::RawImage => "raw_image",
+            TargetOs::Multiboot => "multiboot",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_linux_string_deserializes_as_linux_live() {
+        let target: TargetOs = serde_json::from_str("\"linux\"").unwrap();
+        assert_eq!(target, TargetOs::LinuxLive);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        for target in [
+            TargetOs::Windows,
+            TargetOs::LinuxLive,
+            TargetOs::LinuxInstall,
+            TargetOs::MacOs,
+            TargetOs::RawImage,
+            TargetOs::Multiboot,
+        ] {
+            let json = serde_json::to_string(&target).unwrap();
+            assert_eq!(serde_json::from_str::<TargetOs>(&json).unwrap(), target);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Well-known locations an admin (or a NixOS/Homebrew packager) may drop a
+/// tool path override file, checked in order, mirroring
+/// [`crate::policy::DevicePolicy`]'s search paths.
+#[cfg(target_os = "windows")]
+const TOOL_PATHS_SEARCH_PATHS: &[&str] = &[r"C:\ProgramData\WowUSB\tool_paths.json"];
+
+#[cfg(target_os = "macos")]
+const TOOL_PATHS_SEARCH_PATHS: &[&str] = &["/Library/Application Support/WowUSB/tool_paths.json"];
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const TOOL_PATHS_SEARCH_PATHS: &[&str] = &["/etc/wowusb/tool_paths.json"];
+
+/// Overrides for the absolute paths (or alternate binary names) of external
+/// tools WowUSB shells out to (`7z`, `mkfs.*`, `grub-install`, ...), needed
+/// on systems like NixOS, Homebrew, a portable Windows install, or a Linux
+/// AppImage where these tools don't live on a bare `PATH` lookup.
+///
+/// Resolution order, checked by [`Self::resolve`]:
+/// 1. The `WOWUSB_TOOL_<NAME>` environment variable (dots and dashes in
+///    `<NAME>` become underscores, e.g. `mkfs.fat` -> `WOWUSB_TOOL_MKFS_FAT`).
+/// 2. This settings file, loaded once via [`Self::load`].
+/// 3. `$APPDIR/usr/bin/<tool>`, when running inside an AppImage (its
+///    runtime sets `APPDIR` to the mounted squashfs root before exec'ing
+///    the real binary).
+/// 4. `tools/<tool>` next to WowUSB's own executable, for a portable
+///    install that vendors its own copies instead of relying on the host
+///    having them.
+/// 5. The tool's bare name, resolved against `PATH` as before.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ToolPaths {
+    #[serde(flatten)]
+    overrides: HashMap<String, String>,
+}
+
+impl ToolPaths {
+    /// Load overrides from the first settings file found in the platform's
+    /// well-known locations, or an empty [`ToolPaths`] if none exists.
+    pub fn load() -> Result<Self> {
+        for candidate in TOOL_PATHS_SEARCH_PATHS {
+            let path = PathBuf::from(candidate);
+            if path.exists() {
+                let contents = std::fs::read_to_string(&path)?;
+                return serde_json::from_str(&contents)
+                    .map_err(|e| WowUsbError::configuration(format!("Invalid tool paths file {}: {}", candidate, e)));
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    /// Resolve the executable to invoke for `tool`, applying the
+    /// environment-variable, settings-file, AppImage, and vendored-tools
+    /// overrides in that order.
+    pub fn resolve(&self, tool: &str) -> String {
+        let env_var = format!("WOWUSB_TOOL_{}", tool.to_uppercase().replace(['.', '-'], "_"));
+        if let Ok(path) = std::env::var(&env_var) {
+            return path;
+        }
+
+        if let Some(path) = self.overrides.get(tool) {
+            return path.clone();
+        }
+
+        if let Some(path) = appdir_candidate(tool) {
+            return path;
+        }
+
+        if let Some(path) = vendored_candidate(tool) {
+            return path;
+        }
+
+        tool.to_string()
+    }
+}
+
+/// `$APPDIR/usr/bin/<tool>`, if `APPDIR` is set and the file actually
+/// exists there, so an AppImage build doesn't depend on the host having
+/// these tools installed at all.
+fn appdir_candidate(tool: &str) -> Option<String> {
+    let appdir = std::env::var("APPDIR").ok()?;
+    let candidate = PathBuf::from(appdir).join("usr/bin").join(tool);
+    candidate.exists().then(|| candidate.to_string_lossy().to_string())
+}
+
+/// `tools/<tool>` next to WowUSB's own executable, for a portable install
+/// that ships its own copies of `parted`/`mkfs.*`/`grub-install` alongside
+/// the binary instead of relying on distro packages.
+fn vendored_candidate(tool: &str) -> Option<String> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+    let candidate = exe_dir.join("tools").join(tool);
+    candidate.exists().then(|| candidate.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falls_back_to_bare_name_with_no_overrides() {
+        let paths = ToolPaths::default();
+        assert_eq!(paths.resolve("7z"), "7z");
+    }
+
+    #[test]
+    fn settings_file_override_takes_effect() {
+        let mut overrides = HashMap::new();
+        overrides.insert("grub-install".to_string(), "/run/current-system/sw/bin/grub-install".to_string());
+        let paths = ToolPaths { overrides };
+        assert_eq!(paths.resolve("grub-install"), "/run/current-system/sw/bin/grub-install");
+    }
+
+    #[test]
+    fn env_var_override_wins_over_settings_file() {
+        let mut overrides = HashMap::new();
+        overrides.insert("mkfs.fat".to_string(), "/settings/mkfs.fat".to_string());
+        let paths = ToolPaths { overrides };
+
+        std::env::set_var("WOWUSB_TOOL_MKFS_FAT", "/opt/homebrew/sbin/mkfs.fat");
+        assert_eq!(paths.resolve("mkfs.fat"), "/opt/homebrew/sbin/mkfs.fat");
+        std::env::remove_var("WOWUSB_TOOL_MKFS_FAT");
+    }
+
+    #[test]
+    fn resolves_tool_from_appdir_when_present() {
+        let appdir = std::env::temp_dir().join(format!("wowusb_appdir_test_{}", std::process::id()));
+        std::fs::create_dir_all(appdir.join("usr/bin")).unwrap();
+        std::fs::write(appdir.join("usr/bin/7z"), b"#!/bin/sh\n").unwrap();
+
+        std::env::set_var("APPDIR", &appdir);
+        let resolved = ToolPaths::default().resolve("7z");
+        std::env::remove_var("APPDIR");
+        std::fs::remove_dir_all(&appdir).ok();
+
+        assert_eq!(resolved, appdir.join("usr/bin/7z").to_string_lossy());
+    }
+
+    #[test]
+    fn ignores_appdir_when_tool_not_bundled() {
+        let appdir = std::env::temp_dir().join(format!("wowusb_appdir_empty_test_{}", std::process::id()));
+        std::fs::create_dir_all(appdir.join("usr/bin")).unwrap();
+
+        std::env::set_var("APPDIR", &appdir);
+        let resolved = ToolPaths::default().resolve("grub-install");
+        std::env::remove_var("APPDIR");
+        std::fs::remove_dir_all(&appdir).ok();
+
+        assert_eq!(resolved, "grub-install");
+    }
+}
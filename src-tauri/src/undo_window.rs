@@ -0,0 +1,59 @@
+use crate::error::{WowUsbError, Result};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::Duration;
+
+/// An optional grace period between the user confirming a job and the
+/// first destructive command actually running, giving them a last chance
+/// to abort after clicking through the confirmation dialog.
+pub struct UndoWindow {
+    grace_period: Duration,
+    aborted: Arc<Notify>,
+}
+
+impl UndoWindow {
+    pub fn new(grace_period_seconds: u64) -> Self {
+        Self {
+            grace_period: Duration::from_secs(grace_period_seconds),
+            aborted: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Signal the grace period to abort immediately.
+    pub fn abort(&self) {
+        self.aborted.notify_one();
+    }
+
+    /// Wait out the grace period, returning early with an error if `abort`
+    /// is called before it elapses.
+    pub async fn wait(&self) -> Result<()> {
+        tokio::select! {
+            _ = tokio::time::sleep(self.grace_period) => Ok(()),
+            _ = self.aborted.notified() => Err(WowUsbError::Cancelled),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wait_completes_once_the_grace_period_elapses() {
+        let window = UndoWindow::new(0);
+        assert!(window.wait().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn abort_interrupts_a_pending_wait() {
+        let window = Arc::new(UndoWindow::new(60));
+        let waiter = {
+            let window = window.clone();
+            tokio::spawn(async move { window.wait().await })
+        };
+
+        window.abort();
+        let result = waiter.await.expect("wait task panicked");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::{Result, WowUsbError};
+
+/// Parse human-entered size strings like `"123456789"`, `"123M"`, `"1.2G"`
+/// into a byte count. Pure and overflow-safe: values that would overflow
+/// `u64` once converted to bytes are rejected rather than silently
+/// wrapping or truncating.
+pub fn parse_size_string(size_str: &str) -> Result<u64> {
+    let size_str = size_str.trim().to_uppercase();
+    let invalid = || WowUsbError::validation(format!("Invalid size format: {}", size_str));
+
+    let (numeric_part, multiplier) = if let Some(stripped) = size_str.strip_suffix('G') {
+        (stripped, 1024.0 * 1024.0 * 1024.0)
+    } else if let Some(stripped) = size_str.strip_suffix('M') {
+        (stripped, 1024.0 * 1024.0)
+    } else if let Some(stripped) = size_str.strip_suffix('K') {
+        (stripped, 1024.0)
+    } else {
+        (size_str.as_str(), 1.0)
+    };
+
+    if multiplier == 1.0 {
+        return size_str.parse::<u64>().map_err(|_| invalid());
+    }
+
+    let value: f64 = numeric_part.parse().map_err(|_| invalid())?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(invalid());
+    }
+
+    let bytes = value * multiplier;
+    if bytes > u64::MAX as f64 {
+        return Err(invalid());
+    }
+
+    Ok(bytes as u64)
+}
+
+/// Render a byte count as a human-readable size (`"1.5 GB"`, `"512 B"`),
+/// capping at the largest unit we know about rather than overflowing into
+/// exponent notation for absurdly large inputs.
+pub fn format_size_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB", "PB", "EB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", bytes, UNITS[unit_index])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit_index])
+    }
+}
+
+/// Compute the end offset (in MB) of a fixed-size partition starting at
+/// `start_mb`, or `None` if `size_mb` is `0` (meaning "use the remaining
+/// space on the device" — the caller should emit `parted`'s `100%`
+/// sentinel in that case). Uses checked arithmetic so a corrupt or
+/// maliciously large `size_mb` fails cleanly instead of wrapping into a
+/// bogus, silently-truncated partition table.
+pub fn partition_end_mb(start_mb: u64, size_mb: u64) -> Option<u64> {
+    if size_mb == 0 {
+        return None;
+    }
+    start_mb.checked_add(size_mb)
+}
+
+/// Compute the start offset (in MB) of the partition that follows one
+/// starting at `start_mb` with size `size_mb`. Only meaningful when
+/// `size_mb != 0`, since a `0`-sized ("rest of disk") partition must be
+/// last in the layout.
+pub fn next_start_mb(start_mb: u64, size_mb: u64) -> Option<u64> {
+    start_mb.checked_add(size_mb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parses_plain_bytes() {
+        assert_eq!(parse_size_string("12345").unwrap(), 12345);
+    }
+
+    #[test]
+    fn parses_suffixed_sizes() {
+        assert_eq!(parse_size_string("1K").unwrap(), 1024);
+        assert_eq!(parse_size_string("1M").unwrap(), 1024 * 1024);
+        assert_eq!(parse_size_string("1G").unwrap(), 1024 * 1024 * 1024);
+        assert_eq!(parse_size_string("1.5G").unwrap(), (1.5 * 1024.0 * 1024.0 * 1024.0) as u64);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(parse_size_string("not-a-size").is_err());
+        assert!(parse_size_string("-5M").is_err());
+    }
+
+    #[test]
+    fn formats_zero_as_bytes() {
+        assert_eq!(format_size_bytes(0), "0 B");
+    }
+
+    #[test]
+    fn formats_caps_at_largest_unit() {
+        assert!(format_size_bytes(u64::MAX).ends_with("EB"));
+    }
+
+    #[test]
+    fn rest_of_disk_partition_has_no_end() {
+        assert_eq!(partition_end_mb(100, 0), None);
+    }
+
+    #[test]
+    fn end_overflow_is_rejected() {
+        assert_eq!(partition_end_mb(u64::MAX, 1), None);
+    }
+
+    proptest! {
+        #[test]
+        fn parse_size_string_never_panics(s in ".*") {
+            let _ = parse_size_string(&s);
+        }
+
+        #[test]
+        fn format_size_bytes_never_panics(bytes in any::<u64>()) {
+            let _ = format_size_bytes(bytes);
+        }
+
+        #[test]
+        fn partition_end_mb_is_start_plus_size_when_nonzero(start in any::<u64>(), size in 1u64..=u64::MAX) {
+            let result = partition_end_mb(start, size);
+            match start.checked_add(size) {
+                Some(expected) => prop_assert_eq!(result, Some(expected)),
+                None => prop_assert_eq!(result, None),
+            }
+        }
+
+        #[test]
+        fn partition_end_mb_zero_size_means_rest_of_disk(start in any::<u64>()) {
+            prop_assert_eq!(partition_end_mb(start, 0), None);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::{Result, WowUsbError};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Ed25519 public key (hex-encoded) that release manifests are signed
+/// with. The matching private key is held offline by the release process;
+/// rotating it means shipping a new build with the new key baked in here,
+/// the same way a browser ships pinned CA certificates.
+const RELEASE_SIGNING_PUBLIC_KEY_HEX: &str = "197f6b23e16c8532c6abc838facd5ea789be0c76b2920334039bfa8b3d368d61";
+
+/// Which release stream to check for updates against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+}
+
+/// Signed metadata describing an available app or asset version, published
+/// alongside a detached signature so a compromised CDN can't push a
+/// malicious "update" past a client that verifies it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseMetadata {
+    pub version: String,
+    pub download_url: String,
+    pub sha256: String,
+    pub signature: String,
+    pub channel: UpdateChannel,
+}
+
+/// Result of comparing the running app (or a bundled asset) against the
+/// published metadata for its channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCheckResult {
+    pub current_version: String,
+    pub latest: Option<ReleaseMetadata>,
+    pub update_available: bool,
+}
+
+/// Bundled, versioned assets (bootloader binaries, the ISO catalog) that
+/// are refreshed independently of app releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundledAsset {
+    Bootloader,
+    IsoCatalog,
+    IsoQuirkRules,
+}
+
+pub struct UpdateChecker {
+    manifest_url_base: String,
+}
+
+impl UpdateChecker {
+    pub fn new(manifest_url_base: impl Into<String>) -> Self {
+        Self {
+            manifest_url_base: manifest_url_base.into(),
+        }
+    }
+
+    /// Fetch and verify the release metadata for `channel`, then compare it
+    /// against `current_version`.
+    pub async fn check_app_update(&self, current_version: &str, channel: UpdateChannel) -> Result<UpdateCheckResult> {
+        let metadata = self.fetch_release_metadata(channel).await?;
+        self.verify_signature(&metadata)?;
+
+        let update_available = metadata.version != current_version;
+
+        Ok(UpdateCheckResult {
+            current_version: current_version.to_string(),
+            latest: Some(metadata),
+            update_available,
+        })
+    }
+
+    /// Fetch and verify the release metadata for a bundled asset, so
+    /// bootloader binaries and the ISO catalog can be kept fresh separately
+    /// from app releases.
+    pub async fn check_asset_update(&self, asset: BundledAsset, current_version: &str, channel: UpdateChannel) -> Result<UpdateCheckResult> {
+        let url = format!("{}/assets/{}", self.manifest_url_base, Self::asset_path(asset));
+        let metadata = self.fetch_metadata_from(&url).await?;
+        self.verify_signature(&metadata)?;
+
+        let update_available = metadata.version != current_version;
+
+        Ok(UpdateCheckResult {
+            current_version: current_version.to_string(),
+            latest: Some(metadata),
+            update_available,
+        })
+    }
+
+    fn asset_path(asset: BundledAsset) -> &'static str {
+        match asset {
+            BundledAsset::Bootloader => "bootloader.json",
+            BundledAsset::IsoCatalog => "iso_catalog.json",
+            BundledAsset::IsoQuirkRules => "iso_quirk_rules.json",
+        }
+    }
+
+    async fn fetch_release_metadata(&self, channel: UpdateChannel) -> Result<ReleaseMetadata> {
+        let channel_path = match channel {
+            UpdateChannel::Stable => "stable.json",
+            UpdateChannel::Beta => "beta.json",
+        };
+        let url = format!("{}/{}", self.manifest_url_base, channel_path);
+        self.fetch_metadata_from(&url).await
+    }
+
+    async fn fetch_metadata_from(&self, url: &str) -> Result<ReleaseMetadata> {
+        let response = reqwest::get(url)
+            .await
+            .map_err(|e| WowUsbError::device_operation(format!("Failed to fetch update metadata: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(WowUsbError::device_operation(format!(
+                "Update metadata endpoint returned {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json::<ReleaseMetadata>()
+            .await
+            .map_err(|e| WowUsbError::configuration(format!("Invalid update metadata: {}", e)))
+    }
+
+    /// Verify `metadata.signature` is a valid Ed25519 signature over
+    /// `metadata.sha256`, made by [`RELEASE_SIGNING_PUBLIC_KEY_HEX`]. This
+    /// is what actually stops a compromised CDN from pushing a malicious
+    /// "update" — a mismatched or garbage signature is rejected outright,
+    /// not just an empty one.
+    fn verify_signature(&self, metadata: &ReleaseMetadata) -> Result<()> {
+        let key_bytes: [u8; 32] = hex::decode(RELEASE_SIGNING_PUBLIC_KEY_HEX)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| WowUsbError::configuration("Malformed release signing public key".to_string()))?;
+        let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+            .map_err(|e| WowUsbError::configuration(format!("Invalid release signing public key: {}", e)))?;
+
+        let signature_bytes: [u8; 64] = hex::decode(&metadata.signature)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| WowUsbError::validation("Update metadata signature is malformed".to_string()))?;
+        let signature = Signature::from_bytes(&signature_bytes);
+
+        verifying_key
+            .verify(metadata.sha256.as_bytes(), &signature)
+            .map_err(|_| WowUsbError::validation("Update metadata failed signature verification".to_string()))
+    }
+}
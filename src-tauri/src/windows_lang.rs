@@ -0,0 +1,97 @@
+use crate::error::{Result, WowUsbError};
+use tokio::process::Command as AsyncCommand;
+
+/// Language resources available in a mounted Windows image, as reported by
+/// `dism /Get-Intl` (Windows hosts) or `wimlib-imagex info` (elsewhere).
+pub fn languages_from_dism_intl_listing(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split(':').nth(1))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Selects which language resources to retain on a mounted Windows image,
+/// removing the rest to save space on small sticks.
+pub struct LanguageSelector;
+
+impl LanguageSelector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// List languages present in the image at `wim_path`/`image_index`.
+    pub async fn list_available(&self, wim_path: &str, image_index: u32) -> Result<Vec<String>> {
+        let output = AsyncCommand::new("dism")
+            .args(&[
+                "/image:mounted",
+                &format!("/wimfile:{}", wim_path),
+                &format!("/index:{}", image_index),
+                "/get-intl",
+            ])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(format!(
+                "Failed to list languages: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        Ok(languages_from_dism_intl_listing(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Remove every installed language pack not in `keep_languages`
+    /// (BCP-47 tags, e.g. `"en-US"`).
+    pub async fn apply_selection(&self, wim_path: &str, image_index: u32, keep_languages: &[String]) -> Result<()> {
+        let available = self.list_available(wim_path, image_index).await?;
+
+        for language in available.iter().filter(|lang| !keep_languages.contains(lang)) {
+            let output = AsyncCommand::new("dism")
+                .args(&[
+                    "/image:mounted",
+                    &format!("/wimfile:{}", wim_path),
+                    &format!("/index:{}", image_index),
+                    "/remove-package",
+                    &format!("/packagename:Microsoft-Windows-Client-LanguagePack-Package~31bf3856ad364e35~amd64~{}~", language),
+                ])
+                .output()
+                .await?;
+
+            if !output.status.success() {
+                return Err(WowUsbError::iso_processing(format!(
+                    "Failed to remove language pack {}: {}",
+                    language,
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for LanguageSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dism_intl_listing() {
+        let output = "Current system UI language : en-US\nInstalled language(s): en-US\nde-DE\n";
+        let languages = languages_from_dism_intl_listing(output);
+        assert!(languages.contains(&"en-US".to_string()));
+    }
+
+    #[test]
+    fn empty_listing_yields_no_languages() {
+        assert!(languages_from_dism_intl_listing("").is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Windows Setup's edition/channel selection file, written to
+/// `sources/ei.cfg` on the media to skip the "select edition" prompt.
+/// Format is a fixed three-key INI documented by Microsoft's deployment
+/// tooling; unset fields fall back to Setup's own defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EiConfig {
+    /// e.g. "Professional", "Core", "ServerStandard".
+    pub edition_id: Option<String>,
+    /// 0 = retail, 1 = OEM, 2 = volume license.
+    pub channel: Option<EiChannel>,
+    pub vl_edition: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EiChannel {
+    Retail,
+    Oem,
+    Volume,
+}
+
+impl EiChannel {
+    fn as_code(self) -> &'static str {
+        match self {
+            EiChannel::Retail => "Retail",
+            EiChannel::Oem => "OEM",
+            EiChannel::Volume => "Volume",
+        }
+    }
+}
+
+impl EiConfig {
+    pub fn render(&self) -> String {
+        format!(
+            "[EditionID]\n{}\n[Channel]\n{}\n[VL]\n{}\n",
+            self.edition_id.as_deref().unwrap_or(""),
+            self.channel.map(EiChannel::as_code).unwrap_or(""),
+            if self.vl_edition.unwrap_or(false) { "1" } else { "0" },
+        )
+    }
+
+    /// Write `sources/ei.cfg` under `windows_partition_root`, creating the
+    /// `sources` directory if the extraction hasn't populated it yet.
+    pub fn write_to(&self, windows_partition_root: impl AsRef<Path>) -> Result<PathBuf> {
+        let sources_dir = windows_partition_root.as_ref().join("sources");
+        std::fs::create_dir_all(&sources_dir)?;
+        let path = sources_dir.join("ei.cfg");
+        std::fs::write(&path, self.render())?;
+        Ok(path)
+    }
+}
+
+/// Preset product key file read by Windows Setup from the root of the
+/// media, letting enterprises skip the product key prompt entirely.
+pub fn write_pid_txt(windows_partition_root: impl AsRef<Path>, product_key: &str) -> Result<PathBuf> {
+    let path = windows_partition_root.as_ref().join("PID.txt");
+    std::fs::write(&path, format!("[PID]\nValue={}\n", product_key))?;
+    Ok(path)
+}
+
+/// Copy a user-provided `$OEM$` payload (`SetupComplete.cmd`, unattend
+/// assets, bundled installers) into `sources/$OEM$` on the Windows
+/// partition, the standard mechanism Setup uses for post-install
+/// customization. `sync_policy` controls when copied files are flushed to
+/// the stick rather than left in the OS page cache; see
+/// [`crate::write_cache::SyncPolicy`].
+pub fn inject_oem_folder(
+    windows_partition_root: impl AsRef<Path>,
+    oem_source_dir: impl AsRef<Path>,
+    sync_policy: crate::write_cache::SyncPolicy,
+) -> Result<PathBuf> {
+    let dest = windows_partition_root.as_ref().join("sources").join("$OEM$");
+    let mut scheduler = crate::write_cache::SyncScheduler::new(sync_policy);
+    copy_dir_recursive(oem_source_dir.as_ref(), &dest, &mut scheduler)?;
+    Ok(dest)
+}
+
+fn copy_dir_recursive(source: &Path, dest: &Path, scheduler: &mut crate::write_cache::SyncScheduler) -> Result<()> {
+    std::fs::create_dir_all(dest)?;
+
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let entry_dest = dest.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &entry_dest, scheduler)?;
+        } else {
+            let bytes_copied = std::fs::copy(entry.path(), &entry_dest)?;
+            let should_sync = scheduler.on_bytes_written(bytes_copied) || scheduler.on_file_complete();
+            if should_sync {
+                std::fs::File::open(&entry_dest)?.sync_all()?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_retail_edition() {
+        let cfg = EiConfig {
+            edition_id: Some("Professional".to_string()),
+            channel: Some(EiChannel::Retail),
+            vl_edition: Some(false),
+        };
+
+        let rendered = cfg.render();
+        assert!(rendered.contains("[EditionID]\nProfessional"));
+        assert!(rendered.contains("[Channel]\nRetail"));
+        assert!(rendered.contains("[VL]\n0"));
+    }
+
+    #[test]
+    fn write_pid_txt_writes_expected_format() {
+        let dir = std::env::temp_dir().join(format!("wowusb_pid_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        write_pid_txt(&dir, "AAAAA-BBBBB-CCCCC-DDDDD-EEEEE").unwrap();
+        let contents = std::fs::read_to_string(dir.join("PID.txt")).unwrap();
+        assert_eq!(contents, "[PID]\nValue=AAAAA-BBBBB-CCCCC-DDDDD-EEEEE\n");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn inject_oem_folder_copies_nested_files() {
+        let base = std::env::temp_dir().join(format!("wowusb_oem_test_{}", std::process::id()));
+        let source = base.join("source");
+        let target = base.join("target");
+        std::fs::create_dir_all(source.join("nested")).unwrap();
+        std::fs::write(source.join("SetupComplete.cmd"), "echo hi").unwrap();
+        std::fs::write(source.join("nested").join("script.ps1"), "Write-Host hi").unwrap();
+
+        inject_oem_folder(&target, &source, crate::write_cache::SyncPolicy::AtEnd).unwrap();
+
+        let oem_dir = target.join("sources").join("$OEM$");
+        assert!(oem_dir.join("SetupComplete.cmd").exists());
+        assert!(oem_dir.join("nested").join("script.ps1").exists());
+
+        std::fs::remove_dir_all(&base).ok();
+    }
+}
@@ -0,0 +1,143 @@
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command as AsyncCommand;
+
+/// A target hardware family for a Windows To Go image, selecting which
+/// extra drivers and registry tweaks get injected. Bare Windows install
+/// images frequently lack the USB-boot-critical drivers a specific piece
+/// of hardware needs (NVMe/USB3 controllers, Surface's integrated
+/// peripherals, a hypervisor's paravirtualized devices), so booting on
+/// anything but the machine the image was captured on can fail outright.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HardwareProfile {
+    #[default]
+    Generic,
+    IntelNuc,
+    Surface,
+    Vm,
+}
+
+impl HardwareProfile {
+    /// Driver package directories to add via `dism /add-driver`, named
+    /// relative to the app's bundled resources, mirroring
+    /// [`crate::grub_tooling::BUNDLED_I386_PC_MODULES_DIR`]'s convention of
+    /// a resource-relative rather than absolute path.
+    pub fn driver_packages(self) -> &'static [&'static str] {
+        match self {
+            HardwareProfile::Generic => &[],
+            HardwareProfile::IntelNuc => &["wintogo-drivers/intel-nuc/usb3", "wintogo-drivers/intel-nuc/nvme"],
+            HardwareProfile::Surface => &["wintogo-drivers/surface/touch", "wintogo-drivers/surface/wifi"],
+            HardwareProfile::Vm => &["wintogo-drivers/vm/virtio", "wintogo-drivers/vm/vmware-tools"],
+        }
+    }
+
+    /// Offline `SYSTEM` hive tweaks (registry key path relative to the
+    /// hive root, value name, `REG_DWORD` value) that get the matching
+    /// boot-critical service starting early enough on this hardware.
+    pub fn registry_tweaks(self) -> &'static [(&'static str, &'static str, u32)] {
+        match self {
+            HardwareProfile::Generic => &[],
+            HardwareProfile::IntelNuc => &[(r"ControlSet001\Services\iaStorAVC", "Start", 0)],
+            HardwareProfile::Surface => &[(r"ControlSet001\Services\dwmcsmSurf", "Start", 0)],
+            HardwareProfile::Vm => &[(r"ControlSet001\Services\vioscsi", "Start", 0)],
+        }
+    }
+}
+
+/// Injects `profile`'s drivers (via DISM, mirroring
+/// [`crate::slipstream::UpdateSlipstreamer`]'s image-mounted invocation
+/// style) and registry tweaks (via `reg.exe load`/`add`/`unload` against
+/// the offline `SYSTEM` hive) into a mounted Windows image. A no-op for
+/// [`HardwareProfile::Generic`].
+pub async fn apply_profile(profile: HardwareProfile, mounted_image_path: &str) -> Result<()> {
+    for driver_dir in profile.driver_packages() {
+        let output = AsyncCommand::new("dism")
+            .args(&[
+                &format!("/image:{}", mounted_image_path),
+                "/add-driver",
+                &format!("/driver:{}", driver_dir),
+                "/recurse",
+            ])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Err(WowUsbError::iso_processing(format!(
+                "DISM failed to add driver {}: {}",
+                driver_dir,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+    }
+
+    if !profile.registry_tweaks().is_empty() {
+        apply_registry_tweaks(profile, mounted_image_path).await?;
+    }
+
+    Ok(())
+}
+
+async fn apply_registry_tweaks(profile: HardwareProfile, mounted_image_path: &str) -> Result<()> {
+    const HIVE_KEY: &str = r"HKLM\WOWUSB_WINTOGO_SYSTEM";
+    let hive_path = format!(r"{}\Windows\System32\config\SYSTEM", mounted_image_path);
+
+    let load = AsyncCommand::new("reg")
+        .args(&["load", HIVE_KEY, &hive_path])
+        .output()
+        .await?;
+    if !load.status.success() {
+        return Err(WowUsbError::iso_processing(format!(
+            "Failed to load offline SYSTEM hive at {}: {}",
+            hive_path,
+            String::from_utf8_lossy(&load.stderr)
+        )));
+    }
+
+    for (key_path, value_name, value) in profile.registry_tweaks() {
+        let output = AsyncCommand::new("reg")
+            .args(&[
+                "add",
+                &format!(r"{}\{}", HIVE_KEY, key_path),
+                "/v",
+                value_name,
+                "/t",
+                "REG_DWORD",
+                "/d",
+                &value.to_string(),
+                "/f",
+            ])
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            AsyncCommand::new("reg").args(&["unload", HIVE_KEY]).output().await.ok();
+            return Err(WowUsbError::iso_processing(format!(
+                "Failed to set {}\\{}: {}",
+                key_path,
+                value_name,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+    }
+
+    AsyncCommand::new("reg").args(&["unload", HIVE_KEY]).output().await.ok();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic_profile_has_no_drivers_or_tweaks() {
+        assert!(HardwareProfile::Generic.driver_packages().is_empty());
+        assert!(HardwareProfile::Generic.registry_tweaks().is_empty());
+    }
+
+    #[test]
+    fn hardware_profiles_name_distinct_driver_sets() {
+        assert_ne!(HardwareProfile::IntelNuc.driver_packages(), HardwareProfile::Surface.driver_packages());
+        assert_ne!(HardwareProfile::Surface.driver_packages(), HardwareProfile::Vm.driver_packages());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::error::{Result, WowUsbError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// A record that a device was wiped, suitable for asset-disposal compliance
+/// workflows that expect a paper (or PDF) trail: what was erased, how, by
+/// whom, and when.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipeCertificate {
+    pub device_model: String,
+    pub device_serial: Option<String>,
+    pub method: String,
+    pub passes: u32,
+    pub performed_at: chrono::DateTime<chrono::Utc>,
+    pub operator: Option<String>,
+    /// SHA-256 digest over the fields above, so a tampered certificate is
+    /// detectable. Deliberately not called a signature: there is no key
+    /// involved, so anyone can recompute a matching digest for an altered
+    /// certificate. This proves internal consistency (the certificate
+    /// wasn't edited after generation), not who generated it or that it's
+    /// authentic — don't present it to a compliance auditor as either.
+    pub content_digest: String,
+}
+
+impl WipeCertificate {
+    pub fn new(
+        device_model: impl Into<String>,
+        device_serial: Option<String>,
+        method: impl Into<String>,
+        passes: u32,
+        operator: Option<String>,
+    ) -> Self {
+        let mut certificate = Self {
+            device_model: device_model.into(),
+            device_serial,
+            method: method.into(),
+            passes,
+            performed_at: chrono::Utc::now(),
+            operator,
+            content_digest: String::new(),
+        };
+        certificate.content_digest = certificate.compute_digest();
+        certificate
+    }
+
+    fn compute_digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.device_model.as_bytes());
+        hasher.update(self.device_serial.as_deref().unwrap_or("").as_bytes());
+        hasher.update(self.method.as_bytes());
+        hasher.update(self.passes.to_le_bytes());
+        hasher.update(self.performed_at.to_rfc3339().as_bytes());
+        hasher.update(self.operator.as_deref().unwrap_or("").as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Whether [`Self::content_digest`] still matches the other fields,
+    /// i.e. the certificate hasn't been edited since it was generated.
+    /// Not an authenticity check — see the field's doc comment.
+    pub fn digest_matches(&self) -> bool {
+        self.content_digest == self.compute_digest()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to serialize wipe certificate: {}", e)))
+    }
+
+    pub fn write_json_to(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = path.as_ref().to_path_buf();
+        std::fs::write(&path, self.to_json()?)?;
+        Ok(path)
+    }
+
+    /// Render a one-page PDF summarizing the certificate, for attaching to
+    /// an asset-disposal report.
+    pub fn render_pdf(&self) -> Result<Vec<u8>> {
+        use printpdf::{BuiltinFont, Mm, PdfDocument};
+
+        let (doc, page, layer) = PdfDocument::new("WowUSB Wipe Certificate", Mm(210.0), Mm(297.0), "Layer 1");
+        let font = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| WowUsbError::configuration(format!("Failed to load PDF font: {}", e)))?;
+        let current_layer = doc.get_page(page).get_layer(layer);
+
+        let lines = [
+            "Certificate of Data Destruction".to_string(),
+            String::new(),
+            format!("Device model: {}", self.device_model),
+            format!("Device serial: {}", self.device_serial.as_deref().unwrap_or("unknown")),
+            format!("Method: {}", self.method),
+            format!("Passes: {}", self.passes),
+            format!("Performed at: {}", self.performed_at.to_rfc3339()),
+            format!("Operator: {}", self.operator.as_deref().unwrap_or("unknown")),
+            String::new(),
+            format!("Content digest (SHA-256): {}", self.content_digest),
+        ];
+
+        let mut y = Mm(270.0);
+        for line in lines {
+            current_layer.use_text(line, 12.0, Mm(20.0), y, &font);
+            y -= Mm(10.0);
+        }
+
+        doc.save_to_bytes()
+            .map_err(|e| WowUsbError::configuration(format!("Failed to render wipe certificate PDF: {}", e)))
+    }
+
+    pub fn write_pdf_to(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let path = path.as_ref().to_path_buf();
+        std::fs::write(&path, self.render_pdf()?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_matches_and_detects_tampering() {
+        let mut certificate = WipeCertificate::new(
+            "SanDisk Ultra".to_string(),
+            Some("SN12345".to_string()),
+            "wipefs --all".to_string(),
+            1,
+            Some("alice".to_string()),
+        );
+        assert!(certificate.digest_matches());
+
+        certificate.method = "tampered".to_string();
+        assert!(!certificate.digest_matches());
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let certificate = WipeCertificate::new("Kingston DataTraveler", None, "wipefs --all", 3, None);
+        let json = certificate.to_json().unwrap();
+        let parsed: WipeCertificate = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.device_model, certificate.device_model);
+        assert_eq!(parsed.content_digest, certificate.content_digest);
+    }
+}
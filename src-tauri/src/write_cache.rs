@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/// When to flush written data to the underlying device, rather than
+/// leaving it in the OS page cache. Letting the OS decide (the previous,
+/// implicit behavior) is what made progress bars lie: the copy step
+/// reports 100% the moment userspace hands bytes to the kernel, while the
+/// actual flush to a slow stick can still take minutes at unmount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPolicy {
+    /// Flush after every file finishes writing. Slowest, but progress and
+    /// reality never diverge by more than one file.
+    PerFile,
+    /// Flush after roughly this many megabytes have been written since the
+    /// last flush.
+    PerMegabytes(u64),
+    /// Only flush once, after everything has been written (closest to the
+    /// previous OS-default behavior).
+    AtEnd,
+}
+
+impl Default for SyncPolicy {
+    fn default() -> Self {
+        SyncPolicy::AtEnd
+    }
+}
+
+/// Tracks bytes written since the last flush and decides, per
+/// [`SyncPolicy`], when the caller should flush next. Pure bookkeeping —
+/// callers still have to actually call `sync_all()` (or platform
+/// equivalent) themselves when this says to.
+#[derive(Debug, Clone)]
+pub struct SyncScheduler {
+    policy: SyncPolicy,
+    bytes_since_sync: u64,
+}
+
+impl SyncScheduler {
+    pub fn new(policy: SyncPolicy) -> Self {
+        Self { policy, bytes_since_sync: 0 }
+    }
+
+    /// Record that `bytes` were just written to the current file. Returns
+    /// whether the caller should flush now under [`SyncPolicy::PerMegabytes`].
+    pub fn on_bytes_written(&mut self, bytes: u64) -> bool {
+        self.bytes_since_sync += bytes;
+        match self.policy {
+            SyncPolicy::PerMegabytes(threshold_mb) if threshold_mb > 0 => {
+                if self.bytes_since_sync >= threshold_mb * 1024 * 1024 {
+                    self.bytes_since_sync = 0;
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the caller should flush now that a whole file has finished
+    /// writing, under [`SyncPolicy::PerFile`].
+    pub fn on_file_complete(&mut self) -> bool {
+        match self.policy {
+            SyncPolicy::PerFile => {
+                self.bytes_since_sync = 0;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the caller should do a final flush now that the whole
+    /// operation has finished. Always true, since every policy needs at
+    /// least one flush by the end.
+    pub fn on_finished(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_file_syncs_on_every_file() {
+        let mut scheduler = SyncScheduler::new(SyncPolicy::PerFile);
+        assert!(!scheduler.on_bytes_written(1024));
+        assert!(scheduler.on_file_complete());
+    }
+
+    #[test]
+    fn per_megabytes_syncs_once_threshold_crossed() {
+        let mut scheduler = SyncScheduler::new(SyncPolicy::PerMegabytes(1));
+        assert!(!scheduler.on_bytes_written(512 * 1024));
+        assert!(scheduler.on_bytes_written(600 * 1024));
+        assert!(!scheduler.on_file_complete());
+    }
+
+    #[test]
+    fn at_end_never_syncs_early() {
+        let mut scheduler = SyncScheduler::new(SyncPolicy::AtEnd);
+        assert!(!scheduler.on_bytes_written(10 * 1024 * 1024));
+        assert!(!scheduler.on_file_complete());
+        assert!(scheduler.on_finished());
+    }
+}